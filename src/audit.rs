@@ -0,0 +1,54 @@
+//! # Audit Log Module
+//!
+//! This module keeps an audit trail of conversions, separate from the history.
+//! When `audit_log` is set in the config file, every conversion that is saved to
+//! the history is also appended to that file as one JSON record per line. The
+//! audit log is only ever appended to: `history clear` and `history normalize`
+//! leave it untouched, and no command rewrites it.
+
+use crate::history::HistoryRecord;
+use anyhow::{Result, bail};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+/// Appends conversion records to the audit log.
+///
+/// The parent directory is created if needed, and all records are written at
+/// once, so that an interrupted write cuts off at most the last line.
+///
+/// ## Arguments
+///
+/// * `path` - The path of the audit log (see `Config::audit_log`).
+/// * `records` - The records to append, each with its timestamp.
+///
+/// ## Returns
+///
+/// An `anyhow::Result` which is an error if the audit log cannot be written.
+///
+pub fn append(path: &Path, records: &[HistoryRecord]) -> Result<()> {
+    let mut data: String = String::new();
+    for record in records.iter() {
+        data.push_str(&serde_json::to_string(record)?);
+        data.push('\n');
+    }
+
+    let written: std::io::Result<()> = (|| {
+        if let Some(parent) = path.parent()
+            && !parent.as_os_str().is_empty()
+        {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file: fs::File = OpenOptions::new().create(true).append(true).open(path)?;
+        return file.write_all(data.as_bytes());
+    })();
+    if let Err(e) = written {
+        bail!(format!(
+            "Error: [ERROR] Could not write audit log '{}': {}.",
+            path.display(),
+            e
+        ));
+    }
+
+    return Ok(());
+}
@@ -0,0 +1,146 @@
+//! # Bulk Conversion Module
+//!
+//! This module implements `convert --from-json`. A JSON array of conversion
+//! requests is read from a file or standard input, every request is converted,
+//! and a JSON array with one result per request, in the same order, is written
+//! back. A request that fails (e.g., because of an unknown unit or a missing
+//! field) gets an `error` in its result instead of aborting the others.
+
+use crate::converter::convert;
+use crate::units::{ParseOptions, Unit, UnitType, get_enum_with};
+use anyhow::{Result, bail};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+
+/// A conversion request, e.g. `{"from":"celsius","to":"fahrenheit","value":100}`.
+#[derive(Deserialize)]
+pub struct ConvertRequest {
+    /// The source unit, parsed like `--from`.
+    pub from: String,
+    /// The target unit, parsed like `--to`.
+    pub to: String,
+    /// The value to convert.
+    pub value: f64,
+}
+
+/// The outcome of one conversion request.
+///
+/// Fields that could not be read from the request are left out, as is either
+/// `result` or `error`.
+#[derive(Serialize, Default)]
+pub struct ConvertResult {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<f64>,
+    /// The converted value, if the conversion succeeded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<f64>,
+    /// Why the conversion failed, without the "Error: [ERROR]" prefix.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Converts every request of a JSON array.
+///
+/// ## Arguments
+///
+/// * `path` - The file holding the array, or `-` for standard input.
+/// * `options` - The `ParseOptions` used for the units of every request.
+///
+/// ## Returns
+///
+/// A `Result<String>` with the JSON array of results, or an error if the input
+/// cannot be read or is not a JSON array.
+///
+pub fn run(path: &Path, options: &ParseOptions) -> Result<String> {
+    let mut input: String = String::new();
+    let read: io::Result<()> = match path.as_os_str() == "-" {
+        true => io::stdin().read_to_string(&mut input).map(|_| ()),
+        false => fs::read_to_string(path).map(|content| input = content),
+    };
+    if let Err(e) = read {
+        bail!(format!(
+            "Error: [ERROR] Could not read conversion requests from '{}': {}.",
+            path.display(),
+            e
+        ));
+    }
+
+    let items: Vec<serde_json::Value> = match serde_json::from_str(&input) {
+        Ok(items) => items,
+        Err(e) => bail!(format!(
+            "Error: [ERROR] The conversion requests in '{}' are not a JSON array: {}.",
+            path.display(),
+            e
+        )),
+    };
+    let results: Vec<ConvertResult> = items
+        .into_iter()
+        .map(
+            |item| match serde_json::from_value::<ConvertRequest>(item) {
+                Ok(request) => convert_request(&request, options),
+                Err(e) => ConvertResult {
+                    error: Some(format!("Invalid request: {}.", e)),
+                    ..ConvertResult::default()
+                },
+            },
+        )
+        .collect();
+
+    return Ok(serde_json::to_string(&results)?);
+}
+
+/// Converts a single request.
+///
+/// ## Arguments
+///
+/// * `request` - The `ConvertRequest` to convert.
+/// * `options` - The `ParseOptions` used for its units.
+///
+/// ## Returns
+///
+/// A `ConvertResult` with either the converted value or the error, which is
+/// also given for a result that overflows to infinity.
+///
+pub fn convert_request(request: &ConvertRequest, options: &ParseOptions) -> ConvertResult {
+    let converted: Result<f64> = (|| {
+        let from: Unit = get_enum_with(request.from.clone(), UnitType::Source, options)?;
+        let target_options: ParseOptions = ParseOptions {
+            dimension: Some(from.dimension()),
+            ..*options
+        };
+        let to: Unit = get_enum_with(request.to.clone(), UnitType::Target, &target_options)?;
+        let result: f64 = convert(&from, &to, &request.value)?;
+        if !result.is_finite() {
+            bail!(format!(
+                "Error: [ERROR] Converting {:e} {} to {} overflows to infinity. Use a larger target unit or a smaller value.",
+                request.value, from, to
+            ));
+        }
+        return Ok(result);
+    })();
+
+    let (result, error): (Option<f64>, Option<String>) = match converted {
+        Ok(result) => (Some(result), None),
+        Err(e) => (
+            None,
+            Some(
+                e.to_string()
+                    .trim_start_matches("Error: [ERROR] ")
+                    .to_string(),
+            ),
+        ),
+    };
+    return ConvertResult {
+        from: Some(request.from.clone()),
+        to: Some(request.to.clone()),
+        value: Some(request.value),
+        result,
+        error,
+    };
+}
@@ -3,39 +3,820 @@
 //! This module defines the structure of the command-line interface using the `clap` crate.
 //! It specifies the main commands (`convert`, `list`, `history`) and their arguments.
 
-use clap::{Parser, Subcommand, arg, command};
+use crate::locale::{Charset, Language};
+use crate::units::{UnitDimension, UnitSystem};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use std::path::PathBuf;
 
 /// The main command-line interface structure.
 #[derive(Parser)]
 #[command(
-    about = "\
-        Unit Converter\n\n\
-        A terminal-based application for converting temperature and length units.\
-    ",
+    about = format!(
+        "Unit Converter\n\nA terminal-based application for converting units of {}.",
+        dimension_list("and")
+    ),
     arg_required_else_help = true
 )]
 pub struct Cli {
     #[command(subcommand)]
     pub command: Commands,
+    /// Suppresses error messages; failures are only reported by a non-zero exit code.
+    #[arg(long, global = true)]
+    pub quiet_errors: bool,
+    /// Character set for unit symbols; 'auto' falls back to ASCII (e.g., C, m^2) when the terminal's locale or code page is not UTF-8.
+    #[arg(long, global = true, value_enum, default_value = "auto")]
+    pub charset: Charset,
+    /// Fails with a one-line error instead of printing the help when no command is given (also set by UNITCONV_NO_HELP=1).
+    #[arg(long, global = true)]
+    pub no_implicit_help: bool,
+}
+
+/// Lists the names of all unit dimensions for the help text, e.g. "temperature,
+/// length, ..., and volume".
+///
+/// ## Arguments
+///
+/// * `conjunction` - The word placed before the last dimension ("and" or "or").
+///
+/// ## Returns
+///
+/// A `String` with the comma-separated dimension names.
+///
+fn dimension_list(conjunction: &str) -> String {
+    let dimensions: [UnitDimension; 11] = UnitDimension::all_dimensions();
+    let mut list: String = String::new();
+    for (i, dimension) in dimensions.iter().enumerate() {
+        if i + 1 == dimensions.len() {
+            list.push_str(&format!("{} ", conjunction));
+        }
+        list.push_str(dimension.as_str());
+        if i + 1 < dimensions.len() {
+            list.push_str(", ");
+        }
+    }
+    return list;
 }
 
 /// The subcommands for the application.
 #[derive(Subcommand)]
 pub enum Commands {
-    /// Converts temperature or length units to other supported units
-    Convert {
-        /// Source unit (e.g., celsius, km).
-        #[arg(long)]
-        from: String,
-        /// Target unit (e.g., fahrenheit, miles).
+    /// Converts units of a supported dimension to other units of the same dimension
+    #[command(about = format!("Converts {} units to other supported units", dimension_list("or")))]
+    Convert(Box<ConvertArgs>),
+    /// Displays the list of supported units
+    List {
+        /// Lists every accepted symbol and name of each unit instead.
         #[arg(long)]
-        to: String,
-        /// The numerical value to convert.
-        #[arg(long)]
-        value: f64,
+        aliases: bool,
+        /// Prefixes each unit's dimension with an icon (e.g., 🌡 for temperature).
+        #[arg(long, conflicts_with = "aliases")]
+        emoji: bool,
     },
-    /// Displays the list of supported temperature and length units
-    List,
     /// Displays the history of previous unit conversions
-    History,
+    #[cfg(feature = "history")]
+    #[command(args_conflicts_with_subcommands = true)]
+    History(HistoryArgs),
+    /// Checks the built-in conversion factors against known reference values
+    Verify,
+    /// Exits with code 0 if two units can be converted into each other, and 1 otherwise
+    Check(CheckArgs),
+    /// Converts a value along a preset bundle of common conversions (e.g., weather)
+    Preset {
+        /// The preset to run: weather, travel, storage, or screen.
+        name: String,
+        /// The value to convert along each of the preset's conversions.
+        #[arg(long, allow_hyphen_values = true)]
+        value: f64,
+    },
+    /// Prints a round-trip table of every unit of a dimension through its base unit
+    Diag {
+        /// The dimension to diagnose (e.g., temperature, length).
+        #[arg(value_parser = parse_dimension)]
+        dimension: UnitDimension,
+        /// The value converted to the base unit and back.
+        #[arg(long, default_value_t = 1.0, allow_hyphen_values = true)]
+        value: f64,
+    },
+    /// Prints the effective configuration, for writing a data/config.toml
+    Config {
+        /// Prints every setting as TOML with its current value.
+        #[arg(long, required = true)]
+        print: bool,
+    },
+    /// Fills the history with random sample conversions (for development)
+    #[cfg(feature = "history")]
+    #[command(hide = true)]
+    SeedHistory {
+        /// Number of sample conversions to generate.
+        count: usize,
+    },
+}
+
+/// The arguments of the `convert` subcommand.
+#[derive(Args)]
+pub struct ConvertArgs {
+    /// Source unit (e.g., celsius, km). If omitted in a terminal, a picker is shown.
+    #[arg(long)]
+    pub from: Option<String>,
+    /// Target unit, or a comma-separated list of units (e.g., fahrenheit or km,miles).
+    #[arg(long, required_unless_present_any = ["best_unit", "human", "spec", "to_base", "system", "from_json", "random"])]
+    pub to: Option<String>,
+    /// The numerical value to convert.
+    #[arg(
+        long,
+        required_unless_present_any = ["batch", "target_value", "spec", "from_value", "from_json"],
+        allow_hyphen_values = true,
+        conflicts_with_all = ["batch", "target_value"],
+        value_parser = parse_source_value
+    )]
+    pub value: Option<SourceValue>,
+    /// A compact "VALUE FROM > TO" spec (e.g., "100c>f" or "5 km to miles").
+    #[arg(
+        long,
+        allow_hyphen_values = true,
+        conflicts_with_all = ["from", "to", "value", "batch", "target_value", "best_unit", "human"]
+    )]
+    pub spec: Option<String>,
+    /// Prints a table of conversions from this source value up to --to-value, in steps of --step.
+    #[arg(
+        long,
+        value_name = "START",
+        allow_hyphen_values = true,
+        requires_all = ["to_value", "step"],
+        conflicts_with_all = [
+            "value", "batch", "target_value", "spec", "best_unit", "ndjson", "format_markdown",
+            "format_latex", "exact"
+        ]
+    )]
+    pub from_value: Option<f64>,
+    /// The last source value of the --from-value table (inclusive).
+    #[arg(
+        long,
+        value_name = "END",
+        allow_hyphen_values = true,
+        requires = "from_value"
+    )]
+    pub to_value: Option<f64>,
+    /// The difference between consecutive source values of the --from-value table.
+    #[arg(
+        long,
+        value_name = "STEP",
+        allow_hyphen_values = true,
+        requires = "from_value"
+    )]
+    pub step: Option<f64>,
+    /// Solves for the source value that converts to this target value.
+    #[arg(
+        long,
+        value_name = "VALUE",
+        requires = "to",
+        allow_hyphen_values = true,
+        conflicts_with_all = ["batch", "physical", "best_unit", "human"]
+    )]
+    pub target_value: Option<f64>,
+    /// Converts a JSON array of {"from", "to", "value"} requests read from FILE ("-" for standard input) and prints a JSON array of results.
+    #[arg(
+        long,
+        value_name = "FILE",
+        conflicts_with_all = [
+            "from", "to", "value", "spec", "batch", "target_value", "from_value", "best_unit",
+            "to_base", "human"
+        ]
+    )]
+    pub from_json: Option<PathBuf>,
+    /// Reads the values to convert from standard input, one per line.
+    #[arg(long, requires = "from")]
+    pub batch: bool,
+    /// Prints each result as a one-line JSON object (NDJSON) as soon as it is produced.
+    #[arg(long, conflicts_with_all = ["with_reciprocal", "compare_to_baseline"])]
+    pub ndjson: bool,
+    /// After a batch, prints the count, min, max, and mean of the results to stderr.
+    #[arg(long)]
+    pub summary: bool,
+    /// Requires units in the casing the tool prints them in (e.g., km, °C, MHz, KiB).
+    #[arg(long)]
+    pub strict_case: bool,
+    /// Accepts only unit symbols (e.g., km, °C, c) and rejects full names such as kilometer.
+    #[arg(long)]
+    pub symbols_only: bool,
+    /// Picks the most human-readable target unit instead of using --to.
+    #[arg(long, conflicts_with = "to")]
+    pub best_unit: bool,
+    /// Writes a data size with the largest prefix that keeps it at least 1 (e.g., 1536 B as 1.5 KiB), instead of using --to; binary (KiB, MiB) unless "decimal" (KB, MB) is given.
+    #[arg(
+        long,
+        value_enum,
+        value_name = "SCALE",
+        num_args = 0..=1,
+        default_missing_value = "binary",
+        conflicts_with_all = ["to", "best_unit", "to_base", "system", "physical", "relativistic", "per"]
+    )]
+    pub human: Option<SizeScale>,
+    /// Converts to the base unit of the source's dimension (e.g., celsius, centimeter) instead of using --to.
+    #[arg(long, conflicts_with_all = ["to", "best_unit", "spec", "target_value"])]
+    pub to_base: bool,
+    /// Converts to the canonical unit of a system of units for the source's dimension (e.g., meter for si, inch for imperial) instead of using --to.
+    #[arg(long, value_enum, value_name = "SYSTEM", conflicts_with_all = ["to", "best_unit", "to_base", "spec", "target_value"])]
+    pub system: Option<UnitSystem>,
+    /// Reports the percentage difference of each target's result against the first target.
+    #[arg(long)]
+    pub compare_to_baseline: bool,
+    /// Also prints the reciprocal (1 / result) of each converted value.
+    #[arg(long)]
+    pub with_reciprocal: bool,
+    /// Maximum number of decimal places shown for the converted value [default: $UNITCONV_DECIMALS, or 4].
+    #[arg(long, value_name = "N")]
+    pub decimals: Option<usize>,
+    /// Picks the number of decimal places of each result from the precision of its source value.
+    #[arg(long, value_enum, value_name = "MODE", conflicts_with_all = ["decimals", "target_value"])]
+    pub precision: Option<Precision>,
+    /// Caps the number of decimal places of each result at N, whatever --decimals or --precision chose.
+    #[arg(long, value_name = "N")]
+    pub max_precision: Option<usize>,
+    /// How values are rounded to their number of decimal places.
+    #[arg(long, value_enum, value_name = "MODE", default_value = "standard")]
+    pub round: RoundingMode,
+    /// Snaps each result to the nearest multiple of INC (e.g., 0.25) before formatting.
+    #[arg(long, value_name = "INC", value_parser = parse_increment, conflicts_with = "exact")]
+    pub round_to: Option<f64>,
+    /// Maximum number of decimal places shown for the source value.
+    #[arg(long, value_name = "N", default_value_t = 6)]
+    pub source_decimals: usize,
+    /// Left-pads each formatted value to at least N characters.
+    #[arg(long, value_name = "N", default_value_t = 0)]
+    pub pad: usize,
+    /// Converts across dimensions using a known physical relationship.
+    #[arg(long, value_enum, value_name = "RELATION")]
+    pub physical: Option<PhysicalRelation>,
+    /// Accepts messy input: a unit written after --value (e.g., "100c" or " 100  celsius ") and extra whitespace in unit names.
+    #[arg(long)]
+    pub tolerant_parse: bool,
+    /// Reads --value as a sum of quantities of one dimension (e.g., "5 ft 11 in" or "10 lb 4 oz"), converted from its first unit.
+    #[arg(long, requires = "value", conflicts_with_all = ["from", "spec"])]
+    pub compound: bool,
+    /// Converts --value between two random units of a random dimension, e.g. for demos.
+    #[arg(
+        long,
+        requires = "value",
+        conflicts_with_all = ["from", "to", "spec", "compound", "best_unit", "human", "to_base", "system", "physical", "relativistic", "per"]
+    )]
+    pub random: bool,
+    /// Seeds the random choice of --random, so that it picks the same units every time.
+    #[arg(long, value_name = "N")]
+    pub seed: Option<u64>,
+    /// Resolves unit symbols within this dimension (e.g., time reads "m" as minute) and rejects units outside of it.
+    #[arg(long, value_name = "NAME", value_parser = parse_dimension, conflicts_with_all = ["physical", "relativistic"])]
+    pub assume_dimension: Option<UnitDimension>,
+    /// Fails unless the source and every target unit belong to this dimension (e.g., length); unlike --assume-dimension, symbols are resolved as usual.
+    #[arg(long, value_name = "NAME", value_parser = parse_dimension, conflicts_with_all = ["physical", "relativistic"])]
+    pub dimension: Option<UnitDimension>,
+    /// Converts a mass to its energy equivalent (E = m·c²); short for --physical relativistic.
+    #[arg(long, conflicts_with = "physical")]
+    pub relativistic: bool,
+    /// Language used for unit names in the output (e.g., es, de).
+    #[arg(long, value_enum, value_name = "LANG", default_value = "en")]
+    pub lang: Language,
+    /// Saves the successful conversions to the history even if a later one fails.
+    #[arg(long)]
+    pub commit_partial: bool,
+    /// Attaches a free-text note to the conversion in the history.
+    #[arg(long, visible_alias = "annotate", value_name = "TEXT")]
+    pub note: Option<String>,
+    /// Set from the global `--quiet-errors` flag; suppresses the warnings of the conversion.
+    #[arg(skip)]
+    pub quiet: bool,
+    /// Prefixes each result line with "LABEL: " to tell runs apart in merged logs.
+    #[arg(long, value_name = "LABEL")]
+    pub label: Option<String>,
+    /// Prints each result as a Markdown inline-math snippet (e.g., $100\,°C = 212\,°F$).
+    #[arg(long, conflicts_with_all = ["format_latex", "ndjson"])]
+    pub format_markdown: bool,
+    /// Prints each result as a LaTeX inline-math snippet.
+    #[arg(long, conflicts_with = "ndjson")]
+    pub format_latex: bool,
+    /// Computes results exactly with integer arithmetic when both units are whole multiples of their base unit (e.g., data sizes).
+    #[arg(long, conflicts_with_all = ["physical", "format_markdown", "format_latex", "ndjson"])]
+    pub exact: bool,
+    /// Writes time results as days, hours, minutes and seconds (e.g., "1 d 2 h 3 min").
+    #[arg(
+        long,
+        conflicts_with_all = ["exact", "format_markdown", "format_latex", "ndjson", "from_value"]
+    )]
+    pub humanize: bool,
+    /// Treats the value as a gradient per this length unit (e.g., °C per m), converting temperatures as differences.
+    #[arg(
+        long,
+        value_name = "LENGTH",
+        conflicts_with_all = [
+            "physical", "relativistic", "exact", "humanize", "best_unit", "target_value", "from_value"
+        ]
+    )]
+    pub per: Option<String>,
+    /// The length unit of the converted gradient (e.g., ft for °F per foot) [default: --per].
+    #[arg(long, value_name = "LENGTH", requires = "per")]
+    pub per_to: Option<String>,
+    /// Also shows each result in the canonical unit of the other common system (metric or imperial), e.g. inch for m.
+    #[arg(long, conflicts_with = "per")]
+    pub show_both_systems: bool,
+    /// Prints only "VARNAME=value" (e.g., RESULT=212.0), ready to be eval'd by a shell.
+    #[arg(
+        long,
+        value_name = "VARNAME",
+        value_parser = parse_shell_identifier,
+        conflicts_with_all = [
+            "batch", "from_value", "format_markdown", "format_latex", "ndjson", "label", "exact",
+            "humanize", "per", "with_reciprocal", "compare_to_baseline", "show_both_systems"
+        ]
+    )]
+    pub shell_export: Option<String>,
+    /// Also logs each conversion to the system log (syslog or journald) at info level.
+    #[arg(long)]
+    pub log_syslog: bool,
+    /// Also copies the printed result to the system clipboard.
+    #[arg(long, conflicts_with_all = ["ndjson", "from_json"])]
+    pub clipboard: bool,
+    /// Prints the canonical command for the conversion (full unit names, --value) to stderr.
+    #[arg(long)]
+    pub echo_command: bool,
+}
+
+/// The arguments of the `check` subcommand.
+#[derive(Args)]
+pub struct CheckArgs {
+    /// Source unit (e.g., celsius, km).
+    #[arg(long)]
+    pub from: String,
+    /// Target unit (e.g., kelvin, miles).
+    #[arg(long)]
+    pub to: String,
+    /// Prints whether the units are compatible, and why not if they are not.
+    #[arg(long, short = 'v')]
+    pub verbose: bool,
+}
+
+/// The arguments of the `history` subcommand.
+#[cfg(feature = "history")]
+#[derive(Args)]
+pub struct HistoryArgs {
+    /// Groups the records, e.g. by the dimension of the source unit.
+    #[arg(long, value_enum, value_name = "KEY")]
+    pub group_by: Option<HistoryGrouping>,
+    /// Keeps watching the history file and prints new records as they are added.
+    #[arg(long, short = 'f', conflicts_with = "group_by")]
+    pub follow: bool,
+    /// Shows only records whose value lies in the inclusive range START..END (e.g., 90..110).
+    #[arg(
+        long,
+        value_name = "START..END",
+        value_parser = parse_value_range,
+        allow_hyphen_values = true,
+        conflicts_with_all = ["group_by", "follow"]
+    )]
+    pub value_range: Option<ValueRange>,
+    /// Which value of a record --value-range is compared against.
+    #[arg(
+        long = "match",
+        value_enum,
+        value_name = "FIELD",
+        default_value = "input",
+        requires = "value_range"
+    )]
+    pub match_field: HistoryMatch,
+    /// Time zone for timestamps: local (default), UTC, an offset like +07:00, or a name like America/New_York.
+    #[arg(long, value_name = "TZ")]
+    pub timezone: Option<String>,
+    /// Truncates each printed line to N characters (default: the terminal width; 0 disables).
+    #[arg(long, value_name = "N", alias = "max-history-display-width")]
+    pub max_width: Option<usize>,
+    /// Shows only page N of the history, counting from 1.
+    #[arg(long, value_name = "N", conflicts_with_all = ["group_by", "follow", "value_range"])]
+    pub page: Option<usize>,
+    /// Number of records per page [default: page_size from the config, or 20].
+    #[arg(long, value_name = "M", requires = "page")]
+    pub page_size: Option<usize>,
+    #[command(subcommand)]
+    pub action: Option<HistoryAction>,
+}
+
+/// Actions on the conversion history other than displaying it.
+#[cfg(feature = "history")]
+#[derive(Subcommand)]
+pub enum HistoryAction {
+    /// Writes the whole history to a file as JSON, CSV, or a Markdown table
+    Export {
+        /// The format of the exported file.
+        #[arg(long, value_enum, value_name = "FMT")]
+        format: ExportFormat,
+        /// The path of the file to write; an existing file is overwritten.
+        #[arg(long, value_name = "PATH")]
+        file: PathBuf,
+    },
+    /// Rewrites the records with the current unit symbols and formatting, then saves them
+    Normalize,
+    /// Removes every record from the history (the audit log is kept)
+    Clear,
+    /// Removes records that repeat an earlier conversion, then saves the history
+    Dedupe {
+        /// Which occurrence of each conversion to keep.
+        #[arg(long, value_enum, default_value = "first")]
+        keep: KeepOccurrence,
+    },
+    /// Pins a record, so that the history_limit rotation never drops it
+    Pin {
+        /// The number of the record, as shown by `history`.
+        number: usize,
+    },
+    /// Unpins a record, so that the history_limit rotation may drop it again
+    Unpin {
+        /// The number of the record, as shown by `history`.
+        number: usize,
+    },
+}
+
+/// A source value together with the text it was written as.
+///
+/// The text is kept because it carries the precision the value was given with
+/// (e.g., "1.50" versus "1.5"), which the `f64` alone does not.
+#[derive(Clone)]
+pub struct SourceValue {
+    pub value: f64,
+    pub text: String,
+    /// A unit written after the number (e.g., "c" in "100c"), which is only
+    /// accepted with `--tolerant-parse`.
+    pub unit: Option<String>,
+}
+
+impl SourceValue {
+    /// Counts the decimal places of the written value, taking an exponent into
+    /// account (e.g., "1.25" has 2, "1.5e-3" has 4 and "12e2" has 0).
+    pub fn decimal_places(&self) -> usize {
+        let text: String = self.text.to_ascii_lowercase();
+        let (mantissa, exponent): (&str, i64) = match text.split_once('e') {
+            Some((mantissa, exponent)) => (mantissa, exponent.parse().unwrap_or(0)),
+            None => (text.as_str(), 0),
+        };
+        let fraction: i64 = match mantissa.split_once('.') {
+            Some((_, fraction)) => fraction.len() as i64,
+            None => 0,
+        };
+        return fraction.saturating_sub(exponent).max(0) as usize;
+    }
+
+    /// Counts the significant digits of the written value (e.g., "0.0250" has 3).
+    pub fn significant_digits(&self) -> usize {
+        let text: String = self.text.to_ascii_lowercase();
+        let mantissa: &str = text.split('e').next().unwrap_or("");
+        let digits: String = mantissa.chars().filter(|c| c.is_ascii_digit()).collect();
+        return digits.trim_start_matches('0').len().max(1);
+    }
+}
+
+/// Parses a source value, keeping the text it was written as.
+///
+/// A trailing "%" divides the number by 100 ("50%" is 0.5), whatever the unit.
+/// The kept text is then the number written with its decimal point moved
+/// ("0.50"), so that its precision is unchanged. A unit written after the
+/// number ("100c", " 100  celsius ") is split off into `SourceValue::unit`,
+/// with its whitespace collapsed; whether it is accepted is decided later.
+///
+/// ## Arguments
+///
+/// * `text` - The raw value argument.
+///
+/// ## Returns
+///
+/// A `Result<SourceValue, String>` which is the parsed value, or a message
+/// describing why the argument is not a number.
+///
+pub fn parse_source_value(text: &str) -> Result<SourceValue, String> {
+    let text: &str = text.trim();
+    if let Some(number) = text.strip_suffix('%') {
+        let number: &str = number.trim_end();
+        return match number.parse::<f64>() {
+            Ok(value) => Ok(SourceValue {
+                value: value / 100.0,
+                text: percent_text(number),
+                unit: None,
+            }),
+            Err(_) => Err(format!("'{}' is not a number or percentage", text)),
+        };
+    }
+    match text.parse::<f64>() {
+        Ok(value) => {
+            return Ok(SourceValue {
+                value,
+                text: String::from(text),
+                unit: None,
+            });
+        }
+        Err(_) => match split_leading_number(text) {
+            Some((value, unit)) if !unit.is_empty() => {
+                return Ok(SourceValue {
+                    unit: Some(unit.split_whitespace().collect::<Vec<&str>>().join(" ")),
+                    ..value
+                });
+            }
+            _ => return Err(format!("'{}' is not a number", text)),
+        },
+    }
+}
+
+/// Splits a value written together with a unit (e.g., "100c" or "50% km") into
+/// the number and the rest of the text.
+///
+/// The number is the longest prefix that parses as one, so that exponents
+/// ("1e3km") are kept while the unit may still start right after the digits.
+/// A "%" right after the number makes it a percentage.
+///
+/// ## Arguments
+///
+/// * `text` - The text starting with a number.
+///
+/// ## Returns
+///
+/// An `Option<(SourceValue, &str)>` with the value and the trimmed rest of the
+/// text, or `None` if the text does not start with a finite number.
+///
+pub fn split_leading_number(text: &str) -> Option<(SourceValue, &str)> {
+    let text: &str = text.trim();
+    if !text.starts_with(|c: char| c.is_ascii_digit() || matches!(c, '+' | '-' | '.')) {
+        return None;
+    }
+
+    let (value, rest): (SourceValue, &str) = (1..=text.len())
+        .rev()
+        .filter(|end| text.is_char_boundary(*end))
+        .find_map(|end| match text[..end].parse::<f64>() {
+            Ok(value) if value.is_finite() => Some((
+                SourceValue {
+                    value,
+                    text: String::from(&text[..end]),
+                    unit: None,
+                },
+                text[end..].trim(),
+            )),
+            _ => None,
+        })?;
+    match rest.strip_prefix('%') {
+        Some(rest) => {
+            let value: SourceValue = parse_source_value(&format!("{}%", value.text)).ok()?;
+            return Some((value, rest.trim()));
+        }
+        None => return Some((value, rest)),
+    }
+}
+
+/// Writes a number given as a percentage as the plain number it stands for.
+///
+/// The decimal point is moved two places to the left ("12.5" becomes "0.125"),
+/// or the exponent is lowered by 2 ("5e3" becomes "5e1").
+///
+fn percent_text(number: &str) -> String {
+    if let Some((mantissa, exponent)) = number.split_once(['e', 'E'])
+        && let Ok(exponent) = exponent.parse::<i32>()
+    {
+        return format!("{}e{}", mantissa, exponent - 2);
+    }
+
+    let (sign, digits): (&str, &str) = match number.strip_prefix(['+', '-']) {
+        Some(digits) => (&number[..1], digits),
+        None => ("", number),
+    };
+    let (integer, fraction): (&str, &str) = digits.split_once('.').unwrap_or((digits, ""));
+    let integer: String = format!("{:0>3}", integer);
+    let (integer, moved): (&str, &str) = integer.split_at(integer.len() - 2);
+    let integer: &str = match integer.trim_start_matches('0') {
+        "" => "0",
+        integer => integer,
+    };
+    return format!("{}{}.{}{}", sign, integer, moved, fraction);
+}
+
+/// Parses a dimension name such as `length` (see `UnitDimension::as_str()`).
+///
+/// ## Arguments
+///
+/// * `name` - The raw dimension argument.
+///
+/// ## Returns
+///
+/// A `Result<UnitDimension, String>` which is the parsed dimension, or a message
+/// listing the known dimensions.
+///
+fn parse_dimension(name: &str) -> Result<UnitDimension, String> {
+    let dimensions: [UnitDimension; 11] = UnitDimension::all_dimensions();
+    match dimensions
+        .iter()
+        .find(|dimension| dimension.as_str().eq_ignore_ascii_case(name.trim()))
+    {
+        Some(dimension) => return Ok(*dimension),
+        None => {
+            let names: Vec<&str> = dimensions.iter().map(|d| d.as_str()).collect();
+            return Err(format!(
+                "unknown dimension '{}'; expected one of {}",
+                name,
+                names.join(", ")
+            ));
+        }
+    }
+}
+
+/// Parses the increment of `--round-to`, which must be a finite number above zero.
+///
+/// ## Arguments
+///
+/// * `text` - The raw increment argument.
+///
+/// ## Returns
+///
+/// A `Result<f64, String>` which is the increment, or a message describing why
+/// it is not valid.
+///
+fn parse_increment(text: &str) -> Result<f64, String> {
+    match text.trim().parse::<f64>() {
+        Ok(increment) if increment.is_finite() && increment > 0.0 => return Ok(increment),
+        Ok(_) => return Err(format!("increment '{}' must be greater than 0", text)),
+        Err(_) => return Err(format!("'{}' is not a number", text)),
+    }
+}
+
+/// Parses the variable name of `--shell-export`, which must be a legal shell
+/// identifier: a letter or underscore, followed by letters, digits or underscores.
+///
+/// ## Arguments
+///
+/// * `name` - The raw variable name argument.
+///
+/// ## Returns
+///
+/// A `Result<String, String>` which is the name, or a message describing why it
+/// is not a shell identifier.
+///
+fn parse_shell_identifier(name: &str) -> Result<String, String> {
+    let legal: bool = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    match legal {
+        true => return Ok(String::from(name)),
+        false => {
+            return Err(format!(
+                "'{}' is not a shell variable name (use letters, digits and '_', not starting with a digit)",
+                name
+            ));
+        }
+    }
+}
+
+/// An inclusive range of values used to search the history.
+#[cfg(feature = "history")]
+#[derive(Clone)]
+pub struct ValueRange {
+    pub start: f64,
+    pub end: f64,
+}
+
+#[cfg(feature = "history")]
+impl ValueRange {
+    /// Checks whether a value lies within the range, bounds included.
+    pub fn contains(&self, value: f64) -> bool {
+        return self.start <= value && value <= self.end;
+    }
+}
+
+/// Parses a `START..END` range argument, e.g. `90..110` or `-40..0`.
+///
+/// ## Arguments
+///
+/// * `range` - The raw range argument.
+///
+/// ## Returns
+///
+/// A `Result<ValueRange, String>` which is the parsed range, or a message
+/// describing why the argument is not a valid range.
+///
+#[cfg(feature = "history")]
+fn parse_value_range(range: &str) -> Result<ValueRange, String> {
+    let (start, end) = range.split_once("..").ok_or(format!(
+        "'{}' is not a range; expected START..END (e.g., 90..110)",
+        range
+    ))?;
+    let start: f64 = start
+        .trim()
+        .parse()
+        .map_err(|_| format!("invalid range start '{}'", start))?;
+    let end: f64 = end
+        .trim()
+        .parse()
+        .map_err(|_| format!("invalid range end '{}'", end))?;
+    if start > end {
+        return Err(format!(
+            "range start {} is greater than range end {}",
+            start, end
+        ));
+    }
+    return Ok(ValueRange { start, end });
+}
+
+/// Physical relationships that allow converting between different dimensions.
+#[derive(Clone, ValueEnum)]
+pub enum PhysicalRelation {
+    /// Wavelength (length) ↔ frequency of light, using c = 299792458 m/s.
+    Wavelength,
+    /// Mass ↔ energy equivalent, using E = m·c² with c = 299792458 m/s.
+    Relativistic,
+}
+
+/// Rounding modes applied when formatting values.
+#[derive(Clone, Copy, ValueEnum)]
+pub enum RoundingMode {
+    /// The standard formatting rounding of Rust's `{:.N}`.
+    Standard,
+    /// Round half to even ("banker's rounding").
+    Banker,
+}
+
+/// Which of several identical history records `history dedupe` keeps.
+#[cfg(feature = "history")]
+#[derive(Clone, Copy, ValueEnum)]
+pub enum KeepOccurrence {
+    /// Keep the oldest record.
+    First,
+    /// Keep the most recent record.
+    Last,
+}
+
+/// The prefixes `--human` scales data sizes with.
+#[derive(Clone, Copy, ValueEnum)]
+pub enum SizeScale {
+    /// Powers of 1024: KiB, MiB, GiB, TiB, PiB.
+    Binary,
+    /// Powers of 1000: KB, MB, GB, TB, PB.
+    Decimal,
+}
+
+/// Ways of choosing the number of decimal places of converted values.
+#[derive(Clone, ValueEnum)]
+pub enum Precision {
+    /// Match the precision of the source value (see `SourceValue`).
+    Auto,
+}
+
+/// The value of a history record that a range search is compared against.
+#[cfg(feature = "history")]
+#[derive(Clone, ValueEnum)]
+pub enum HistoryMatch {
+    /// The value that was converted.
+    Input,
+    /// The converted value.
+    Output,
+}
+
+/// Formats the conversion history can be exported in.
+#[cfg(feature = "history")]
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ExportFormat {
+    /// A JSON array of the history records.
+    Json,
+    /// Comma-separated values with a header row.
+    Csv,
+    /// A Markdown table with aligned columns.
+    Md,
+}
+
+/// Keys by which the conversion history can be grouped.
+#[cfg(feature = "history")]
+#[derive(Clone, ValueEnum)]
+pub enum HistoryGrouping {
+    /// Group by the dimension of the source unit (temperature, length, ...).
+    Dimension,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn help_text_names_every_dimension() {
+        let command: clap::Command = Cli::command();
+        let about: String = command.get_about().unwrap().to_string();
+        let convert: String = command
+            .find_subcommand("convert")
+            .and_then(|convert| convert.get_about())
+            .unwrap()
+            .to_string();
+        for dimension in UnitDimension::all_dimensions() {
+            assert!(about.contains(dimension.as_str()), "{}", about);
+            assert!(convert.contains(dimension.as_str()), "{}", convert);
+        }
+        assert!(about.ends_with("area, and volume."));
+        assert!(convert.contains("area, or volume units"));
+    }
+
+    #[test]
+    fn percentages_are_divided_by_one_hundred() {
+        assert_eq!(parse_source_value("50%").unwrap().value, 0.5);
+        assert_eq!(parse_source_value("100%").unwrap().value, 1.0);
+        assert_eq!(parse_source_value("-2.5%").unwrap().value, -0.025);
+        assert!(parse_source_value("%").is_err());
+    }
 }
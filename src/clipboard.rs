@@ -0,0 +1,125 @@
+//! # Clipboard Module
+//!
+//! This module copies conversion results to the system clipboard for `--clipboard`,
+//! using the `arboard` crate (the `clipboard` feature), which supports macOS,
+//! Windows, X11 and Wayland. On X11, the text is handed over to the clipboard
+//! manager when the program exits. When the feature is disabled, or on a headless
+//! system (e.g., over SSH), copying is a no-op and a warning is printed.
+
+/// A clipboard that text can be copied to.
+trait Target {
+    /// Replaces the contents of the clipboard with text.
+    ///
+    /// ## Returns
+    ///
+    /// `true` if the text was copied.
+    ///
+    fn set_text(&mut self, text: &str) -> bool;
+}
+
+#[cfg(feature = "clipboard")]
+impl Target for arboard::Clipboard {
+    fn set_text(&mut self, text: &str) -> bool {
+        return arboard::Clipboard::set_text(self, text).is_ok();
+    }
+}
+
+/// Copies text to the system clipboard, or warns that no clipboard is available.
+///
+/// ## Arguments
+///
+/// * `text` - The text to copy (e.g., the printed conversion lines).
+///
+pub fn copy(text: &str) {
+    let mut clipboard: Option<Box<dyn Target>> = open();
+    if copy_to(clipboard.as_deref_mut(), text) {
+        return;
+    }
+    if cfg!(feature = "clipboard") {
+        eprintln!(
+            "Warning: [WARNING] No clipboard is available on this system; --clipboard is ignored."
+        );
+    } else {
+        eprintln!(
+            "Warning: [WARNING] This build has no clipboard support (the 'clipboard' feature is disabled); --clipboard is ignored."
+        );
+    }
+}
+
+/// Copies text to a clipboard, if there is one.
+///
+/// ## Arguments
+///
+/// * `target` - The clipboard, or `None` if none is available.
+/// * `text` - The text to copy.
+///
+/// ## Returns
+///
+/// `true` if the text was copied.
+///
+fn copy_to<T: Target + ?Sized>(target: Option<&mut T>, text: &str) -> bool {
+    return target.is_some_and(|target| target.set_text(text));
+}
+
+/// Opens the system clipboard.
+///
+/// ## Returns
+///
+/// An `Option<Box<dyn Target>>` which is `None` if there is no clipboard, such
+/// as on a system without a display.
+///
+#[cfg(feature = "clipboard")]
+fn open() -> Option<Box<dyn Target>> {
+    let clipboard: arboard::Clipboard = arboard::Clipboard::new().ok()?;
+    return Some(Box::new(clipboard));
+}
+
+/// Reports that there is no clipboard, since the `clipboard` feature is disabled.
+#[cfg(not(feature = "clipboard"))]
+fn open() -> Option<Box<dyn Target>> {
+    return None;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A clipboard that keeps the copied text in memory, or rejects it.
+    struct MockClipboard {
+        contents: Option<String>,
+        available: bool,
+    }
+
+    impl Target for MockClipboard {
+        fn set_text(&mut self, text: &str) -> bool {
+            if self.available {
+                self.contents = Some(String::from(text));
+            }
+            return self.available;
+        }
+    }
+
+    #[test]
+    fn copies_the_text() {
+        let mut clipboard: MockClipboard = MockClipboard {
+            contents: None,
+            available: true,
+        };
+        assert!(copy_to(Some(&mut clipboard), "10.0 km = 6.2137 miles"));
+        assert_eq!(
+            clipboard.contents.as_deref(),
+            Some("10.0 km = 6.2137 miles")
+        );
+    }
+
+    #[test]
+    fn reports_a_missing_clipboard() {
+        let mut clipboard: MockClipboard = MockClipboard {
+            contents: None,
+            available: false,
+        };
+        assert!(!copy_to(Some(&mut clipboard), "10.0 km = 6.2137 miles"));
+        assert_eq!(clipboard.contents, None);
+        assert!(!copy_to::<MockClipboard>(None, "10.0 km = 6.2137 miles"));
+    }
+}
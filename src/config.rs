@@ -0,0 +1,354 @@
+//! # Configuration Module
+//!
+//! This module loads user settings from `data/config.toml`. Only a small subset of
+//! TOML is understood: one `key = value` pair per line, where the value is a quoted
+//! string or a bare word, plus blank lines and `#` comments. Settings from the
+//! file act as defaults that command-line options override. `config --print`
+//! writes the effective settings back in the same format.
+
+use crate::converter::REFERENCE_TOLERANCE;
+use crate::units::UnitDimension;
+use anyhow::{Result, bail};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The settings read from the configuration file.
+#[derive(Default)]
+pub struct Config {
+    /// The time zone in which history timestamps are shown (see `TimeZone::parse()`).
+    #[cfg_attr(not(feature = "history"), allow(dead_code))]
+    pub timezone: Option<String>,
+    /// The relative tolerance `verify` uses for every dimension without its own.
+    pub tolerance: Option<f64>,
+    /// Per-dimension `verify` tolerances, keyed by dimension name (e.g., "length").
+    pub dimension_tolerances: HashMap<String, f64>,
+    /// The relative tolerance within which two unit factors count as identical
+    /// (see `converter::set_identity_tolerance()`).
+    pub identity_tolerance: Option<f64>,
+    /// The number of records per page of `history --page`.
+    #[cfg_attr(not(feature = "history"), allow(dead_code))]
+    pub page_size: Option<usize>,
+    /// How the history file is stored (`history_format = "json"` or `"jsonl"`).
+    #[cfg_attr(not(feature = "history"), allow(dead_code))]
+    pub history_format: HistoryFormat,
+    /// The path of the append-only audit log, if conversions are audited.
+    #[cfg_attr(not(feature = "history"), allow(dead_code))]
+    pub audit_log: Option<PathBuf>,
+    /// The maximum number of records kept in the history (see `History::rotate()`).
+    #[cfg_attr(not(feature = "history"), allow(dead_code))]
+    pub history_limit: Option<usize>,
+    /// The default number of decimal places of converted values (see `--decimals`).
+    pub decimals: Option<usize>,
+}
+
+/// The storage format of the history file.
+#[derive(Clone, Copy, Default, PartialEq)]
+#[cfg_attr(not(feature = "history"), allow(dead_code))]
+pub enum HistoryFormat {
+    /// One pretty-printed JSON document, rewritten on every save.
+    #[default]
+    Json,
+    /// One JSON record per line (JSON Lines), to which new records are appended.
+    Jsonl,
+}
+
+impl Config {
+    const FILE_PATH: &'static str = "data/config.toml";
+    /// Number of records per page of `history --page` when no size is configured.
+    pub const DEFAULT_PAGE_SIZE: usize = 20;
+
+    /// Loads the configuration file.
+    ///
+    /// If the file does not exist, it returns the default (empty) configuration.
+    ///
+    /// ## Returns
+    ///
+    /// A `Result<Self>` which is the loaded `Config` on success, or an error if
+    /// the file cannot be read or contains an invalid line or unknown key.
+    ///
+    pub fn load() -> Result<Self> {
+        let file_path: &Path = Path::new(Self::FILE_PATH);
+        if !file_path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content: String = match fs::read_to_string(file_path) {
+            Ok(content) => content,
+            Err(e) => bail!(format!(
+                "Error: [ERROR] Could not read config file '{}': {}.",
+                file_path.display(),
+                e
+            )),
+        };
+        return Self::parse(&content);
+    }
+
+    /// Parses the content of a configuration file.
+    ///
+    /// ## Arguments
+    ///
+    /// * `content` - The text of the configuration file.
+    ///
+    /// ## Returns
+    ///
+    /// A `Result<Self>` which is the parsed `Config`, or an error naming the
+    /// first invalid line.
+    ///
+    fn parse(content: &str) -> Result<Self> {
+        let mut config: Config = Config::default();
+        for (i, line) in content.lines().enumerate() {
+            let line: &str = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once('=') else {
+                bail!(format!(
+                    "Error: [ERROR] Config file '{}', line {}: expected 'key = value'.",
+                    Self::FILE_PATH,
+                    i + 1
+                ));
+            };
+            let value: String = parse_value(value);
+            match key.trim() {
+                "timezone" => config.timezone = Some(value),
+                "audit_log" => config.audit_log = Some(PathBuf::from(value)),
+                "page_size" => match value.parse::<usize>() {
+                    Ok(size) if size > 0 => config.page_size = Some(size),
+                    _ => bail!(format!(
+                        "Error: [ERROR] Config file '{}', line {}: page_size '{}' is not a positive integer.",
+                        Self::FILE_PATH,
+                        i + 1,
+                        value
+                    )),
+                },
+                "history_limit" => match value.parse::<usize>() {
+                    Ok(limit) if limit > 0 => config.history_limit = Some(limit),
+                    _ => bail!(format!(
+                        "Error: [ERROR] Config file '{}', line {}: history_limit '{}' is not a positive integer.",
+                        Self::FILE_PATH,
+                        i + 1,
+                        value
+                    )),
+                },
+                "decimals" => match value.parse::<usize>() {
+                    Ok(decimals) => config.decimals = Some(decimals),
+                    _ => bail!(format!(
+                        "Error: [ERROR] Config file '{}', line {}: decimals '{}' is not a non-negative integer.",
+                        Self::FILE_PATH,
+                        i + 1,
+                        value
+                    )),
+                },
+                "history_format" => match value.as_str() {
+                    "json" => config.history_format = HistoryFormat::Json,
+                    "jsonl" => config.history_format = HistoryFormat::Jsonl,
+                    _ => bail!(format!(
+                        "Error: [ERROR] Config file '{}', line {}: history_format '{}' is not 'json' or 'jsonl'.",
+                        Self::FILE_PATH,
+                        i + 1,
+                        value
+                    )),
+                },
+                "tolerance" => config.tolerance = Some(parse_tolerance(&value, i + 1)?),
+                "identity_tolerance" => {
+                    config.identity_tolerance = Some(parse_tolerance(&value, i + 1)?)
+                }
+                key if key.starts_with("tolerance.") => {
+                    let dimension: &str = key.trim_start_matches("tolerance.");
+                    if !UnitDimension::all_dimensions()
+                        .iter()
+                        .any(|d| d.as_str() == dimension)
+                    {
+                        bail!(format!(
+                            "Error: [ERROR] Config file '{}', line {}: unknown dimension '{}' in '{}'.",
+                            Self::FILE_PATH,
+                            i + 1,
+                            dimension,
+                            key
+                        ));
+                    }
+                    config
+                        .dimension_tolerances
+                        .insert(String::from(dimension), parse_tolerance(&value, i + 1)?);
+                }
+                key => bail!(format!(
+                    "Error: [ERROR] Config file '{}', line {}: unknown key '{}'.",
+                    Self::FILE_PATH,
+                    i + 1,
+                    key
+                )),
+            }
+        }
+
+        return Ok(config);
+    }
+
+    /// Writes the effective configuration as a configuration file.
+    ///
+    /// Every key is written with the value in effect, which is the configured one
+    /// or else the built-in default. Keys without a default (e.g., `audit_log`)
+    /// are written as comments with an example value, so that the output lists
+    /// every available key and can be saved as `data/config.toml` as it is.
+    ///
+    /// ## Arguments
+    ///
+    /// * `decimals` - The effective default number of decimal places, which may
+    ///   also come from the `UNITCONV_DECIMALS` variable.
+    ///
+    /// ## Returns
+    ///
+    /// A `String` with one commented `key = value` line per setting.
+    ///
+    pub fn to_toml(&self, decimals: usize) -> String {
+        let mut lines: Vec<String> = Vec::new();
+        let mut setting = |comment: &str, line: String| {
+            lines.push(format!("# {}", comment));
+            lines.push(line);
+            lines.push(String::new());
+        };
+
+        setting(
+            "Decimal places of converted values (--decimals)",
+            format!("decimals = {}", decimals),
+        );
+        setting(
+            "Time zone for history timestamps",
+            format!(
+                "timezone = \"{}\"",
+                self.timezone.as_deref().unwrap_or("local")
+            ),
+        );
+        setting(
+            "Records per page of `history --page`",
+            format!(
+                "page_size = {}",
+                self.page_size.unwrap_or(Self::DEFAULT_PAGE_SIZE)
+            ),
+        );
+        setting(
+            "Storage format of the history: \"json\" or \"jsonl\"",
+            format!(
+                "history_format = \"{}\"",
+                match self.history_format {
+                    HistoryFormat::Json => "json",
+                    HistoryFormat::Jsonl => "jsonl",
+                }
+            ),
+        );
+        setting(
+            "Maximum number of records kept in the history (unlimited if unset)",
+            match self.history_limit {
+                Some(limit) => format!("history_limit = {}", limit),
+                None => String::from("# history_limit = 500"),
+            },
+        );
+        setting(
+            "Append-only audit log of saved conversions (none if unset)",
+            match &self.audit_log {
+                Some(path) => format!("audit_log = \"{}\"", path.display()),
+                None => String::from("# audit_log = \"data/audit.jsonl\""),
+            },
+        );
+
+        let mut tolerances: Vec<(&String, &f64)> = self.dimension_tolerances.iter().collect();
+        tolerances.sort_by(|a, b| a.0.cmp(b.0));
+        let mut tolerance_lines: Vec<String> = vec![format!(
+            "tolerance = {:?}",
+            self.tolerance.unwrap_or(REFERENCE_TOLERANCE)
+        )];
+        match tolerances.is_empty() {
+            true => tolerance_lines.push(String::from("# tolerance.length = 1e-4")),
+            false => tolerance_lines.extend(tolerances.iter().map(|(dimension, tolerance)| {
+                format!("tolerance.{} = {:?}", dimension, tolerance)
+            })),
+        }
+        setting(
+            "Relative tolerance used by `verify`, optionally per dimension",
+            tolerance_lines.join("\n"),
+        );
+        setting(
+            "Relative difference within which two units count as identical",
+            format!(
+                "identity_tolerance = {:?}",
+                self.identity_tolerance.unwrap_or(0.0)
+            ),
+        );
+
+        return lines.join("\n").trim_end().to_string();
+    }
+
+    /// Gets the relative tolerance `verify` uses for a dimension.
+    ///
+    /// A `tolerance.<dimension>` setting takes precedence over `tolerance`, which
+    /// in turn replaces the built-in `REFERENCE_TOLERANCE`.
+    ///
+    /// ## Arguments
+    ///
+    /// * `dimension` - The dimension of the reference conversion.
+    ///
+    /// ## Returns
+    ///
+    /// The maximum accepted relative deviation.
+    ///
+    pub fn tolerance_for(&self, dimension: &UnitDimension) -> f64 {
+        match self.dimension_tolerances.get(dimension.as_str()) {
+            Some(tolerance) => return *tolerance,
+            None => return self.tolerance.unwrap_or(REFERENCE_TOLERANCE),
+        }
+    }
+}
+
+/// Parses a tolerance value, which must be a finite, non-negative number.
+fn parse_tolerance(value: &str, line: usize) -> Result<f64> {
+    match value.parse::<f64>() {
+        Ok(tolerance) if tolerance.is_finite() && tolerance >= 0.0 => return Ok(tolerance),
+        _ => bail!(format!(
+            "Error: [ERROR] Config file '{}', line {}: tolerance '{}' is not a non-negative number.",
+            Config::FILE_PATH,
+            line,
+            value
+        )),
+    }
+}
+
+/// Parses a config value, removing surrounding quotes and a trailing comment.
+pub fn parse_value(value: &str) -> String {
+    let value: &str = value.trim();
+    if let Some(quoted) = value.strip_prefix('"')
+        && let Some(end) = quoted.find('"')
+    {
+        return String::from(&quoted[..end]);
+    }
+
+    let value: &str = match value.split_once('#') {
+        Some((value, _)) => value,
+        None => value,
+    };
+    return String::from(value.trim());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dimension_tolerance_overrides_the_default() {
+        let config: Config = Config {
+            dimension_tolerances: HashMap::from([(String::from("length"), 1e-4)]),
+            ..Config::default()
+        };
+        assert_eq!(config.tolerance_for(&UnitDimension::Length), 1e-4);
+        assert_eq!(
+            config.tolerance_for(&UnitDimension::Data),
+            REFERENCE_TOLERANCE
+        );
+
+        // An astronomical unit given as 1.496e13 cm deviates from the defined
+        // 149597870700 m by about 1.4e-5: within 1e-4, but not within 1e-9.
+        let deviation: f64 = ((1.496e13 - 1.495978707e13) / 1.495978707e13_f64).abs();
+        assert!(deviation <= config.tolerance_for(&UnitDimension::Length));
+        assert!(deviation > config.tolerance_for(&UnitDimension::Data));
+    }
+}
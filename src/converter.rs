@@ -3,33 +3,541 @@
 //! This module contains the core logic for converting values between different units.
 //! It validates that conversions are only attempted between units of the same dimension
 //! (e.g., length to length) and then applies the appropriate mathematical formula.
+//! Units take part in conversions through the `Convertible` trait, which the built-in
+//! `Unit` enum implements and which downstream crates can implement for their own units.
+//! A small number of well-known physical relationships (such as wavelength ↔ frequency)
+//! are handled by dedicated functions that deliberately cross dimensions.
 
-use crate::units::Unit;
+use crate::units::{Unit, UnitDimension};
 use anyhow::{Result, bail};
+use std::fmt::Display;
+use std::sync::OnceLock;
+
+/// The relation between a unit and the base unit of its dimension.
+///
+/// A value `v` in the unit equals `(v + offset) * numerator / denominator` in the
+/// base unit. Keeping the numerator and denominator separate lets ratios such as
+/// 5/9 (Fahrenheit) or 1/10000000 (Nanometer) be applied without first rounding
+/// them to a single `f64`.
+///
+/// A unit that measures the reciprocal of its base unit (e.g., L/100km against
+/// km/L) has `inverse` set: a value `v` then equals `numerator / (denominator * v)`
+/// in the base unit, and a value of 0 has no counterpart in the other unit.
+#[derive(Clone, Copy)]
+pub struct Factor {
+    pub numerator: f64,
+    pub denominator: f64,
+    pub offset: f64,
+    pub inverse: bool,
+}
+
+impl Factor {
+    /// Creates a purely multiplicative factor (no offset).
+    const fn scale(numerator: f64, denominator: f64) -> Self {
+        return Factor {
+            numerator,
+            denominator,
+            offset: 0.0,
+            inverse: false,
+        };
+    }
+
+    /// Creates a factor for a unit that measures the reciprocal of its base unit.
+    const fn reciprocal(numerator: f64, denominator: f64) -> Self {
+        return Factor {
+            numerator,
+            denominator,
+            offset: 0.0,
+            inverse: true,
+        };
+    }
+
+    /// Raises a purely multiplicative factor to a power (e.g., length → area).
+    fn powi(self, exponent: i32) -> Self {
+        return Factor::scale(
+            self.numerator.powi(exponent),
+            self.denominator.powi(exponent),
+        );
+    }
+
+    /// Creates a factor with an offset applied before scaling.
+    const fn affine(numerator: f64, denominator: f64, offset: f64) -> Self {
+        return Factor {
+            numerator,
+            denominator,
+            offset,
+            inverse: false,
+        };
+    }
+
+    /// Converts a value of the unit this factor describes into the base unit.
+    fn to_base(self, v: f64) -> f64 {
+        if self.inverse {
+            return self.numerator / (self.denominator * v);
+        }
+        return (v + self.offset) * self.numerator / self.denominator;
+    }
+
+    /// Converts a value from the base unit into the unit this factor describes.
+    fn to_unit(self, v: f64) -> f64 {
+        if self.inverse {
+            return self.numerator / (self.denominator * v);
+        }
+        return v * self.denominator / self.numerator - self.offset;
+    }
+
+    /// Re-expresses a factor relative to another unit of the same dimension.
+    ///
+    /// Both `self` and `base` are relative to the same reference unit; the result
+    /// relates `self` to `base` instead. Rebasing onto the reference unit itself
+    /// (a factor of exactly 1 with no offset) returns `self` unchanged. The base
+    /// unit itself is never an inverse unit, so only `self` can be one.
+    fn rebase(self, base: Factor) -> Self {
+        let numerator: f64 = self.numerator * base.denominator;
+        let denominator: f64 = self.denominator * base.numerator;
+        return Factor {
+            numerator,
+            denominator,
+            offset: self.offset - base.offset * denominator / numerator,
+            inverse: self.inverse,
+        };
+    }
+}
+
+/// A unit of measurement that can be converted through a base unit.
+///
+/// Every unit converts its values into the base unit of its dimension and back.
+/// Two units can be converted between each other whenever they report the same
+/// `dimension()`, which lets downstream crates add their own units (or entire
+/// dimensions) without modifying the built-in `Unit` enum. The `Display`
+/// implementation is used as the unit's name in messages.
+pub trait Convertible: Display {
+    /// Converts a value expressed in this unit into the base unit of its dimension.
+    fn to_base(&self, v: f64) -> f64;
+    /// Converts a value expressed in the base unit of the dimension into this unit.
+    #[allow(clippy::wrong_self_convention)]
+    fn from_base(&self, v: f64) -> f64;
+    /// Gets the name of the dimension this unit belongs to (e.g., "length").
+    fn dimension(&self) -> &str;
+    /// Gets the kind of quantity this unit measures (e.g., "torque").
+    ///
+    /// Units of the same dimension can still measure physically distinct quantities,
+    /// and are only converted into each other if their kinds match as well. The
+    /// default is the name of the dimension.
+    fn kind(&self) -> &str {
+        return self.dimension();
+    }
+    /// Gets the `Factor` relating this unit to its base unit, if it has one.
+    ///
+    /// Units that only provide `to_base`/`from_base` can keep the default `None`;
+    /// they are then always converted through the base unit.
+    fn factor(&self) -> Option<Factor> {
+        return None;
+    }
+}
+
+/// Gets the base unit through which values of a dimension are converted.
+///
+/// Any unit of the dimension can serve as its base; every factor is re-expressed
+/// relative to it by `unit_factor()`. The choice matters for precision, since
+/// values are offset and scaled into the base unit and back:
+///
+/// * Temperature uses Celsius rather than Kelvin. With a Kelvin base, every
+///   Celsius or Fahrenheit value is shifted by about 273 on its way through the
+///   base, so everyday temperatures lose their low digits. A round trip
+///   °C → °F → °C is off by about 1e-14 through Kelvin for 1e-9 °C, 0.1 °C or
+///   36.6 °C, against 2e-15 or less (often exactly 0) through Celsius. Round trips
+///   starting from Kelvin are about as precise with either base.
+/// * Purely multiplicative dimensions mostly take the fast path of `convert()`,
+///   which does not depend on the base at all; their bases (Centimeter, Hertz,
+///   Bit) are the units whose factors keep the table's ratios exact.
+///
+/// ## Arguments
+///
+/// * `dimension` - The `UnitDimension` to look up.
+///
+/// ## Returns
+///
+/// The base `Unit` of the dimension.
+///
+pub fn base_unit(dimension: &UnitDimension) -> Unit {
+    match dimension {
+        UnitDimension::Temperature => return Unit::Celsius,
+        UnitDimension::Length => return Unit::Centimeter,
+        UnitDimension::Frequency => return Unit::Hertz,
+        UnitDimension::Data => return Unit::Bit,
+        UnitDimension::Energy => return Unit::Joule,
+        UnitDimension::Speed => return Unit::MeterPerSecond,
+        UnitDimension::FuelEconomy => return Unit::KilometerPerLiter,
+        UnitDimension::Time => return Unit::Second,
+        UnitDimension::Mass => return Unit::Gram,
+        UnitDimension::Area => return Unit::Area(Box::new(base_unit(&UnitDimension::Length))),
+        UnitDimension::Volume => {
+            return Unit::Volume(Box::new(base_unit(&UnitDimension::Length)));
+        }
+    }
+}
+
+/// Gets the `Factor` of a built-in unit relative to the base unit of its dimension.
+///
+/// The factor is looked up in the reference table (`reference_factor()`) and
+/// rebased onto the unit returned by `base_unit()`.
+///
+/// ## Arguments
+///
+/// * `unit` - The `Unit` to look up.
+///
+/// ## Returns
+///
+/// The unit's `Factor`.
+///
+pub fn unit_factor(unit: &Unit) -> Factor {
+    let base: Unit = base_unit(&unit.dimension());
+    return reference_factor(unit).rebase(reference_factor(&base));
+}
+
+/// Gets the `Factor` of a built-in unit relative to the reference unit of its dimension.
+///
+/// This is the factor table used by all conversions between built-in units. The
+/// reference units (Celsius for temperature, Centimeter for length, Hertz for
+/// frequency, Bit for data, Joule for energy, MeterPerSecond for speed,
+/// KilometerPerLiter for fuel economy, Second for time, Gram for mass, and the
+/// square and cubic Centimeter for area and volume) have a factor of exactly 1 with no offset. The match
+/// deliberately has no catch-all arm, so a new `Unit` variant does not compile without a factor.
+///
+/// ## Arguments
+///
+/// * `unit` - The `Unit` to look up.
+///
+/// ## Returns
+///
+/// The unit's reference `Factor`.
+///
+fn reference_factor(unit: &Unit) -> Factor {
+    match unit {
+        // Temperature
+        Unit::Celsius => return Factor::scale(1.0, 1.0), // Reference
+        Unit::Fahrenheit => return Factor::affine(5.0, 9.0, -32.0),
+        Unit::Kelvin => return Factor::affine(1.0, 1.0, -273.15),
+        // Length
+        Unit::Centimeter => return Factor::scale(1.0, 1.0), // Reference
+        Unit::Inch => return Factor::scale(2.54, 1.0),
+        Unit::Foot => return Factor::scale(30.48, 1.0),
+        Unit::Kilometer => return Factor::scale(100000.0, 1.0),
+        Unit::Mile => return Factor::scale(160934.4, 1.0),
+        Unit::Nanometer => return Factor::scale(1.0, 10000000.0),
+        Unit::Millimeter => return Factor::scale(1.0, 10.0),
+        Unit::Meter => return Factor::scale(100.0, 1.0),
+        // Typographic units are defined through the inch (pixels at 96 DPI)
+        Unit::Point => return Factor::scale(2.54, 72.0),
+        Unit::Pica => return Factor::scale(2.54, 6.0),
+        Unit::Pixel => return Factor::scale(2.54, 96.0),
+        // Frequency
+        Unit::Hertz => return Factor::scale(1.0, 1.0), // Reference
+        Unit::Kilohertz => return Factor::scale(1e3, 1.0),
+        Unit::Megahertz => return Factor::scale(1e6, 1.0),
+        Unit::Gigahertz => return Factor::scale(1e9, 1.0),
+        Unit::Terahertz => return Factor::scale(1e12, 1.0),
+        // Data
+        Unit::Bit => return Factor::scale(1.0, 1.0), // Reference
+        Unit::Byte => return Factor::scale(8.0, 1.0),
+        Unit::Kilobyte => return Factor::scale(8e3, 1.0),
+        Unit::Megabyte => return Factor::scale(8e6, 1.0),
+        Unit::Gigabyte => return Factor::scale(8e9, 1.0),
+        Unit::Terabyte => return Factor::scale(8e12, 1.0),
+        Unit::Petabyte => return Factor::scale(8e15, 1.0),
+        Unit::Kibibyte => return Factor::scale(8192.0, 1.0),
+        Unit::Mebibyte => return Factor::scale(8388608.0, 1.0),
+        Unit::Gibibyte => return Factor::scale(8589934592.0, 1.0),
+        Unit::Tebibyte => return Factor::scale(8796093022208.0, 1.0),
+        Unit::Pebibyte => return Factor::scale(9007199254740992.0, 1.0),
+        // Energy (torque shares the scale, see `Unit::kind()`)
+        Unit::Joule => return Factor::scale(1.0, 1.0), // Reference
+        Unit::Kilojoule => return Factor::scale(1e3, 1.0),
+        Unit::NewtonMeter => return Factor::scale(1.0, 1.0),
+        // Speed
+        Unit::MeterPerSecond => return Factor::scale(1.0, 1.0), // Reference
+        Unit::KilometerPerHour => return Factor::scale(1000.0, 3600.0),
+        Unit::MilePerHour => return Factor::scale(1609.344, 3600.0),
+        Unit::Knot => return Factor::scale(1852.0, 3600.0),
+        // Fuel economy (a US gallon is 3.785411784 L, an imperial gallon 4.54609 L)
+        Unit::KilometerPerLiter => return Factor::scale(1.0, 1.0), // Reference
+        Unit::LiterPer100Kilometer => return Factor::reciprocal(100.0, 1.0),
+        Unit::MilePerGallon => return Factor::scale(1.609344, 3.785411784),
+        Unit::MilePerImperialGallon => return Factor::scale(1.609344, 4.54609),
+        // Time
+        Unit::Millisecond => return Factor::scale(1.0, 1000.0),
+        Unit::Second => return Factor::scale(1.0, 1.0), // Reference
+        Unit::Minute => return Factor::scale(60.0, 1.0),
+        Unit::Hour => return Factor::scale(3600.0, 1.0),
+        Unit::Day => return Factor::scale(86400.0, 1.0),
+        Unit::Week => return Factor::scale(604800.0, 1.0),
+        // Mass (the pound is defined as exactly 453.59237 g, the ounce as 1/16 lb)
+        Unit::Milligram => return Factor::scale(1.0, 1000.0),
+        Unit::Gram => return Factor::scale(1.0, 1.0), // Reference
+        Unit::Kilogram => return Factor::scale(1000.0, 1.0),
+        Unit::Tonne => return Factor::scale(1e6, 1.0),
+        Unit::Ounce => return Factor::scale(453.59237, 16.0),
+        Unit::Pound => return Factor::scale(453.59237, 1.0),
+        // Area and volume (reference: square and cubic centimeter)
+        Unit::Area(length) => return reference_factor(length).powi(2),
+        Unit::Volume(length) => return reference_factor(length).powi(3),
+        // Custom units are defined relative to the base unit, which is the reference
+        Unit::Custom(_) => return Factor::scale(unit.custom().factor, 1.0),
+    }
+}
+
+impl Convertible for Unit {
+    /// Converts a value into the base unit of the dimension using the factor table.
+    fn to_base(&self, v: f64) -> f64 {
+        return unit_factor(self).to_base(v);
+    }
+
+    /// Converts a value from the base unit of the dimension using the factor table.
+    fn from_base(&self, v: f64) -> f64 {
+        return unit_factor(self).to_unit(v);
+    }
+
+    /// Gets the name of the unit's `UnitDimension`.
+    fn dimension(&self) -> &str {
+        return Unit::dimension(self).as_str();
+    }
+
+    /// Gets the quantity kind of the unit (see `Unit::kind()`).
+    fn kind(&self) -> &str {
+        return Unit::kind(self);
+    }
+
+    /// Gets the unit's entry in the factor table.
+    fn factor(&self) -> Option<Factor> {
+        return Some(unit_factor(self));
+    }
+}
+
+/// The relative tolerance within which `convert()` treats two factors as identical,
+/// set once by `set_identity_tolerance()`.
+static IDENTITY_TOLERANCE: OnceLock<f64> = OnceLock::new();
+
+/// Sets the relative tolerance within which `convert()` treats two units as identical.
+///
+/// Units whose factors have the same offset and scales within this relative
+/// tolerance (e.g., a custom unit defined as 1 cm, and cm itself) are converted
+/// by returning the value unchanged, so they do not accrue rounding error. The
+/// default is 0, which only short-circuits exactly equal scales. Only the first
+/// call has an effect.
+///
+/// ## Arguments
+///
+/// * `tolerance` - The maximum relative difference of the two scales.
+///
+pub fn set_identity_tolerance(tolerance: f64) {
+    let _ = IDENTITY_TOLERANCE.set(tolerance);
+}
+
+/// Checks whether two factors are identical within the identity tolerance.
+fn is_identity(from: Factor, to: Factor) -> bool {
+    if from.offset != to.offset || from.inverse != to.inverse {
+        return false;
+    }
+    let from_scale: f64 = from.numerator / from.denominator;
+    let to_scale: f64 = to.numerator / to.denominator;
+    let tolerance: f64 = IDENTITY_TOLERANCE.get().copied().unwrap_or(0.0);
+    return (from_scale - to_scale).abs() <= tolerance * from_scale.abs().max(to_scale.abs());
+}
+
+/// Relative deviation from a reference value that `verify` still accepts.
+pub const REFERENCE_TOLERANCE: f64 = 1e-9;
+
+/// Checks the factor table for mistakes that need no reference values.
+///
+/// Every built-in unit must have a finite, positive factor whose round trip
+/// through the base unit returns 1 (within `REFERENCE_TOLERANCE`), and the base
+/// unit of every dimension, which is also its reference unit, must have a
+/// reference factor of exactly 1 with no offset. It is run on startup in debug
+/// builds, to catch a bad factor before it ships.
+///
+/// ## Returns
+///
+/// A `Vec<String>` describing each problem, which is expected to be empty.
+///
+pub fn factor_table_problems() -> Vec<String> {
+    let mut problems: Vec<String> = Vec::new();
+    for unit in Unit::all_units().iter() {
+        if let Some(problem) = factor_problem(&unit.canonical_name(), unit_factor(unit)) {
+            problems.push(problem);
+        }
+    }
+    for dimension in UnitDimension::all_dimensions().iter() {
+        let base: Unit = base_unit(dimension);
+        let factor: Factor = reference_factor(&base);
+        if factor.numerator != factor.denominator || factor.offset != 0.0 || factor.inverse {
+            problems.push(format!(
+                "base unit {} of [{}] does not have a factor of exactly 1",
+                base.canonical_name(),
+                dimension
+            ));
+        }
+    }
+
+    return problems;
+}
+
+/// Checks a single unit's factor for the mistakes `factor_table_problems()` looks for.
+///
+/// ## Arguments
+///
+/// * `name` - The name of the unit, used in the description.
+/// * `factor` - The unit's `Factor` relative to the base unit of its dimension.
+///
+/// ## Returns
+///
+/// An `Option<String>` describing the problem, or `None` if the factor is valid
+/// and converts 1 back to itself.
+///
+fn factor_problem(name: &str, factor: Factor) -> Option<String> {
+    let positive = |x: f64| -> bool { x.is_finite() && x > 0.0 };
+    if !positive(factor.numerator) || !positive(factor.denominator) || !factor.offset.is_finite() {
+        return Some(format!("{} has an invalid factor", name));
+    }
+    let round_trip: f64 = factor.to_unit(factor.to_base(1.0));
+    if (round_trip - 1.0).abs() > REFERENCE_TOLERANCE {
+        return Some(format!("{} converts 1 to itself as {}", name, round_trip));
+    }
+
+    return None;
+}
+
+/// Provides known reference conversions for checking the factor table.
+///
+/// Each entry is `(from, to, value, expected)`, where `expected` is the exact (or
+/// defined) result of converting `value` from `from` to `to`. The values come from
+/// unit definitions rather than from this crate, so they catch typos in the table.
+///
+/// ## Returns
+///
+/// A `Vec` of reference conversions covering every built-in unit.
+///
+pub fn reference_conversions() -> Vec<(Unit, Unit, f64, f64)> {
+    return vec![
+        // Temperature
+        (Unit::Celsius, Unit::Fahrenheit, 0.0, 32.0),
+        (Unit::Celsius, Unit::Fahrenheit, 100.0, 212.0),
+        (Unit::Fahrenheit, Unit::Celsius, -40.0, -40.0),
+        (Unit::Celsius, Unit::Kelvin, 0.0, 273.15),
+        (Unit::Kelvin, Unit::Fahrenheit, 0.0, -459.67),
+        // Length
+        (Unit::Inch, Unit::Centimeter, 1.0, 2.54),
+        (Unit::Foot, Unit::Inch, 1.0, 12.0),
+        (Unit::Mile, Unit::Kilometer, 1.0, 1.609344),
+        (Unit::Kilometer, Unit::Meter, 1.0, 1000.0),
+        (Unit::Meter, Unit::Millimeter, 1.0, 1000.0),
+        (Unit::Millimeter, Unit::Nanometer, 1.0, 1e6),
+        (Unit::Inch, Unit::Point, 1.0, 72.0),
+        (Unit::Inch, Unit::Pica, 1.0, 6.0),
+        (Unit::Inch, Unit::Pixel, 1.0, 96.0),
+        // Frequency
+        (Unit::Kilohertz, Unit::Hertz, 1.0, 1e3),
+        (Unit::Megahertz, Unit::Kilohertz, 1.0, 1e3),
+        (Unit::Gigahertz, Unit::Megahertz, 1.0, 1e3),
+        (Unit::Terahertz, Unit::Gigahertz, 1.0, 1e3),
+        // Data
+        (Unit::Byte, Unit::Bit, 1.0, 8.0),
+        (Unit::Kilobyte, Unit::Byte, 1.0, 1e3),
+        (Unit::Megabyte, Unit::Kilobyte, 1.0, 1e3),
+        (Unit::Gigabyte, Unit::Megabyte, 1.0, 1e3),
+        (Unit::Terabyte, Unit::Gigabyte, 1.0, 1e3),
+        (Unit::Petabyte, Unit::Terabyte, 1.0, 1e3),
+        (Unit::Kibibyte, Unit::Byte, 1.0, 1024.0),
+        (Unit::Mebibyte, Unit::Kibibyte, 1.0, 1024.0),
+        (Unit::Gibibyte, Unit::Mebibyte, 1.0, 1024.0),
+        (Unit::Tebibyte, Unit::Gibibyte, 1.0, 1024.0),
+        (Unit::Pebibyte, Unit::Tebibyte, 1.0, 1024.0),
+        // Energy
+        (Unit::Kilojoule, Unit::Joule, 1.0, 1e3),
+        (Unit::KilometerPerHour, Unit::MeterPerSecond, 36.0, 10.0),
+        (Unit::MilePerHour, Unit::KilometerPerHour, 1.0, 1.609344),
+        (Unit::Knot, Unit::KilometerPerHour, 1.0, 1.852),
+        // Fuel economy
+        (
+            Unit::KilometerPerLiter,
+            Unit::LiterPer100Kilometer,
+            20.0,
+            5.0,
+        ),
+        (
+            Unit::MilePerGallon,
+            Unit::LiterPer100Kilometer,
+            1.0,
+            378.5411784 / 1.609344,
+        ),
+        (
+            Unit::MilePerImperialGallon,
+            Unit::MilePerGallon,
+            1.0,
+            3.785411784 / 4.54609,
+        ),
+        // Time
+        (Unit::Second, Unit::Millisecond, 1.0, 1000.0),
+        (Unit::Hour, Unit::Minute, 1.0, 60.0),
+        (Unit::Day, Unit::Hour, 1.0, 24.0),
+        (Unit::Week, Unit::Day, 1.0, 7.0),
+        // Mass
+        (Unit::Gram, Unit::Milligram, 1.0, 1000.0),
+        (Unit::Tonne, Unit::Kilogram, 1.0, 1000.0),
+        (Unit::Kilogram, Unit::Pound, 1.0, 2.2046226218487757),
+        (Unit::Pound, Unit::Ounce, 1.0, 16.0),
+        // Area and volume
+        (
+            Unit::Area(Box::new(Unit::Meter)),
+            Unit::Area(Box::new(Unit::Centimeter)),
+            1.0,
+            1e4,
+        ),
+        (
+            Unit::Volume(Box::new(Unit::Meter)),
+            Unit::Volume(Box::new(Unit::Centimeter)),
+            1.0,
+            1e6,
+        ),
+    ];
+}
 
 /// Validates if two units can be converted between each other.
 ///
 /// A conversion is valid only if both units belong to the same dimension
-/// (e.g., both are temperature units or both are length units).
+/// (e.g., both are temperature units or both are length units) and measure the
+/// same kind of quantity (e.g., not torque and energy).
 ///
 /// ## Arguments
 ///
-/// * `from` - A reference to the source `Unit`.
-/// * `to` - A reference to the target `Unit`.
+/// * `from` - A reference to the source unit.
+/// * `to` - A reference to the target unit.
 ///
 /// ## Returns
 ///
 /// An `anyhow::Result` which is `Ok(())` if the units are compatible, or an 
 /// `Err` with a descriptive message if they are not.
 ///
-fn validate(from: &Unit, to: &Unit) -> Result<()> {
+pub fn validate(from: &dyn Convertible, to: &dyn Convertible) -> Result<()> {
     if from.dimension() != to.dimension() {
         bail!(format!(
             "Error: [ERROR] Cannot convert between different unit categories: [{}] {} → [{}] {}",
             from.dimension(),
-            from.to_string(),
+            from,
             to.dimension(),
-            to.to_string()
+            to
+        ));
+    }
+    if from.kind() != to.kind() {
+        bail!(format!(
+            "Error: [ERROR] Cannot convert [{}] {} → [{}] {}: both share the [{}] dimension, but {} and {} are different physical quantities.",
+            from.kind(),
+            from,
+            to.kind(),
+            to,
+            from.dimension(),
+            from.kind(),
+            to.kind()
         ));
     }
 
@@ -39,50 +547,833 @@ fn validate(from: &Unit, to: &Unit) -> Result<()> {
 /// Converts a value from a source unit to a target unit.
 ///
 /// The conversion is performed in two steps:
-/// 1. The source value is converted to a base unit for its dimension (Celsius 
-///    for temperature, Centimeter for length).
-/// 2. The value in the base unit is then converted to the target unit.
+/// 1. The source value is converted to the base unit of its dimension
+///    with `Convertible::to_base`.
+/// 2. The value in the base unit is then converted to the target unit
+///    with `Convertible::from_base`.
+///
+/// When both units are pure multiples of the base unit (no offset), the value is
+/// instead multiplied by the ratio of their factors directly, so that e.g. GiB → MiB
+/// is exactly 1024 without going through bits.
+///
+/// Converting a unit to itself, or to a unit with an identical factor (see
+/// `set_identity_tolerance()`), returns the value unchanged. Units are compared
+/// by their factors, not their names, so a unit without a factor is always
+/// converted through the base unit.
+///
+/// Converting between a unit and the reciprocal of its quantity (e.g., mpg and
+/// L/100km) divides by the value, so a value of 0 (or one so close to 0 that
+/// the result overflows) is rejected instead of giving infinity.
 ///
 /// ## Arguments
 ///
-/// * `from` - The source `Unit`.
-/// * `to` - The target `Unit`.
+/// * `from` - The source unit.
+/// * `to` - The target unit.
 /// * `value` - The `f64` value to convert.
 ///
 /// ## Returns
 ///
 /// An `anyhow::Result<f64>` containing the converted value on success,
-/// or an error if the units are incompatible.
+/// or an error if the units are incompatible or the conversion would divide
+/// by zero.
 ///
-pub fn convert(from: &Unit, to: &Unit, value: &f64) -> Result<f64> {
-    validate(&from, &to)?;
-    if *from == *to {
+pub fn convert(from: &dyn Convertible, to: &dyn Convertible, value: &f64) -> Result<f64> {
+    validate(from, to)?;
+    if let (Some(from_factor), Some(to_factor)) = (from.factor(), to.factor())
+        && is_identity(from_factor, to_factor)
+    {
         return Ok(*value);
     }
 
-    let base_val: f64 = match from {
-        // Temperature
-        Unit::Celsius => *value, // Base
-        Unit::Fahrenheit => 5.0 / 9.0 * (*value - 32.0),
-        Unit::Kelvin => *value - 273.15,
-        // Length
-        Unit::Centimeter => *value, // Base
-        Unit::Inch => *value * 2.54,
-        Unit::Kilometer => *value * 100000.0,
-        Unit::Mile => *value * 160930.0,
+    // Fast path: two purely multiplicative units are related by a single ratio,
+    // which avoids a round trip through a (possibly tiny) base unit.
+    let final_val: f64 = match (from.factor(), to.factor()) {
+        (Some(from_factor), Some(to_factor))
+            if from_factor.offset == 0.0
+                && to_factor.offset == 0.0
+                && !from_factor.inverse
+                && !to_factor.inverse =>
+        {
+            let numerator: f64 = from_factor.numerator * to_factor.denominator;
+            let denominator: f64 = from_factor.denominator * to_factor.numerator;
+            *value * numerator / denominator
+        }
+        _ => to.from_base(from.to_base(*value)),
     };
+    let is_reciprocal: bool = [from.factor(), to.factor()]
+        .iter()
+        .any(|factor| factor.is_some_and(|factor| factor.inverse));
+    if is_reciprocal && value.is_finite() && (*value == 0.0 || !final_val.is_finite()) {
+        let value: String = if *value == 0.0 {
+            String::from("0")
+        } else {
+            format!("{:e}", value)
+        };
+        bail!(format!(
+            "Error: [ERROR] Cannot convert {} {} to {} (division by zero).",
+            value, from, to
+        ));
+    }
+    if from.dimension() == UnitDimension::Data.as_str() {
+        check_data_range(from, to, value, &final_val)?;
+    }
 
-    let final_val: f64 = match to {
-        // Temperature
-        Unit::Celsius => base_val,
-        Unit::Fahrenheit => (9.0 / 5.0 * base_val) + 32.0,
-        Unit::Kelvin => base_val + 273.15,
-        // Length
-        Unit::Centimeter => base_val,
-        Unit::Inch => base_val / 2.54,
-        Unit::Kilometer => base_val / 100000.0,
-        Unit::Mile => base_val / 160930.0,
+    return Ok(final_val);
+}
+
+/// The result of a conversion together with everything that went into it.
+#[derive(Clone)]
+pub struct Conversion {
+    /// The value that was converted, in the source unit.
+    pub input: f64,
+    /// The converted value, in the target unit.
+    pub output: f64,
+    /// The source unit.
+    pub from: Unit,
+    /// The target unit.
+    pub to: Unit,
+    /// The input expressed in the base unit of its dimension (see `base_unit()`).
+    pub base_value: f64,
+}
+
+/// Converts a value like `convert()`, returning the result with its details.
+///
+/// This is meant for library users, such as user interfaces that show the value
+/// in the base unit next to the result.
+///
+/// ## Arguments
+///
+/// * `from` - The source unit.
+/// * `to` - The target unit.
+/// * `value` - The `f64` value to convert.
+///
+/// ## Returns
+///
+/// An `anyhow::Result<Conversion>` with the result on success, or the error of
+/// `convert()`.
+///
+pub fn convert_detailed(from: &Unit, to: &Unit, value: f64) -> Result<Conversion> {
+    let output: f64 = convert(from, to, &value)?;
+    return Ok(Conversion {
+        input: value,
+        output,
+        from: from.clone(),
+        to: to.clone(),
+        base_value: from.to_base(value),
+    });
+}
+
+/// Converts a value from a source unit to each of several target units.
+///
+/// Each target is converted with `convert()` on its own, so an incompatible
+/// target only fails its own entry.
+///
+/// ## Arguments
+///
+/// * `from` - The source unit.
+/// * `targets` - The target units.
+/// * `value` - The `f64` value to convert.
+///
+/// ## Returns
+///
+/// A `Vec<Result<f64>>` with one result per target, in the order of `targets`.
+///
+pub fn convert_many(from: &Unit, targets: &[Unit], value: f64) -> Vec<Result<f64>> {
+    return targets.iter().map(|to| convert(from, to, &value)).collect();
+}
+
+/// Converts a value exactly between two units with whole-number factors.
+///
+/// Data sizes (and other units that are whole multiples of their base unit)
+/// relate by integer ratios, which `f64` cannot always represent: 5 GiB is
+/// exactly 5368709120 B, but large results or fractional inputs pick up float
+/// error. Here the value, as written, is read as a decimal fraction and scaled
+/// with `u128` arithmetic instead. The result is only returned if it has a
+/// finite decimal expansion, which holds for all built-in data units.
+///
+/// ## Arguments
+///
+/// * `from` - The source `Unit`.
+/// * `to` - The target `Unit`.
+/// * `text` - The value as written, e.g. "5", "1.5" or "2e3".
+///
+/// ## Returns
+///
+/// An `Option<String>` with the exact result in decimal notation, or `None` if
+/// a unit's factor is not a whole number, the text is not a plain decimal
+/// number, or the arithmetic overflows; callers then fall back to `convert()`.
+///
+pub fn convert_exact(from: &Unit, to: &Unit, text: &str) -> Option<String> {
+    validate(from, to).ok()?;
+    let from_factor: u128 = integer_factor(from)?;
+    let to_factor: u128 = integer_factor(to)?;
+    let (negative, mantissa, exponent): (bool, u128, i32) = parse_decimal(text)?;
+
+    // value = mantissa * 10^exponent, so the result is numerator / denominator.
+    let mut numerator: u128 = mantissa.checked_mul(from_factor)?;
+    let mut denominator: u128 = to_factor;
+    if exponent >= 0 {
+        numerator = numerator.checked_mul(10u128.checked_pow(exponent as u32)?)?;
+    } else {
+        denominator = denominator.checked_mul(10u128.checked_pow(-exponent as u32)?)?;
+    }
+    let divisor: u128 = gcd(numerator, denominator);
+    numerator /= divisor;
+    denominator /= divisor;
+
+    // The result has a finite decimal expansion only if the denominator divides a power of 10.
+    let (mut rest, mut twos, mut fives): (u128, u32, u32) = (denominator, 0, 0);
+    while rest % 2 == 0 {
+        rest /= 2;
+        twos += 1;
+    }
+    while rest % 5 == 0 {
+        rest /= 5;
+        fives += 1;
+    }
+    if rest != 1 {
+        return None;
+    }
+    let places: u32 = twos.max(fives);
+    let scaled: u128 = numerator.checked_mul(10u128.checked_pow(places)? / denominator)?;
+
+    let digits: String = format!("{:0>width$}", scaled, width = places as usize + 1);
+    let (whole, fraction): (&str, &str) = digits.split_at(digits.len() - places as usize);
+    let fraction: &str = fraction.trim_end_matches('0');
+    let sign: &str = if negative && scaled != 0 { "-" } else { "" };
+    if fraction.is_empty() {
+        return Some(format!("{}{}", sign, whole));
+    }
+    return Some(format!("{}{}.{}", sign, whole, fraction));
+}
+
+/// Gets a unit's factor if it is a whole multiple of its base unit.
+///
+/// Factors above 2^53 are rejected, since the `f64` may no longer hold the
+/// exact integer.
+///
+fn integer_factor(unit: &Unit) -> Option<u128> {
+    let factor: Factor = unit_factor(unit);
+    let scale: f64 = factor.numerator / factor.denominator;
+    if factor.offset != 0.0
+        || factor.inverse
+        || scale < 1.0
+        || scale.fract() != 0.0
+        || scale > 9_007_199_254_740_992.0
+    {
+        return None;
+    }
+    return Some(scale as u128);
+}
+
+/// Parses a plain decimal number such as "-1.25" or "3e2".
+///
+/// ## Returns
+///
+/// An `Option` with the sign, the digits as an integer and the power of 10 to
+/// scale them by (e.g., "-1.25" gives `(true, 125, -2)`), or `None` if the text
+/// is not a decimal number or has too many digits.
+///
+fn parse_decimal(text: &str) -> Option<(bool, u128, i32)> {
+    let text: &str = text.trim();
+    let (negative, text): (bool, &str) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text.strip_prefix('+').unwrap_or(text)),
+    };
+    let (mantissa, exponent): (&str, i32) = match text.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (mantissa, exponent.parse().ok()?),
+        None => (text, 0),
     };
+    let (whole, fraction): (&str, &str) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let digits: String = format!("{}{}", whole, fraction);
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
 
-    return Ok(final_val);
+    let value: u128 = digits.parse().ok()?;
+    return Some((
+        negative,
+        value,
+        exponent.checked_sub(fraction.len() as i32)?,
+    ));
+}
+
+/// Computes the greatest common divisor of two integers.
+fn gcd(a: u128, b: u128) -> u128 {
+    let (mut a, mut b): (u128, u128) = (a, b);
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    return a;
+}
+
+/// Finds the source value that converts to a given target value.
+///
+/// Every conversion is an affine relation `target = a * source + b` (or, for
+/// inverse units, a reciprocal one), which is inverted by converting the target
+/// value back from the target unit to the source unit. The inverse exists only if the scale `a` is non-zero and finite,
+/// which is checked on the factors of both units.
+///
+/// ## Arguments
+///
+/// * `from` - The source unit.
+/// * `to` - The target unit.
+/// * `target` - The `f64` value expressed in the target unit.
+///
+/// ## Returns
+///
+/// An `anyhow::Result<f64>` containing the source value on success, or an error
+/// if the units are incompatible or the relation cannot be inverted.
+///
+pub fn solve_source(from: &dyn Convertible, to: &dyn Convertible, target: &f64) -> Result<f64> {
+    validate(from, to)?;
+    for unit in [from, to] {
+        if let Some(factor) = unit.factor() {
+            let scale: f64 = factor.numerator / factor.denominator;
+            if scale == 0.0 || !scale.is_finite() {
+                bail!(format!(
+                    "Error: [ERROR] The conversion {} → {} cannot be inverted.",
+                    from, to
+                ));
+            }
+        }
+    }
+
+    let source: f64 = convert(to, from, target)?;
+    if !source.is_finite() {
+        bail!(format!(
+            "Error: [ERROR] No finite {} value converts to {} {}.",
+            from, target, to
+        ));
+    }
+    return Ok(source);
+}
+
+/// Checks that a data-size conversion produced a meaningful result.
+///
+/// Data units span many orders of magnitude (a petabyte is 8e15 bits), so extreme
+/// inputs can overflow to infinity, or collapse to zero when a non-zero input is
+/// expressed in a much larger unit.
+///
+/// ## Arguments
+///
+/// * `from` - The source unit.
+/// * `to` - The target unit.
+/// * `value` - The value that was converted.
+/// * `final_val` - The value expressed in the target unit.
+///
+/// ## Returns
+///
+/// An `anyhow::Result` which is `Ok(())` if the result is usable, or an `Err`
+/// explaining why it is not.
+///
+fn check_data_range(
+    from: &dyn Convertible,
+    to: &dyn Convertible,
+    value: &f64,
+    final_val: &f64,
+) -> Result<()> {
+    if !final_val.is_finite() {
+        bail!(format!(
+            "Error: [ERROR] Converting {:e} {} to {} overflows the representable range.",
+            value, from, to
+        ));
+    }
+    if *value != 0.0 && *final_val == 0.0 {
+        bail!(format!(
+            "Error: [ERROR] Converting {:e} {} to {} loses all precision.",
+            value, from, to
+        ));
+    }
+
+    return Ok(());
+}
+
+/// Picks the most human-readable unit to express a value in.
+///
+/// Every unit of the source unit's dimension is tried, and the one whose result
+/// falls within the readable range of 1 to 1000 (closest to the middle of that range
+/// on a logarithmic scale) is chosen. If no unit lands in that range, the closest one
+/// is used. Typographic units (point, pica and pixel) are only tried when the
+/// source unit is one of them, so that e.g. 0.5 cm becomes 5 mm rather than points.
+/// Temperature is not supported, since its units differ by an offset rather than
+/// by magnitude.
+///
+/// ## Arguments
+///
+/// * `from` - The source `Unit`.
+/// * `value` - The `f64` value expressed in the source unit.
+///
+/// ## Returns
+///
+/// An `anyhow::Result<Unit>` containing the chosen target unit on success,
+/// or an error if the dimension has no meaningful magnitude ordering.
+///
+pub fn best_unit(from: &Unit, value: &f64) -> Result<Unit> {
+    if from.dimension() == UnitDimension::Temperature {
+        bail!(format!(
+            "Error: [ERROR] Cannot pick a best unit for [{}] {}.",
+            from.dimension(),
+            from
+        ));
+    }
+    if *value == 0.0 || !value.is_finite() {
+        return Ok(from.clone());
+    }
+
+    let mut best: Unit = from.clone();
+    let mut best_score: (bool, f64) = (false, f64::INFINITY);
+    let typographic = |unit: &Unit| matches!(unit, Unit::Point | Unit::Pica | Unit::Pixel);
+    for unit in from.compatible_units() {
+        if typographic(&unit) && !typographic(from) {
+            continue;
+        }
+        let magnitude: f64 = convert(from, &unit, value)?.abs();
+        let in_range: bool = (1.0..1000.0).contains(&magnitude);
+        let distance: f64 = (magnitude.log10() - 1.5).abs();
+        if (in_range && !best_score.0) || (in_range == best_score.0 && distance < best_score.1) {
+            best = unit;
+            best_score = (in_range, distance);
+        }
+    }
+
+    return Ok(best);
+}
+
+/// Picks the largest byte unit in which a data size is still at least 1.
+///
+/// The units are tried from the byte upwards, in powers of 1024 (KiB to PiB)
+/// or of 1000 (KB to PB). Sizes below one byte are given in bytes.
+///
+/// ## Arguments
+///
+/// * `from` - The source `Unit`, which must be a data size.
+/// * `value` - The `f64` value expressed in the source unit.
+/// * `binary` - Whether to scale by powers of 1024 instead of 1000.
+///
+/// ## Returns
+///
+/// An `anyhow::Result<Unit>` containing the chosen byte unit on success, or an
+/// error if the source unit is not a data size.
+///
+pub fn human_data_unit(from: &Unit, value: &f64, binary: bool) -> Result<Unit> {
+    if from.dimension() != UnitDimension::Data {
+        bail!(format!(
+            "Error: [ERROR] --human only scales data sizes, got [{}] {}.",
+            from.dimension(),
+            from
+        ));
+    }
+
+    let units: [Unit; 6] = match binary {
+        true => [
+            Unit::Byte,
+            Unit::Kibibyte,
+            Unit::Mebibyte,
+            Unit::Gibibyte,
+            Unit::Tebibyte,
+            Unit::Pebibyte,
+        ],
+        false => [
+            Unit::Byte,
+            Unit::Kilobyte,
+            Unit::Megabyte,
+            Unit::Gigabyte,
+            Unit::Terabyte,
+            Unit::Petabyte,
+        ],
+    };
+    let mut human: Unit = Unit::Byte;
+    for unit in units.into_iter().skip(1) {
+        if convert(from, &unit, value)?.abs() < 1.0 {
+            break;
+        }
+        human = unit;
+    }
+
+    return Ok(human);
+}
+
+/// The speed of light in vacuum, in meters per second.
+const SPEED_OF_LIGHT: f64 = 299_792_458.0;
+
+/// Converts between the wavelength and the frequency of light.
+///
+/// This bypasses the usual dimension check for the known relationship `c = λ·f`.
+/// One unit must be a length (the wavelength) and the other a frequency. The value
+/// is first brought to meters or Hertz, related through the speed of light, and then
+/// converted to the requested target unit.
+///
+/// ## Arguments
+///
+/// * `from` - The source `Unit` (a length or a frequency).
+/// * `to` - The target `Unit` (a frequency or a length, respectively).
+/// * `value` - The `f64` value to convert.
+///
+/// ## Returns
+///
+/// An `anyhow::Result<f64>` containing the converted value on success, or an error
+/// if the units do not form a wavelength/frequency pair or the value is not positive.
+///
+pub fn convert_light(from: &Unit, to: &Unit, value: &f64) -> Result<f64> {
+    if *value <= 0.0 {
+        bail!(format!(
+            "Error: [ERROR] Wavelength and frequency must be positive, got {}.",
+            value
+        ));
+    }
+
+    match (from.dimension(), to.dimension()) {
+        (UnitDimension::Length, UnitDimension::Frequency) => {
+            let meters: f64 = convert(from, &Unit::Centimeter, value)? / 100.0;
+            return convert(&Unit::Hertz, to, &(SPEED_OF_LIGHT / meters));
+        }
+        (UnitDimension::Frequency, UnitDimension::Length) => {
+            let hertz: f64 = convert(from, &Unit::Hertz, value)?;
+            let centimeters: f64 = SPEED_OF_LIGHT / hertz * 100.0;
+            return convert(&Unit::Centimeter, to, &centimeters);
+        }
+        _ => bail!(format!(
+            "Error: [ERROR] Wavelength mode requires a length and a frequency unit: [{}] {} → [{}] {}",
+            from.dimension(),
+            from,
+            to.dimension(),
+            to
+        )),
+    }
+}
+
+/// Converts a gradient: a quantity per unit of length (e.g., °C per meter).
+///
+/// The quantity is converted as a difference, so that offsets cancel out (a
+/// change of 1 °C is a change of 1.8 °F, not 33.8 °F). A gradient per source
+/// length is then a gradient per target length multiplied by the number of
+/// source lengths in one target length.
+///
+/// ## Arguments
+///
+/// * `from` - The source `Unit` of the quantity.
+/// * `to` - The target `Unit` of the quantity.
+/// * `per` - The source and target length units of the gradient.
+/// * `value` - The `f64` gradient to convert.
+///
+/// ## Returns
+///
+/// An `anyhow::Result<f64>` containing the converted gradient on success, or an
+/// error if the quantities are incompatible or a `per` unit is not a length.
+///
+pub fn convert_gradient(from: &Unit, to: &Unit, per: (&Unit, &Unit), value: &f64) -> Result<f64> {
+    for length in [per.0, per.1] {
+        if length.dimension() != UnitDimension::Length {
+            bail!(format!(
+                "Error: [ERROR] A gradient must be per a length unit, got [{}] {}.",
+                length.dimension(),
+                length
+            ));
+        }
+    }
+
+    let difference: f64 = convert(from, to, value)? - convert(from, to, &0.0)?;
+    let lengths: f64 = convert(per.1, per.0, &1.0)?;
+    return Ok(difference * lengths);
+}
+
+/// Converts between a mass and its energy equivalent.
+///
+/// This bypasses the usual dimension check for the known relationship `E = m·c²`.
+/// One unit must be a mass and the other an energy (not torque). The value is
+/// first brought to kilograms or joules, related through the speed of light, and
+/// then converted to the requested target unit.
+///
+/// ## Arguments
+///
+/// * `from` - The source `Unit` (a mass or an energy).
+/// * `to` - The target `Unit` (an energy or a mass, respectively).
+/// * `value` - The `f64` value to convert.
+///
+/// ## Returns
+///
+/// An `anyhow::Result<f64>` containing the converted value on success, or an error
+/// if the units do not form a mass/energy pair or the value is negative.
+///
+pub fn convert_mass_energy(from: &Unit, to: &Unit, value: &f64) -> Result<f64> {
+    if *value < 0.0 {
+        bail!(format!(
+            "Error: [ERROR] Mass and energy must not be negative, got {}.",
+            value
+        ));
+    }
+
+    let c_squared: f64 = SPEED_OF_LIGHT * SPEED_OF_LIGHT;
+    match (from.kind(), to.kind()) {
+        ("mass", "energy") => {
+            let kilograms: f64 = convert(from, &Unit::Kilogram, value)?;
+            return convert(&Unit::Joule, to, &(kilograms * c_squared));
+        }
+        ("energy", "mass") => {
+            let joules: f64 = convert(from, &Unit::Joule, value)?;
+            return convert(&Unit::Kilogram, to, &(joules / c_squared));
+        }
+        _ => bail!(format!(
+            "Error: [ERROR] Relativistic mode requires a mass and an energy unit: [{}] {} → [{}] {}",
+            from.kind(),
+            from,
+            to.kind(),
+            to
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::units::{UnitType, get_enum};
+
+    fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+        assert!(
+            (actual - expected).abs() <= tolerance,
+            "expected {} ± {}, got {}",
+            expected,
+            tolerance,
+            actual
+        );
+    }
+
+    #[test]
+    fn wavelength_converts_to_frequency_of_light() {
+        let terahertz: f64 = convert_light(&Unit::Nanometer, &Unit::Terahertz, &500.0).unwrap();
+        assert_close(terahertz, 599.585, 1e-3);
+        let nanometers: f64 =
+            convert_light(&Unit::Terahertz, &Unit::Nanometer, &terahertz).unwrap();
+        assert_close(nanometers, 500.0, 1e-9);
+        assert!(convert_light(&Unit::Nanometer, &Unit::Terahertz, &0.0).is_err());
+        assert!(convert_light(&Unit::Nanometer, &Unit::Kilometer, &500.0).is_err());
+    }
+
+    #[test]
+    fn data_sizes_report_overflow_instead_of_infinity() {
+        let bits: f64 = convert(&Unit::Terabyte, &Unit::Bit, &2.0).unwrap();
+        assert_eq!(bits, 1.6e13);
+
+        let overflow: String = convert(&Unit::Petabyte, &Unit::Bit, &1e300)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            overflow.contains("overflows the representable range"),
+            "{}",
+            overflow
+        );
+        let underflow: String = convert(&Unit::Bit, &Unit::Pebibyte, &1e-310)
+            .unwrap_err()
+            .to_string();
+        assert!(underflow.contains("loses all precision"), "{}", underflow);
+    }
+
+    #[test]
+    fn prefixed_units_convert_by_an_exact_ratio() {
+        assert_eq!(
+            convert(&Unit::Gibibyte, &Unit::Mebibyte, &1.0).unwrap(),
+            1024.0
+        );
+        assert_eq!(
+            convert(&Unit::Pebibyte, &Unit::Bit, &1.0).unwrap(),
+            9007199254740992.0
+        );
+        assert_eq!(
+            convert(&Unit::Kilometer, &Unit::Nanometer, &3.0).unwrap(),
+            3e12
+        );
+        assert_eq!(
+            convert(&Unit::Terahertz, &Unit::Megahertz, &0.1).unwrap(),
+            100000.0
+        );
+    }
+
+    #[test]
+    fn typographic_units_follow_the_inch() {
+        assert_close(
+            convert(&Unit::Point, &Unit::Pica, &12.0).unwrap(),
+            1.0,
+            1e-12,
+        );
+        assert_close(
+            convert(&Unit::Inch, &Unit::Point, &1.0).unwrap(),
+            72.0,
+            1e-12,
+        );
+        assert_close(
+            convert(&Unit::Inch, &Unit::Pixel, &1.0).unwrap(),
+            96.0,
+            1e-12,
+        );
+        for (name, unit) in [
+            ("pt", Unit::Point),
+            ("pica", Unit::Pica),
+            ("px", Unit::Pixel),
+        ] {
+            assert!(get_enum(String::from(name), UnitType::Source).unwrap() == unit);
+        }
+    }
+
+    #[test]
+    fn best_unit_skips_typographic_units() {
+        assert!(best_unit(&Unit::Centimeter, &0.5).unwrap() == Unit::Millimeter);
+        assert!(best_unit(&Unit::Meter, &1500.0).unwrap() == Unit::Kilometer);
+        assert!(best_unit(&Unit::Celsius, &20.0).is_err());
+    }
+
+    #[test]
+    fn celsius_base_round_trips_temperatures_better_than_kelvin() {
+        // Converting through a base applies `to_base` and then `from_base`. With
+        // Kelvin as the base, small Celsius and Fahrenheit values are shifted by
+        // 273.15 and back, which loses their low bits; with Celsius as the base,
+        // Celsius values pass through unchanged. Celsius is therefore the default.
+        fn round_trip(unit: &Unit, base: &Unit, v: f64) -> f64 {
+            let factor: Factor = reference_factor(unit).rebase(reference_factor(base));
+            let in_base: f64 = (v + factor.offset) * factor.numerator / factor.denominator;
+            return in_base * factor.denominator / factor.numerator - factor.offset;
+        }
+
+        let values: [f64; 4] = [0.1, 1e-3, 36.6, -40.0];
+        let error = |base: &Unit| -> f64 {
+            return values
+                .iter()
+                .map(|v| (round_trip(&Unit::Celsius, base, *v) - v).abs())
+                .sum();
+        };
+        assert_eq!(error(&Unit::Celsius), 0.0);
+        assert!(error(&Unit::Kelvin) > 0.0);
+        assert!(base_unit(&UnitDimension::Temperature) == Unit::Celsius);
+        assert_eq!(Unit::Celsius.from_base(Unit::Celsius.to_base(0.1)), 0.1);
+    }
+
+    #[test]
+    fn torque_is_not_converted_to_energy() {
+        let message: String = validate(&Unit::NewtonMeter, &Unit::Joule)
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            message,
+            "Error: [ERROR] Cannot convert [torque] n-m → [energy] j: both share the [energy] dimension, but torque and energy are different physical quantities."
+        );
+        assert!(validate(&Unit::Kilojoule, &Unit::Joule).is_ok());
+        assert!(validate(&Unit::NewtonMeter, &Unit::NewtonMeter).is_ok());
+    }
+
+    #[test]
+    fn solve_source_inverts_the_conversion() {
+        assert_eq!(
+            solve_source(&Unit::Celsius, &Unit::Fahrenheit, &212.0).unwrap(),
+            100.0
+        );
+        assert_close(
+            solve_source(&Unit::Kilometer, &Unit::Mile, &1.0).unwrap(),
+            1.609344,
+            1e-12,
+        );
+        assert!(solve_source(&Unit::Celsius, &Unit::Kilometer, &212.0).is_err());
+    }
+
+    #[test]
+    fn every_unit_round_trips_through_its_base() {
+        for unit in Unit::all_units().iter() {
+            let round_trip: f64 = unit.from_base(unit.to_base(1.0));
+            assert_close(round_trip, 1.0, 1e-12);
+            assert_eq!(convert(unit, unit, &1.0).unwrap(), 1.0, "{}", unit);
+        }
+    }
+
+    #[test]
+    fn zero_is_rejected_by_inverse_conversions() {
+        assert_eq!(
+            convert(&Unit::MilePerGallon, &Unit::LiterPer100Kilometer, &0.0)
+                .unwrap_err()
+                .to_string(),
+            "Error: [ERROR] Cannot convert 0 mpg to l/100km (division by zero)."
+        );
+        assert!(convert(&Unit::MilePerGallon, &Unit::LiterPer100Kilometer, &1e-320).is_err());
+        let liters: f64 =
+            convert(&Unit::MilePerGallon, &Unit::LiterPer100Kilometer, &30.0).unwrap();
+        assert_close(liters, 7.84049, 1e-5);
+        assert_eq!(
+            convert(&Unit::MilePerGallon, &Unit::KilometerPerLiter, &0.0).unwrap(),
+            0.0
+        );
+    }
+
+    #[test]
+    fn exact_conversion_of_data_sizes() {
+        assert_eq!(
+            convert_exact(&Unit::Gibibyte, &Unit::Byte, "5").as_deref(),
+            Some("5368709120")
+        );
+        assert_eq!(
+            convert_exact(&Unit::Pebibyte, &Unit::Bit, "3").as_deref(),
+            Some("27021597764222976")
+        );
+        assert_eq!(
+            convert_exact(&Unit::Byte, &Unit::Kibibyte, "1").as_deref(),
+            Some("0.0009765625")
+        );
+        assert!(convert_exact(&Unit::Celsius, &Unit::Fahrenheit, "1").is_none());
+    }
+
+    #[test]
+    fn mass_converts_to_its_energy_equivalent() {
+        let joules: f64 = convert_mass_energy(&Unit::Kilogram, &Unit::Joule, &1.0).unwrap();
+        assert_close(joules / 1e16, 8.98755, 1e-5);
+        let grams: f64 = convert_mass_energy(&Unit::Joule, &Unit::Gram, &joules).unwrap();
+        assert_close(grams, 1000.0, 1e-9);
+        assert_eq!(
+            convert_mass_energy(&Unit::Kilogram, &Unit::Joule, &-1.0)
+                .unwrap_err()
+                .to_string(),
+            "Error: [ERROR] Mass and energy must not be negative, got -1."
+        );
+    }
+
+    #[test]
+    fn factor_check_catches_a_bad_factor() {
+        assert!(factor_table_problems().is_empty());
+        assert_eq!(factor_problem("mile", Factor::scale(160934.4, 1.0)), None);
+        assert_eq!(
+            factor_problem("mile", Factor::scale(0.0, 1.0)).as_deref(),
+            Some("mile has an invalid factor")
+        );
+        assert_eq!(
+            factor_problem("mile", Factor::scale(f64::INFINITY, 1.0)).as_deref(),
+            Some("mile has an invalid factor")
+        );
+        // A tiny factor loses the value's low bits on the way to the base unit.
+        let tiny: Option<String> = factor_problem("mile", Factor::affine(1e-300, 1e300, 1e20));
+        assert!(tiny.is_some_and(|problem| problem.starts_with("mile converts 1 to itself as")));
+    }
+
+    #[test]
+    fn temperature_gradients_convert_as_differences() {
+        let per_foot: f64 = convert_gradient(
+            &Unit::Celsius,
+            &Unit::Fahrenheit,
+            (&Unit::Meter, &Unit::Foot),
+            &1.0,
+        )
+        .unwrap();
+        assert_close(per_foot, 0.54864, 1e-9);
+        let per_meter: f64 = convert_gradient(
+            &Unit::Celsius,
+            &Unit::Kelvin,
+            (&Unit::Meter, &Unit::Meter),
+            &2.0,
+        )
+        .unwrap();
+        assert_eq!(per_meter, 2.0);
+    }
+
+    #[test]
+    fn human_data_unit_picks_the_largest_prefix() {
+        assert!(human_data_unit(&Unit::Byte, &1536.0, true).unwrap() == Unit::Kibibyte);
+        assert!(human_data_unit(&Unit::Byte, &1000000.0, false).unwrap() == Unit::Megabyte);
+        assert!(human_data_unit(&Unit::Byte, &1000000.0, true).unwrap() == Unit::Kibibyte);
+        assert!(human_data_unit(&Unit::Meter, &1536.0, true).is_err());
+    }
 }
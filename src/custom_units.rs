@@ -0,0 +1,509 @@
+//! # Custom Units Module
+//!
+//! This module loads user-defined units from `data/units.toml`. Each unit is a
+//! `[name]` section with a `dimension`, a `factor` and an optional `symbol`:
+//!
+//! ```toml
+//! [furlong]
+//! symbol = "fur"
+//! dimension = "length"
+//! factor = 20116.8 # centimeters in one furlong
+//! ```
+//!
+//! The factor is the value of one unit in the base unit of its dimension (see
+//! `converter::base_unit()`). The file is validated as a whole when it is loaded,
+//! and every mistake is reported with the line and field it concerns. Loaded units
+//! are kept in a registry for the lifetime of the process and are parsed, listed
+//! and converted like the built-in ones (see `Unit::Custom`).
+//!
+//! Validated units are cached in `data/units.cache`, stamped with the size and
+//! modification time of the file and the version of the tool. As long as the
+//! stamp matches, later runs read the cache instead of validating the file again.
+
+use crate::config::parse_value;
+use crate::units::{Unit, UnitDimension, UnitType, get_enum};
+use anyhow::{Result, bail};
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+use std::time::UNIX_EPOCH;
+
+/// A unit defined in the custom-units file.
+pub struct CustomUnit {
+    /// The unit's full name, as written in its section header.
+    pub name: String,
+    /// The unit's symbol; the name is used if the file gives none.
+    pub symbol: String,
+    /// The dimension the unit belongs to.
+    pub dimension: UnitDimension,
+    /// The value of one unit in the base unit of its dimension.
+    pub factor: f64,
+}
+
+/// Identifies a registered custom unit, held by `Unit::Custom`.
+///
+/// Identifiers are only created by this module, one for each loaded unit, so a
+/// `Unit::Custom` always refers to a unit that exists.
+#[derive(Clone, Copy, PartialEq)]
+pub struct CustomId(usize);
+
+impl CustomId {
+    /// Gets the definition of the custom unit.
+    ///
+    /// ## Returns
+    ///
+    /// The `CustomUnit` this identifier refers to.
+    ///
+    pub fn unit(self) -> &'static CustomUnit {
+        return &registered()[self.0];
+    }
+}
+
+/// The custom units of this process, set once by `load()`.
+static REGISTRY: OnceLock<Vec<CustomUnit>> = OnceLock::new();
+
+const FILE_PATH: &str = "data/units.toml";
+const CACHE_PATH: &str = "data/units.cache";
+
+/// Loads the custom-units file and registers its units.
+///
+/// If the file does not exist, no custom units are registered. If the cache
+/// matches the file, its units are registered without reading the file;
+/// otherwise the file is validated and the cache is rebuilt.
+///
+/// ## Returns
+///
+/// An `anyhow::Result` which is an error if the file cannot be read or fails
+/// validation.
+///
+pub fn load() -> Result<()> {
+    let file_path: &Path = Path::new(FILE_PATH);
+    if !file_path.exists() {
+        return Ok(());
+    }
+
+    let stamp: Option<String> = cache_stamp(file_path);
+    if let Some(stamp) = &stamp
+        && let Some(units) = read_cache(stamp)
+    {
+        let _ = REGISTRY.set(units);
+        return Ok(());
+    }
+
+    let content: String = match fs::read_to_string(file_path) {
+        Ok(content) => content,
+        Err(e) => bail!(format!(
+            "Error: [ERROR] Could not read custom units file '{}': {}.",
+            file_path.display(),
+            e
+        )),
+    };
+    let units: Vec<CustomUnit> = parse(&content)?;
+    if let Some(stamp) = &stamp {
+        write_cache(stamp, &units);
+    }
+    let _ = REGISTRY.set(units);
+    return Ok(());
+}
+
+/// Builds the stamp that ties the cache to one state of the custom-units file.
+///
+/// The tool's version is part of the stamp, since a new version may add
+/// built-in units that a cached custom unit clashes with.
+///
+/// ## Returns
+///
+/// An `Option<String>` which is `None` if the file's size or modification time
+/// cannot be read, in which case the cache is not used.
+///
+fn cache_stamp(file_path: &Path) -> Option<String> {
+    let metadata: fs::Metadata = fs::metadata(file_path).ok()?;
+    let modified: u128 = metadata
+        .modified()
+        .ok()?
+        .duration_since(UNIX_EPOCH)
+        .ok()?
+        .as_nanos();
+    return Some(format!(
+        "unitconv {} {} {}",
+        env!("CARGO_PKG_VERSION"),
+        metadata.len(),
+        modified
+    ));
+}
+
+/// Reads the cached custom units, if the cache was built for the given stamp.
+///
+/// The first line of the cache is the stamp, followed by one unit per line as
+/// tab-separated name, symbol, dimension and the bits of the factor.
+///
+/// ## Returns
+///
+/// An `Option<Vec<CustomUnit>>` which is `None` if there is no cache, it is
+/// stale, or it cannot be read.
+///
+fn read_cache(stamp: &str) -> Option<Vec<CustomUnit>> {
+    let content: String = fs::read_to_string(CACHE_PATH).ok()?;
+    let mut lines = content.lines();
+    if lines.next()? != stamp {
+        return None;
+    }
+
+    let mut units: Vec<CustomUnit> = Vec::new();
+    for line in lines {
+        let fields: Vec<&str> = line.split('\t').collect();
+        let [name, symbol, dimension, factor] = fields[..] else {
+            return None;
+        };
+        units.push(CustomUnit {
+            name: String::from(name),
+            symbol: String::from(symbol),
+            dimension: UnitDimension::all_dimensions()
+                .into_iter()
+                .find(|known| known.as_str() == dimension)?,
+            factor: f64::from_bits(u64::from_str_radix(factor, 16).ok()?),
+        });
+    }
+    return Some(units);
+}
+
+/// Writes validated custom units to the cache.
+///
+/// The cache only saves time, so failing to write it is not an error. Units
+/// whose name or symbol contains a tab or line break are not cached.
+///
+fn write_cache(stamp: &str, units: &[CustomUnit]) {
+    let mut content: String = format!("{}\n", stamp);
+    for unit in units.iter() {
+        if [&unit.name, &unit.symbol]
+            .iter()
+            .any(|field| field.contains(['\t', '\n', '\r']))
+        {
+            let _ = fs::remove_file(CACHE_PATH);
+            return;
+        }
+        content.push_str(&format!(
+            "{}\t{}\t{}\t{:016x}\n",
+            unit.name,
+            unit.symbol,
+            unit.dimension.as_str(),
+            unit.factor.to_bits()
+        ));
+    }
+    let _ = fs::write(CACHE_PATH, content);
+}
+
+/// Gets the registered custom units.
+///
+/// ## Returns
+///
+/// A slice with every loaded custom unit, in file order.
+///
+fn registered() -> &'static [CustomUnit] {
+    match REGISTRY.get() {
+        Some(units) => return units,
+        None => return &[],
+    }
+}
+
+/// Gets a `Unit::Custom` for each registered custom unit.
+///
+/// ## Returns
+///
+/// A `Vec<Unit>` with the custom units, in file order.
+///
+pub fn units() -> Vec<Unit> {
+    return (0..registered().len())
+        .map(|index| Unit::Custom(CustomId(index)))
+        .collect();
+}
+
+/// A `[name]` section of the file, before it is validated as a whole.
+struct Section {
+    line: usize,
+    name: String,
+    /// The symbol with the line it is given on.
+    symbol: Option<(String, usize)>,
+    dimension: Option<UnitDimension>,
+    factor: Option<f64>,
+}
+
+/// Parses and validates the content of a custom-units file.
+///
+/// ## Arguments
+///
+/// * `content` - The text of the custom-units file.
+///
+/// ## Returns
+///
+/// A `Result<Vec<CustomUnit>>` with the units in file order, or an error naming
+/// the line and field of the first problem found.
+///
+fn parse(content: &str) -> Result<Vec<CustomUnit>> {
+    let mut sections: Vec<Section> = Vec::new();
+    for (i, line) in content.lines().enumerate() {
+        let line_number: usize = i + 1;
+        let line: &str = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some(header) = line.strip_prefix('[') {
+            let Some((name, _)) = header.split_once(']') else {
+                bail!(error(line_number, "expected a '[name]' section header"));
+            };
+            sections.push(Section {
+                line: line_number,
+                name: validate_name(name.trim(), line_number)?,
+                symbol: None,
+                dimension: None,
+                factor: None,
+            });
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once('=') else {
+            bail!(error(line_number, "expected 'key = value'"));
+        };
+        let Some(section) = sections.last_mut() else {
+            bail!(error(
+                line_number,
+                "'key = value' before the first '[name]' section header"
+            ));
+        };
+        let value: String = parse_value(value);
+        match key.trim() {
+            "symbol" => section.symbol = Some((validate_name(&value, line_number)?, line_number)),
+            "dimension" => section.dimension = Some(parse_dimension(&value, line_number)?),
+            "factor" => match value.parse::<f64>() {
+                Ok(factor) if factor.is_finite() && factor > 0.0 => section.factor = Some(factor),
+                _ => bail!(error(
+                    line_number,
+                    &format!(
+                        "field 'factor': '{}' is not a finite, positive number",
+                        value
+                    )
+                )),
+            },
+            key => bail!(error(line_number, &format!("unknown field '{}'", key))),
+        }
+    }
+
+    let mut units: Vec<CustomUnit> = Vec::new();
+    for section in sections.into_iter() {
+        let Some(dimension) = section.dimension else {
+            bail!(error(
+                section.line,
+                &format!("unit '{}' has no 'dimension' field", section.name)
+            ));
+        };
+        let Some(factor) = section.factor else {
+            bail!(error(
+                section.line,
+                &format!("unit '{}' has no 'factor' field", section.name)
+            ));
+        };
+        let (symbol, symbol_line): (String, usize) = section
+            .symbol
+            .unwrap_or((section.name.clone(), section.line));
+
+        for (field, value, line) in [
+            ("name", &section.name, section.line),
+            ("symbol", &symbol, symbol_line),
+        ] {
+            if get_enum(value.clone(), UnitType::Source).is_ok() {
+                bail!(error(
+                    line,
+                    &format!(
+                        "{} '{}' of unit '{}' clashes with a built-in unit",
+                        field, value, section.name
+                    )
+                ));
+            }
+            let taken_by: Option<&CustomUnit> = units.iter().find(|unit| {
+                return unit.name.eq_ignore_ascii_case(value)
+                    || unit.symbol.eq_ignore_ascii_case(value);
+            });
+            if let Some(unit) = taken_by {
+                bail!(error(
+                    line,
+                    &format!(
+                        "{} '{}' of unit '{}' is already used by unit '{}'",
+                        field, value, section.name, unit.name
+                    )
+                ));
+            }
+        }
+
+        units.push(CustomUnit {
+            name: section.name,
+            symbol,
+            dimension,
+            factor,
+        });
+    }
+
+    return Ok(units);
+}
+
+/// Formats a validation error for a line of the custom-units file.
+fn error(line: usize, message: &str) -> String {
+    return format!(
+        "Error: [ERROR] Custom units file '{}', line {}: {}.",
+        FILE_PATH, line, message
+    );
+}
+
+/// Checks that a unit name or symbol can be written on the command line.
+///
+/// Names must not be empty, start with a digit (which `--spec` would read as
+/// part of the value), or contain a character with a meaning of its own in unit
+/// arguments: "," separates target units, "^" raises a unit to a power and ">"
+/// separates the units of a `--spec`.
+///
+/// ## Returns
+///
+/// A `Result<String>` with the name, or an error naming the line.
+///
+fn validate_name(name: &str, line: usize) -> Result<String> {
+    if name.is_empty() {
+        bail!(error(line, "a unit name or symbol must not be empty"));
+    }
+    if name.starts_with(|c: char| c.is_ascii_digit()) || name.contains([',', '^', '>']) {
+        bail!(error(
+            line,
+            &format!(
+                "'{}' must not start with a digit or contain ',', '^' or '>'",
+                name
+            )
+        ));
+    }
+
+    return Ok(String::from(name));
+}
+
+/// Parses the `dimension` field of a custom unit.
+///
+/// Temperature is not accepted, since its units differ by an offset that a
+/// single factor cannot express.
+///
+/// ## Returns
+///
+/// A `Result<UnitDimension>` with the dimension, or an error naming the line.
+///
+fn parse_dimension(value: &str, line: usize) -> Result<UnitDimension> {
+    let allowed: Vec<UnitDimension> = UnitDimension::all_dimensions()
+        .into_iter()
+        .filter(|dimension| *dimension != UnitDimension::Temperature)
+        .collect();
+    match allowed
+        .into_iter()
+        .find(|dimension| dimension.as_str() == value)
+    {
+        Some(dimension) => return Ok(dimension),
+        None => {
+            let names: Vec<&str> = UnitDimension::all_dimensions()
+                .iter()
+                .filter(|dimension| **dimension != UnitDimension::Temperature)
+                .map(|dimension| dimension.as_str())
+                .collect();
+            bail!(error(
+                line,
+                &format!(
+                    "field 'dimension': '{}' is not one of {}",
+                    value,
+                    names.join(", ")
+                )
+            ));
+        }
+    }
+}
+
+/// Finds a registered custom unit by its symbol, ignoring case.
+///
+/// ## Returns
+///
+/// An `Option<Unit>` with the matching `Unit::Custom`, or `None`.
+///
+pub fn find_symbol(unit: &str) -> Option<Unit> {
+    return registered()
+        .iter()
+        .position(|custom| custom.symbol.eq_ignore_ascii_case(unit))
+        .map(|index| Unit::Custom(CustomId(index)));
+}
+
+/// Finds a registered custom unit by its full name, ignoring case.
+///
+/// ## Returns
+///
+/// An `Option<Unit>` with the matching `Unit::Custom`, or `None`.
+///
+pub fn find_name(unit: &str) -> Option<Unit> {
+    return registered()
+        .iter()
+        .position(|custom| custom.name.eq_ignore_ascii_case(unit))
+        .map(|index| Unit::Custom(CustomId(index)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_error(content: &str) -> String {
+        return parse(content).err().unwrap().to_string();
+    }
+
+    #[test]
+    fn valid_file_is_loaded() {
+        let units: Vec<CustomUnit> = parse(
+            "# Old units\n\
+             [furlong]\n\
+             symbol = \"fur\"\n\
+             dimension = \"length\"\n\
+             factor = 20116.8 # centimeters in one furlong\n\
+             \n\
+             [stone]\n\
+             dimension = \"mass\"\n\
+             factor = 6350.29318\n",
+        )
+        .unwrap();
+        assert_eq!(units.len(), 2);
+        assert_eq!(
+            (units[0].name.as_str(), units[0].symbol.as_str()),
+            ("furlong", "fur")
+        );
+        assert!(units[0].dimension == UnitDimension::Length);
+        assert_eq!(units[0].factor, 20116.8);
+        assert_eq!(units[1].symbol, "stone");
+    }
+
+    #[test]
+    fn invalid_files_report_the_line_and_field() {
+        assert_eq!(
+            parse_error("[]\ndimension = \"length\"\nfactor = 2\n"),
+            "Error: [ERROR] Custom units file 'data/units.toml', line 1: a unit name or symbol must not be empty."
+        );
+        assert_eq!(
+            parse_error("[x]\ndimension = \"flux\"\nfactor = 2\n"),
+            "Error: [ERROR] Custom units file 'data/units.toml', line 2: field 'dimension': 'flux' is not one of length, frequency, data, energy, speed, fuel, time, mass, area, volume."
+        );
+        assert_eq!(
+            parse_error("[x]\ndimension = \"length\"\nfactor = -2\n"),
+            "Error: [ERROR] Custom units file 'data/units.toml', line 3: field 'factor': '-2' is not a finite, positive number."
+        );
+        assert_eq!(
+            parse_error("[x]\nsymbol = \"km\"\ndimension = \"length\"\nfactor = 2\n"),
+            "Error: [ERROR] Custom units file 'data/units.toml', line 2: symbol 'km' of unit 'x' clashes with a built-in unit."
+        );
+        assert_eq!(
+            parse_error("[x]\ndimension = \"length\"\n"),
+            "Error: [ERROR] Custom units file 'data/units.toml', line 1: unit 'x' has no 'factor' field."
+        );
+        assert_eq!(
+            parse_error(
+                "[x]\ndimension = \"length\"\nfactor = 2\n[y]\nsymbol = \"x\"\ndimension = \"mass\"\nfactor = 3\n"
+            ),
+            "Error: [ERROR] Custom units file 'data/units.toml', line 5: symbol 'x' of unit 'y' is already used by unit 'x'."
+        );
+    }
+}
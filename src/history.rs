@@ -1,98 +1,1437 @@
 //! # Conversion History Module
 //!
 //! This module manages the persistence of conversion history. It provides functionality
-//! to load, save, add, and display conversion records from a JSON file.
+//! to load, save, add, and display conversion records from a JSON file. With
+//! `history_format = "jsonl"` in the config file, the records are kept in a JSON
+//! Lines file instead, one record per line, and new records are appended to it.
 
-use anyhow::{Ok, Result, bail};
+use crate::cli::{ExportFormat, HistoryMatch, ValueRange};
+use crate::config::HistoryFormat;
+use crate::locale;
+use crate::timezone::TimeZone;
+use crate::units::{Unit, UnitType, get_enum};
+use anyhow::{Result, bail};
 use serde::{Deserialize, Serialize};
-use std::{fs, path::Path};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::{fs, thread};
+
+/// Errors that can occur while loading or saving the history file.
+///
+/// Each variant carries the path involved and renders a message that suggests
+/// how to fix the problem.
+#[derive(Debug)]
+pub enum HistoryError {
+    /// The history file exists but could not be read.
+    Read { path: PathBuf, source: io::Error },
+    /// The history file could be read but does not contain valid history JSON.
+    Parse {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+    /// A line of a JSON Lines history file is not a valid history record.
+    ParseLine {
+        path: PathBuf,
+        line: usize,
+        source: serde_json::Error,
+    },
+    /// The history file could not be written.
+    Write { path: PathBuf, source: io::Error },
+    /// The history file or its directory is not accessible with the current permissions.
+    Permission { path: PathBuf, source: io::Error },
+    /// The parent of the history file exists but is not a directory.
+    NotADirectory { path: PathBuf },
+}
+
+impl HistoryError {
+    /// Builds the error for a failed read, distinguishing permission problems.
+    fn read(path: &Path, source: io::Error) -> Self {
+        let path: PathBuf = path.to_path_buf();
+        match source.kind() {
+            ErrorKind::PermissionDenied => return HistoryError::Permission { path, source },
+            _ => return HistoryError::Read { path, source },
+        }
+    }
+
+    /// Builds the error for a failed write, distinguishing permission problems.
+    fn write(path: &Path, source: io::Error) -> Self {
+        let path: PathBuf = path.to_path_buf();
+        match source.kind() {
+            ErrorKind::PermissionDenied => return HistoryError::Permission { path, source },
+            _ => return HistoryError::Write { path, source },
+        }
+    }
+}
+
+impl Display for HistoryError {
+    /// Formats the error together with a suggestion on how to fix it.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HistoryError::Read { path, source } => write!(
+                f,
+                "Error: [ERROR] Could not read history file '{}': {}. Check that the path points to a readable file.",
+                path.display(),
+                source
+            ),
+            HistoryError::Parse { path, source } => write!(
+                f,
+                "Error: [ERROR] History file '{}' is not valid: {}. Fix the JSON by hand, or move the file away to start a new history.",
+                path.display(),
+                source
+            ),
+            HistoryError::ParseLine { path, line, source } => write!(
+                f,
+                "Error: [ERROR] History file '{}', line {} is not a valid record: {}. Fix or delete that line by hand.",
+                path.display(),
+                line,
+                source
+            ),
+            HistoryError::Write { path, source } => write!(
+                f,
+                "Error: [ERROR] Could not write history file '{}': {}. Check that the disk is not full and the directory is writable.",
+                path.display(),
+                source
+            ),
+            HistoryError::Permission { path, source } => write!(
+                f,
+                "Error: [ERROR] Permission denied for history file '{}': {}. Check the file permissions (e.g., chmod u+rw '{}').",
+                path.display(),
+                source,
+                path.display()
+            ),
+            HistoryError::NotADirectory { path } => write!(
+                f,
+                "Error: [ERROR] History path parent '{}' exists but is not a directory. Move or rename that file so the history directory can be created.",
+                path.display()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for HistoryError {
+    /// Returns the underlying IO or JSON error, if any.
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            HistoryError::Read { source, .. }
+            | HistoryError::Write { source, .. }
+            | HistoryError::Permission { source, .. } => return Some(source),
+            HistoryError::Parse { source, .. } | HistoryError::ParseLine { source, .. } => {
+                return Some(source);
+            }
+            HistoryError::NotADirectory { .. } => return None,
+        }
+    }
+}
 
 /// A single entry in the conversion history.
+///
+/// Besides the formatted `value` line, newer records also store the structured
+/// parts of the conversion. Records written by older versions only contain
+/// `value`, so the structured fields are optional.
 #[derive(Serialize, Deserialize)]
 pub struct HistoryRecord {
     pub value: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    /// When the conversion was made, in UNIX seconds (UTC).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<i64>,
+    /// A hash of the other fields, set by `History::add()` (see `HistoryRecord::checksum()`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<String>,
+    /// Whether the record is kept when the history is rotated (see `History::rotate()`).
+    /// It is not part of the checksum, since pinning does not change the conversion.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub pinned: bool,
+}
+
+impl Display for HistoryRecord {
+    /// Formats the record as its conversion line, followed by its note if it has one.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.note {
+            Some(note) => write!(f, "{} ({})", self.value, note),
+            None => write!(f, "{}", self.value),
+        }
+    }
+}
+
+impl HistoryRecord {
+    /// Gets the source unit of the record, if it is structured and still recognized.
+    ///
+    /// ## Returns
+    ///
+    /// An `Option<Unit>` which is `None` for legacy (flat) records.
+    ///
+    pub fn source_unit(&self) -> Option<Unit> {
+        let from: &String = self.from.as_ref()?;
+        return get_enum(from.clone(), UnitType::Source).ok();
+    }
+
+    /// Computes the checksum of the record's fields.
+    ///
+    /// This is a 64-bit FNV-1a hash, which is enough to notice a record that was
+    /// edited by hand but is not meant to resist deliberate forgery. Numbers are
+    /// hashed with 12 significant digits so that reading them back from JSON
+    /// cannot change the result.
+    ///
+    /// ## Returns
+    ///
+    /// A `String` with the hash as 16 hexadecimal digits.
+    ///
+    pub fn checksum(&self) -> String {
+        let number = |value: Option<f64>| -> String {
+            match value {
+                Some(value) => return format!("{:.12e}", value),
+                None => return String::new(),
+            }
+        };
+        let fields: [String; 7] = [
+            self.value.clone(),
+            self.from.clone().unwrap_or_default(),
+            self.to.clone().unwrap_or_default(),
+            number(self.input),
+            number(self.output),
+            self.note.clone().unwrap_or_default(),
+            self.timestamp.map(|t| t.to_string()).unwrap_or_default(),
+        ];
+
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        for byte in fields.join("\u{1f}").bytes() {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+        return format!("{:016x}", hash);
+    }
+
+    /// Checks whether the record still matches its checksum.
+    ///
+    /// Records written before checksums were introduced have none and are
+    /// always accepted.
+    ///
+    pub fn is_intact(&self) -> bool {
+        match &self.checksum {
+            Some(checksum) => return *checksum == self.checksum(),
+            None => return true,
+        }
+    }
+
+    /// Gets the record's fields as text, for exporting it as a table row.
+    ///
+    /// ## Arguments
+    ///
+    /// * `timestamp` - Formats the timestamp (UNIX seconds) of the record.
+    ///
+    /// ## Returns
+    ///
+    /// The value, from, to, input, output, note and timestamp fields, with an
+    /// empty string for each field the record does not have.
+    ///
+    fn fields(&self, timestamp: impl Fn(i64) -> String) -> [String; 7] {
+        return [
+            self.value.clone(),
+            self.from.clone().unwrap_or_default(),
+            self.to.clone().unwrap_or_default(),
+            self.input
+                .map(|input| input.to_string())
+                .unwrap_or_default(),
+            self.output
+                .map(|output| output.to_string())
+                .unwrap_or_default(),
+            self.note.clone().unwrap_or_default(),
+            self.timestamp.map(timestamp).unwrap_or_default(),
+        ];
+    }
+
+    /// Formats the record for display, prefixed by its timestamp if it has one.
+    ///
+    /// ## Arguments
+    ///
+    /// * `timezone` - The `TimeZone` in which the timestamp is shown.
+    ///
+    /// ## Returns
+    ///
+    /// A `String` such as `[2024-07-04 08:00:00 -04:00] 100.0 °C = 212.0 °F`.
+    ///
+    pub fn display_in(&self, timezone: &TimeZone) -> String {
+        match self.timestamp {
+            Some(timestamp) => return format!("[{}] {}", timezone.format(timestamp), self),
+            None => return self.to_string(),
+        }
+    }
+}
+
+/// Settings controlling how history records are printed.
+pub struct HistoryView {
+    /// The time zone in which timestamps are shown.
+    pub timezone: TimeZone,
+    /// The maximum number of characters per printed line, or `None` to never truncate.
+    pub max_width: Option<usize>,
+}
+
+impl HistoryView {
+    /// Formats a numbered record line, truncating it to `max_width` with an ellipsis.
+    /// Pinned records are marked with a star.
+    ///
+    /// ## Arguments
+    ///
+    /// * `number` - The position of the record in the history (1-based).
+    /// * `record` - The `HistoryRecord` to format.
+    ///
+    /// ## Returns
+    ///
+    /// A `String` with the line, at most `max_width` characters long.
+    ///
+    pub fn line(&self, number: usize, record: &HistoryRecord) -> String {
+        let star: &str = match (record.pinned, locale::ascii_only()) {
+            (false, _) => "",
+            (true, false) => "★ ",
+            (true, true) => "* ",
+        };
+        let line: String = format!("{}. {}{}", number, star, record.display_in(&self.timezone));
+        match self.max_width {
+            Some(width) if line.chars().count() > width => {
+                let kept: String = line.chars().take(width.saturating_sub(1)).collect();
+                return format!("{}…", kept);
+            }
+            _ => return line,
+        }
+    }
 }
 
 /// The collection of all conversion history records.
 #[derive(Serialize, Deserialize, Default)]
 pub struct History {
     pub list: Vec<HistoryRecord>,
+    /// The most recently used unit names, most recent first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub recent_units: Vec<String>,
+    /// Records added during this run that have not been committed to `list` yet.
+    #[serde(skip)]
+    pending: Vec<HistoryRecord>,
+    /// The format of the history file.
+    #[serde(skip)]
+    format: HistoryFormat,
+    /// The number of records in `list` that are already in the history file.
+    #[serde(skip)]
+    saved: usize,
+    /// Whether the next save must rewrite the whole JSON Lines file instead of
+    /// appending to it, e.g. to drop an incomplete last record.
+    #[serde(skip)]
+    rewrite: bool,
 }
 
 impl History {
     const FILE_PATH: &'static str = "data/history.json";
+    const JSONL_FILE_PATH: &'static str = "data/history.jsonl";
+    const FOLLOW_INTERVAL: Duration = Duration::from_millis(500);
+    const RECENT_UNITS: usize = 8;
+    const SAVE_ATTEMPTS: u32 = 3;
+    const SAVE_RETRY_DELAY: Duration = Duration::from_millis(50);
 
-    /// Loads conversion history from the JSON file.
+    /// Gets the path of the history file for a storage format.
+    fn file_path(format: HistoryFormat) -> &'static Path {
+        match format {
+            HistoryFormat::Json => return Path::new(Self::FILE_PATH),
+            HistoryFormat::Jsonl => return Path::new(Self::JSONL_FILE_PATH),
+        }
+    }
+
+    /// Loads conversion history from the history file.
     ///
     /// If the file does not exist, it returns a new, empty `History` instance.
+    /// A warning is printed for every record whose checksum no longer matches
+    /// its fields, since it was probably edited by hand.
+    ///
+    /// ## Arguments
+    ///
+    /// * `format` - The `HistoryFormat` of the file (see `Config::history_format`).
+    ///
+    /// ## Returns
+    ///
+    /// A `Result<Self, HistoryError>` which is the loaded `History` on success, or a
+    /// `HistoryError` if the file cannot be read or parsed.
+    ///
+    pub fn load(format: HistoryFormat) -> Result<Self, HistoryError> {
+        let history: History = Self::read(format, true)?;
+        for (i, record) in history.list.iter().enumerate() {
+            history.warn_if_edited(i, record);
+        }
+        return Ok(history);
+    }
+
+    /// Reads the history file without checking the checksums of its records.
+    ///
+    /// ## Arguments
+    ///
+    /// * `format` - The `HistoryFormat` of the file.
+    /// * `warn` - Whether to warn about an incomplete last record of a JSON Lines
+    ///   file (see `from_lines()`).
     ///
     /// ## Returns
     ///
-    /// An `anyhow::Result<Self>` which is the loaded `History` on success, or an
-    /// error if the file cannot be read or parsed.
+    /// A `Result<Self, HistoryError>` which is the read `History`, or a
+    /// `HistoryError` if the file cannot be read or parsed.
     ///
-    pub fn load() -> Result<Self> {
-        let file_path: &Path = Path::new(Self::FILE_PATH);
+    fn read(format: HistoryFormat, warn: bool) -> Result<Self, HistoryError> {
+        let file_path: &Path = Self::file_path(format);
         if !file_path.exists() {
-            return Ok(Self::default());
+            return Ok(History {
+                format,
+                ..Self::default()
+            });
         }
 
-        let str_json: String = fs::read_to_string(file_path)?;
-        let list: History = serde_json::from_str(&str_json)?;
+        let content: String =
+            fs::read_to_string(file_path).map_err(|e| HistoryError::read(file_path, e))?;
+        let mut list: History = match format {
+            HistoryFormat::Json => {
+                serde_json::from_str(&content).map_err(|e| HistoryError::Parse {
+                    path: file_path.to_path_buf(),
+                    source: e,
+                })?
+            }
+            HistoryFormat::Jsonl => Self::from_lines(&content, file_path, warn)?,
+        };
+        list.format = format;
+        list.saved = list.list.len();
         return Ok(list);
     }
 
-    /// Saves the current conversion history to the JSON file.
+    /// Warns if a record does not match its checksum, since it was probably edited by hand.
     ///
-    /// It serializes the `History` struct into a pretty-printed JSON string and 
-    /// writes it to the file. It also ensures the parent directory exists.
+    /// ## Arguments
+    ///
+    /// * `index` - The zero-based position of the record in the history.
+    /// * `record` - The `HistoryRecord` to check.
+    ///
+    fn warn_if_edited(&self, index: usize, record: &HistoryRecord) {
+        if !record.is_intact() {
+            eprintln!(
+                "Warning: [WARNING] History file '{}', record {}: '{}' does not match its checksum; it may have been edited by hand.",
+                Self::file_path(self.format).display(),
+                index + 1,
+                record
+            );
+        }
+    }
+
+    /// Reads the records of a JSON Lines history file.
+    ///
+    /// Blank lines are skipped. A last line that is not valid and not ended by a
+    /// newline was cut off by an interrupted write, so it is skipped with a
+    /// warning instead of failing the whole load (and removed by the next save).
+    /// The recently used units are rebuilt from the records, since the file only
+    /// holds records.
+    ///
+    /// ## Arguments
+    ///
+    /// * `content` - The text of the history file.
+    /// * `file_path` - The path of the history file, for messages.
+    /// * `warn` - Whether to warn about a skipped incomplete record.
     ///
     /// ## Returns
     ///
-    /// An `anyhow::Result<()>` indicating success or failure of the save operation.
+    /// A `Result<Self, HistoryError>` with the records, or a `HistoryError` naming
+    /// the first invalid line.
     ///
-    pub fn save(&self) -> Result<()> {
-        let file_path: &Path = Path::new(Self::FILE_PATH);
-        let data: String = serde_json::to_string_pretty(&self)?;
+    fn from_lines(content: &str, file_path: &Path, warn: bool) -> Result<Self, HistoryError> {
+        let mut history: History = History::default();
+        let line_count: usize = content.lines().count();
+        for (i, line) in content.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<HistoryRecord>(line) {
+                Ok(record) => {
+                    if let (Some(from), Some(to)) = (&record.from, &record.to) {
+                        history.touch_recent([to.clone(), from.clone()]);
+                    }
+                    history.list.push(record);
+                }
+                Err(_) if i + 1 == line_count && !content.ends_with('\n') => {
+                    history.rewrite = true;
+                    if warn {
+                        eprintln!(
+                            "Warning: [WARNING] History file '{}', line {}: skipping an incomplete record.",
+                            file_path.display(),
+                            i + 1
+                        );
+                    }
+                }
+                Err(e) => {
+                    return Err(HistoryError::ParseLine {
+                        path: file_path.to_path_buf(),
+                        line: i + 1,
+                        source: e,
+                    });
+                }
+            }
+        }
+
+        return Ok(history);
+    }
+
+    /// Saves the current conversion history to the history file.
+    ///
+    /// In the JSON format, it serializes the `History` struct into a pretty-printed
+    /// JSON string and rewrites the file. In the JSON Lines format, only the records
+    /// committed since the file was loaded are appended, one per line, unless
+    /// existing records changed (or an incomplete last record has to be dropped)
+    /// and the file is rewritten. It also ensures the parent directory exists, and
+    /// reports a clear error if the parent path exists but is not a directory.
+    ///
+    /// A rewritten file is replaced atomically (see `write_atomic()`), and a write
+    /// that fails because another process holds the file is retried with a
+    /// growing delay (see `retry()`) before the error is reported.
+    ///
+    /// ## Returns
+    ///
+    /// A `Result<(), HistoryError>` indicating success or failure of the save operation.
+    ///
+    pub fn save(&self) -> Result<(), HistoryError> {
+        let file_path: &Path = Self::file_path(self.format);
+        let data: String = match self.format {
+            HistoryFormat::Json => serde_json::to_string_pretty(&self)
+                .map_err(|e| HistoryError::write(file_path, io::Error::other(e)))?,
+            HistoryFormat::Jsonl => {
+                let mut data: String = String::new();
+                let skip: usize = if self.rewrite { 0 } else { self.saved };
+                for record in self.list.iter().skip(skip) {
+                    let line: String = serde_json::to_string(record)
+                        .map_err(|e| HistoryError::write(file_path, io::Error::other(e)))?;
+                    data.push_str(&line);
+                    data.push('\n');
+                }
+                data
+            }
+        };
 
         // Create parent directory if it doesn't exist
         if let Some(parent) = file_path.parent() {
-            fs::create_dir_all(parent)?;
+            if parent.exists() && !parent.is_dir() {
+                return Err(HistoryError::NotADirectory {
+                    path: parent.to_path_buf(),
+                });
+            }
+            fs::create_dir_all(parent).map_err(|e| HistoryError::write(parent, e))?;
         }
 
-        fs::write(file_path, data)?;
-        return Ok(());
+        let written: io::Result<()> = match self.format {
+            HistoryFormat::Json => Self::retry(|| Self::write_atomic(file_path, &data)),
+            HistoryFormat::Jsonl if self.rewrite => {
+                Self::retry(|| Self::write_atomic(file_path, &data))
+            }
+            // The new records are written at once, so that a crash cuts off at most the last line.
+            HistoryFormat::Jsonl => Self::retry(|| {
+                return OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(file_path)
+                    .and_then(|mut file| file.write_all(data.as_bytes()));
+            }),
+        };
+        return written.map_err(|e| HistoryError::write(file_path, e));
+    }
+
+    /// Replaces a file with new content atomically.
+    ///
+    /// The content is written to a temporary file next to it, which is then
+    /// renamed over the file, so that readers see either the old or the new
+    /// history, never a partly written one.
+    ///
+    /// ## Arguments
+    ///
+    /// * `file_path` - The path of the file to replace.
+    /// * `data` - The new content of the file.
+    ///
+    fn write_atomic(file_path: &Path, data: &str) -> io::Result<()> {
+        let mut temp_name = file_path.as_os_str().to_os_string();
+        temp_name.push(".tmp");
+        let temp_path: PathBuf = PathBuf::from(temp_name);
+        let written: io::Result<()> =
+            fs::write(&temp_path, data).and_then(|_| fs::rename(&temp_path, file_path));
+        if written.is_err() {
+            let _ = fs::remove_file(&temp_path);
+        }
+        return written;
+    }
+
+    /// Runs a write, retrying it while the file is held by another process.
+    ///
+    /// On Windows, a file that another `unitconv` is reading or writing cannot
+    /// be replaced for a moment. Such a write is tried `SAVE_ATTEMPTS` times in
+    /// all, waiting `SAVE_RETRY_DELAY` longer before each new attempt (50 ms,
+    /// then 100 ms). Other errors are returned at once.
+    ///
+    /// ## Arguments
+    ///
+    /// * `write` - The write to run.
+    ///
+    /// ## Returns
+    ///
+    /// An `io::Result<()>` with the outcome of the last attempt.
+    ///
+    fn retry(write: impl FnMut() -> io::Result<()>) -> io::Result<()> {
+        return Self::retry_when(Self::is_locked, write);
+    }
+
+    /// Runs a write like `retry()`, retrying the errors that `locked` accepts.
+    ///
+    /// ## Arguments
+    ///
+    /// * `locked` - Checks whether an error may go away on its own.
+    /// * `write` - The write to run.
+    ///
+    /// ## Returns
+    ///
+    /// An `io::Result<()>` with the outcome of the last attempt.
+    ///
+    fn retry_when(
+        locked: impl Fn(&io::Error) -> bool,
+        mut write: impl FnMut() -> io::Result<()>,
+    ) -> io::Result<()> {
+        let mut attempt: u32 = 1;
+        loop {
+            match write() {
+                Err(e) if attempt < Self::SAVE_ATTEMPTS && locked(&e) => {
+                    thread::sleep(Self::SAVE_RETRY_DELAY * attempt);
+                    attempt += 1;
+                }
+                written => return written,
+            }
+        }
+    }
+
+    /// Checks whether a failed write may succeed once another process releases the file.
+    ///
+    /// Only the Windows sharing and lock violations qualify; any other error,
+    /// such as a denied permission, would fail the same way again.
+    fn is_locked(error: &io::Error) -> bool {
+        // ERROR_SHARING_VIOLATION and ERROR_LOCK_VIOLATION.
+        return cfg!(windows) && matches!(error.raw_os_error(), Some(32) | Some(33));
+    }
+
+    /// Moves unit names to the front of the recently used units, in the given order.
+    fn touch_recent(&mut self, names: [String; 2]) {
+        for name in names {
+            self.recent_units.retain(|used| *used != name);
+            self.recent_units.insert(0, name);
+        }
+        self.recent_units.truncate(Self::RECENT_UNITS);
     }
 
     /// Adds a new conversion record to the history.
     ///
+    /// The record is staged until `commit()` is called, so that a command that
+    /// fails halfway does not leave part of its records behind. Both units are
+    /// moved to the front of the recently used units, and the record gets the
+    /// checksum that `load()` verifies.
+    ///
     /// ## Arguments
     ///
     /// * `record` - The `String` representation of the conversion result to add.
+    /// * `from` - The source `Unit` of the conversion.
+    /// * `to` - The target `Unit` of the conversion.
+    /// * `input` - The value that was converted.
+    /// * `output` - The converted value.
+    /// * `note` - An optional free-text note attached to the conversion.
+    ///
+    pub fn add(
+        &mut self,
+        record: String,
+        from: &Unit,
+        to: &Unit,
+        input: f64,
+        output: f64,
+        note: Option<String>,
+    ) {
+        self.touch_recent([to.to_string(), from.to_string()]);
+
+        let mut record: HistoryRecord = HistoryRecord {
+            value: record,
+            from: Some(from.to_string()),
+            to: Some(to.to_string()),
+            input: Some(input),
+            output: Some(output),
+            note,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .ok()
+                .map(|elapsed| elapsed.as_secs() as i64),
+            checksum: None,
+            pinned: false,
+        };
+        record.checksum = Some(record.checksum());
+        self.pending.push(record);
+    }
+
+    /// Rewrites the structured records with the current unit symbols.
+    ///
+    /// The units of each record are parsed again from its `from` and `to` fields
+    /// and written back with their current names. In its display string only
+    /// the unit following each value is replaced by its current symbol, so the
+    /// numbers keep their precision and anything after the unit (such as the
+    /// distance of a gradient) is kept as it was. A side whose unit is written
+    /// differently, such as a humanized duration, is left unchanged. The
+    /// checksum of a rewritten record is updated; records without a checksum
+    /// keep having none. Legacy records, and records whose units are no longer
+    /// recognized, are skipped. The history still has to be saved afterwards.
+    ///
+    /// Nothing is rewritten while a record does not match its checksum, since a
+    /// new checksum would hide that it was edited.
+    ///
+    /// ## Arguments
+    ///
+    /// * `symbol` - Gives the symbol a unit is displayed with.
+    ///
+    /// ## Returns
+    ///
+    /// A `Result` with the number of records that changed and the number that
+    /// were skipped, or an error listing the records that fail their checksum.
+    ///
+    pub fn normalize(&mut self, symbol: impl Fn(&Unit) -> String) -> Result<(usize, usize)> {
+        let edited: Vec<String> = self
+            .list
+            .iter()
+            .enumerate()
+            .filter(|(_, record)| !record.is_intact())
+            .map(|(i, _)| (i + 1).to_string())
+            .collect();
+        if !edited.is_empty() {
+            bail!(format!(
+                "Error: [ERROR] History record(s) {} do not match their checksum; check or remove them before normalizing.",
+                edited.join(", ")
+            ));
+        }
+
+        let mut changed: usize = 0;
+        let mut skipped: usize = 0;
+        for record in self.list.iter_mut() {
+            let (Some(old_from), Some(old_to)) = (record.from.clone(), record.to.clone()) else {
+                skipped += 1;
+                continue;
+            };
+            let (Ok(from), Ok(to)) = (
+                get_enum(old_from.clone(), UnitType::Source),
+                get_enum(old_to.clone(), UnitType::Target),
+            ) else {
+                skipped += 1;
+                continue;
+            };
+
+            let value: String = match record.value.split_once(" = ") {
+                Some((source, target)) => format!(
+                    "{} = {}",
+                    Self::replace_unit(source, &old_from, &symbol(&from)),
+                    Self::replace_unit(target, &old_to, &symbol(&to))
+                ),
+                None => record.value.clone(),
+            };
+            let (from, to): (String, String) = (from.to_string(), to.to_string());
+            if record.value == value
+                && record.from.as_ref() == Some(&from)
+                && record.to.as_ref() == Some(&to)
+            {
+                continue;
+            }
+            record.value = value;
+            record.from = Some(from);
+            record.to = Some(to);
+            if record.checksum.is_some() {
+                record.checksum = Some(record.checksum());
+            }
+            changed += 1;
+        }
+        self.rewrite = true;
+
+        return Ok((changed, skipped));
+    }
+
+    /// Replaces the unit that follows the value of one side of a record.
+    ///
+    /// ## Arguments
+    ///
+    /// * `side` - One side of a display string, such as `10.0 kilometer`.
+    /// * `old` - The unit as it was stored with the record.
+    /// * `new` - The current symbol of that unit.
+    ///
+    /// ## Returns
+    ///
+    /// The side with its unit replaced, or unchanged if the value is not
+    /// followed by `old` as a whole word.
+    ///
+    fn replace_unit(side: &str, old: &str, new: &str) -> String {
+        let Some((value, unit)) = side.split_once(' ') else {
+            return String::from(side);
+        };
+        let Some(rest) = unit.strip_prefix(old) else {
+            return String::from(side);
+        };
+        if rest.starts_with(|c: char| c.is_alphanumeric()) {
+            return String::from(side);
+        }
+        return format!("{} {}{}", value, new, rest);
+    }
+
+    /// Removes every record and the recently used units from the history.
+    ///
+    /// The history still has to be saved afterwards. The audit log, if any, is
+    /// not affected.
+    ///
+    /// ## Returns
     ///
-    pub fn add(&mut self, record: String) {
-        self.list.push(HistoryRecord { value: record });
+    /// The number of records that were removed.
+    ///
+    pub fn clear(&mut self) -> usize {
+        let removed: usize = self.list.len();
+        self.list.clear();
+        self.recent_units.clear();
+        self.rewrite = true;
+        return removed;
+    }
+
+    /// Removes records that repeat the same conversion.
+    ///
+    /// Two structured records are the same conversion if their units, input,
+    /// output and note are equal, however they were formatted; legacy records
+    /// are compared by their display string and note. Of each group of equal
+    /// records, only the first or the last one is kept, and it is pinned if any
+    /// of them was. The history still has to be saved afterwards.
+    ///
+    /// ## Arguments
+    ///
+    /// * `keep_last` - Whether to keep the most recent record of each group
+    ///   instead of the oldest one.
+    ///
+    /// ## Returns
+    ///
+    /// The number of records that were removed.
+    ///
+    pub fn dedupe(&mut self, keep_last: bool) -> usize {
+        let key = |record: &HistoryRecord| -> String {
+            let conversion: String = match (&record.from, &record.to, record.input, record.output) {
+                (Some(from), Some(to), Some(input), Some(output)) => {
+                    format!("{}\u{1f}{}\u{1f}{:e}\u{1f}{:e}", from, to, input, output)
+                }
+                _ => record.value.clone(),
+            };
+            return format!(
+                "{}\u{1e}{}",
+                conversion,
+                record.note.clone().unwrap_or_default()
+            );
+        };
+
+        let before: usize = self.list.len();
+        let mut records: Vec<HistoryRecord> = std::mem::take(&mut self.list);
+        if keep_last {
+            records.reverse();
+        }
+        let mut kept: Vec<HistoryRecord> = Vec::new();
+        let mut seen: HashMap<String, usize> = HashMap::new();
+        for record in records.into_iter() {
+            match seen.get(&key(&record)) {
+                Some(&index) => kept[index].pinned |= record.pinned,
+                None => {
+                    seen.insert(key(&record), kept.len());
+                    kept.push(record);
+                }
+            }
+        }
+        if keep_last {
+            kept.reverse();
+        }
+        self.list = kept;
+
+        let removed: usize = before - self.list.len();
+        if removed > 0 {
+            self.rewrite = true;
+        }
+        return removed;
+    }
+
+    /// Pins or unpins a record, so that rotation keeps or may drop it.
+    ///
+    /// The history still has to be saved afterwards.
+    ///
+    /// ## Arguments
+    ///
+    /// * `number` - The position of the record in the history (1-based), as shown by `history`.
+    /// * `pinned` - Whether the record is pinned.
+    ///
+    /// ## Returns
+    ///
+    /// An `anyhow::Result<&HistoryRecord>` with the record, or an error if there
+    /// is no record with that number.
+    ///
+    pub fn set_pinned(&mut self, number: usize, pinned: bool) -> Result<&HistoryRecord> {
+        let count: usize = self.list.len();
+        let Some(record) = number
+            .checked_sub(1)
+            .and_then(|index| self.list.get_mut(index))
+        else {
+            bail!(format!(
+                "Error: [ERROR] There is no record {}; the history has {} record(s).",
+                number, count
+            ));
+        };
+        record.pinned = pinned;
+        self.rewrite = true;
+        return Ok(record);
+    }
+
+    /// Drops the oldest unpinned records until at most `limit` records are left.
+    ///
+    /// Pinned records are never dropped, so the history may stay above the
+    /// limit if more than `limit` records are pinned. The history still has to
+    /// be saved afterwards.
+    ///
+    /// ## Arguments
+    ///
+    /// * `limit` - The maximum number of records (see `Config::history_limit`).
+    ///
+    /// ## Returns
+    ///
+    /// The number of records that were dropped.
+    ///
+    pub fn rotate(&mut self, limit: usize) -> usize {
+        let mut excess: usize = self.list.len().saturating_sub(limit);
+        let before: usize = self.list.len();
+        self.list.retain(|record| {
+            if excess > 0 && !record.pinned {
+                excess -= 1;
+                return false;
+            }
+            return true;
+        });
+        let dropped: usize = before - self.list.len();
+        if dropped > 0 {
+            self.rewrite = true;
+        }
+        return dropped;
+    }
+
+    /// Gets the records added since the last commit.
+    pub fn pending(&self) -> &[HistoryRecord] {
+        return &self.pending;
+    }
+
+    /// Checks whether any records were added since the last commit.
+    pub fn has_pending(&self) -> bool {
+        return !self.pending.is_empty();
+    }
+
+    /// Moves the records staged by `add()` into the history, to be written by `save()`.
+    pub fn commit(&mut self) {
+        self.list.append(&mut self.pending);
     }
 
     /// Prints the entire conversion history to the console.
     ///
     /// If the history is empty, it returns an error with a corresponding message.
     ///
+    /// ## Arguments
+    ///
+    /// * `view` - The `HistoryView` controlling how records are shown.
+    ///
     /// ## Returns
     ///
     /// An `anyhow::Result<()>` which is `Ok(())` on success, or an error if
     /// the history is empty.
     ///
-    pub fn print(&self) -> Result<()> {
+    pub fn print(&self, view: &HistoryView) -> Result<()> {
         if self.list.is_empty() {
             bail!("No conversion history found.");
         }
 
         println!("Conversion History:");
         for (i, record) in self.list.iter().enumerate() {
-            println!("{}. {}", i + 1, record.value);
+            println!("{}", view.line(i + 1, record));
+        }
+
+        return Ok(());
+    }
+
+    /// Prints one page of the conversion history, followed by a "Page N/M" footer.
+    ///
+    /// Records keep their position in the full history as their number, so page 2
+    /// of 10-record pages shows records 11 to 20.
+    ///
+    /// ## Arguments
+    ///
+    /// * `page` - The page to print, counting from 1.
+    /// * `page_size` - The number of records per page.
+    /// * `view` - The `HistoryView` controlling how records are shown.
+    ///
+    /// ## Returns
+    ///
+    /// An `anyhow::Result<()>` which is `Ok(())` on success, or an error if
+    /// the history is empty or the page does not exist.
+    ///
+    pub fn print_page(&self, page: usize, page_size: usize, view: &HistoryView) -> Result<()> {
+        if self.list.is_empty() {
+            bail!("No conversion history found.");
+        }
+        if page_size == 0 {
+            bail!("Error: [ERROR] The page size must be at least 1.");
+        }
+        let pages: usize = self.list.len().div_ceil(page_size);
+        if page == 0 || page > pages {
+            bail!(format!(
+                "Error: [ERROR] Page {} does not exist; the history has {} page(s) of {} records.",
+                page, pages, page_size
+            ));
+        }
+
+        println!("Conversion History:");
+        let start: usize = (page - 1) * page_size;
+        for (i, record) in self.list.iter().enumerate().skip(start).take(page_size) {
+            println!("{}", view.line(i + 1, record));
         }
+        println!("Page {}/{}", page, pages);
 
         return Ok(());
     }
+
+    /// Prints the conversion history grouped by the dimension of the source unit.
+    ///
+    /// Groups are printed in the order in which they first appear, each under a
+    /// header with the number of records it holds. Records keep their position in
+    /// the full history as their number. Legacy records without structured fields
+    /// are collected in a final "ungrouped" group.
+    ///
+    /// ## Arguments
+    ///
+    /// * `view` - The `HistoryView` controlling how records are shown.
+    ///
+    /// ## Returns
+    ///
+    /// An `anyhow::Result<()>` which is `Ok(())` on success, or an error if
+    /// the history is empty.
+    ///
+    pub fn print_by_dimension(&self, view: &HistoryView) -> Result<()> {
+        if self.list.is_empty() {
+            bail!("No conversion history found.");
+        }
+
+        let mut groups: Vec<(String, Vec<usize>)> = Vec::new();
+        let mut ungrouped: Vec<usize> = Vec::new();
+        for (i, record) in self.list.iter().enumerate() {
+            match record.source_unit() {
+                Some(unit) => {
+                    let name: String = unit.dimension().to_string();
+                    match groups.iter_mut().find(|(group, _)| *group == name) {
+                        Some((_, indices)) => indices.push(i),
+                        None => groups.push((name, vec![i])),
+                    }
+                }
+                None => ungrouped.push(i),
+            }
+        }
+        if !ungrouped.is_empty() {
+            groups.push((String::from("ungrouped"), ungrouped));
+        }
+
+        println!("Conversion History (grouped by dimension):");
+        for (name, indices) in groups.iter() {
+            println!("\n[{}] ({})", name, indices.len());
+            for i in indices.iter() {
+                println!("{}", view.line(i + 1, &self.list[*i]));
+            }
+        }
+
+        return Ok(());
+    }
+
+    /// Prints the records whose input or output value lies within a range.
+    ///
+    /// Records keep their position in the full history as their number. Legacy
+    /// records without structured fields have no numeric value and never match.
+    ///
+    /// ## Arguments
+    ///
+    /// * `range` - The inclusive `ValueRange` to search.
+    /// * `field` - Whether the record's input or output value is compared.
+    /// * `view` - The `HistoryView` controlling how records are shown.
+    ///
+    /// ## Returns
+    ///
+    /// An `anyhow::Result<()>` which is `Ok(())` on success, or an error if
+    /// no record lies within the range.
+    ///
+    pub fn print_in_range(
+        &self,
+        range: &ValueRange,
+        field: &HistoryMatch,
+        view: &HistoryView,
+    ) -> Result<()> {
+        let matches: Vec<(usize, &HistoryRecord)> = self
+            .list
+            .iter()
+            .enumerate()
+            .filter(|(_, record)| {
+                let value: Option<f64> = match field {
+                    HistoryMatch::Input => record.input,
+                    HistoryMatch::Output => record.output,
+                };
+                return value.is_some_and(|value| range.contains(value));
+            })
+            .collect();
+        if matches.is_empty() {
+            bail!(
+                "No conversion history found with a value between {} and {}.",
+                range.start,
+                range.end
+            );
+        }
+
+        println!("Conversion History:");
+        for (i, record) in matches.iter() {
+            println!("{}", view.line(i + 1, record));
+        }
+
+        return Ok(());
+    }
+
+    /// Writes the whole conversion history to a file in the chosen format.
+    ///
+    /// An empty history is exported too: as an empty JSON array, a CSV header
+    /// row, or a Markdown table without rows.
+    ///
+    /// ## Arguments
+    ///
+    /// * `format` - The `ExportFormat` of the file.
+    /// * `path` - The path of the file to write.
+    ///
+    /// ## Returns
+    ///
+    /// An `anyhow::Result<()>` which is an error if the records cannot be
+    /// serialized or the file cannot be written.
+    ///
+    pub fn export(&self, format: &ExportFormat, path: &Path) -> Result<()> {
+        let content: String = match format {
+            ExportFormat::Json => self.to_json()?,
+            ExportFormat::Csv => self.to_csv(),
+            ExportFormat::Md => self.to_markdown(),
+        };
+        if let Err(e) = fs::write(path, content) {
+            bail!(format!(
+                "Error: [ERROR] Could not write export file '{}': {}.",
+                path.display(),
+                e
+            ));
+        }
+
+        println!(
+            "Exported {} record(s) to '{}'.",
+            self.list.len(),
+            path.display()
+        );
+        return Ok(());
+    }
+
+    /// Serializes the history records as a pretty-printed JSON array.
+    fn to_json(&self) -> Result<String> {
+        match serde_json::to_string_pretty(&self.list) {
+            Ok(json) => return Ok(json + "\n"),
+            Err(e) => bail!(format!(
+                "Error: [ERROR] Could not serialize the history: {}.",
+                e
+            )),
+        }
+    }
+
+    /// Formats the history records as CSV, one row per record after a header row.
+    ///
+    /// Missing fields are left empty, and timestamps stay in UNIX seconds.
+    ///
+    fn to_csv(&self) -> String {
+        let mut csv: String = String::from("value,from,to,input,output,note,timestamp\n");
+        for record in self.list.iter() {
+            let fields: [String; 7] = record.fields(|timestamp| timestamp.to_string());
+            let row: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+            csv.push_str(&row.join(","));
+            csv.push('\n');
+        }
+        return csv;
+    }
+
+    /// Formats the history records as a Markdown table with aligned columns.
+    ///
+    /// Timestamps are shown in UTC.
+    ///
+    fn to_markdown(&self) -> String {
+        let header: [String; 7] = [
+            "Conversion",
+            "From",
+            "To",
+            "Input",
+            "Output",
+            "Note",
+            "Timestamp",
+        ]
+        .map(String::from);
+        let mut rows: Vec<[String; 7]> = vec![header];
+        for record in self.list.iter() {
+            let fields: [String; 7] = record.fields(|timestamp| TimeZone::Utc.format(timestamp));
+            rows.push(fields.map(|field| field.replace('|', "\\|")));
+        }
+
+        let mut widths: [usize; 7] = [3; 7];
+        for row in rows.iter() {
+            for (width, cell) in widths.iter_mut().zip(row.iter()) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+        let line = |cells: Vec<String>| -> String {
+            return format!("| {} |\n", cells.join(" | "));
+        };
+
+        let mut table: String = String::new();
+        for (i, row) in rows.iter().enumerate() {
+            let cells: Vec<String> = row
+                .iter()
+                .zip(widths.iter())
+                .map(|(cell, width)| {
+                    let padding: usize = width.saturating_sub(cell.chars().count());
+                    return format!("{}{}", cell, " ".repeat(padding));
+                })
+                .collect();
+            table.push_str(&line(cells));
+            if i == 0 {
+                table.push_str(&line(
+                    widths.iter().map(|width| "-".repeat(*width)).collect(),
+                ));
+            }
+        }
+        return table;
+    }
+
+    /// Gets the size and modification time of the history file, if it exists.
+    fn file_stamp(&self) -> Option<(u64, SystemTime)> {
+        let metadata: fs::Metadata = fs::metadata(Self::file_path(self.format)).ok()?;
+        return Some((metadata.len(), metadata.modified().ok()?));
+    }
+
+    /// Prints the history, then keeps printing new records as they are saved.
+    ///
+    /// The history file is polled for changes in its size or modification time.
+    /// Whenever it changes, it is reloaded and the records beyond those already
+    /// printed are shown. Only these new records are checked against their
+    /// checksums, so that warnings about older ones are not repeated on every
+    /// reload, and a record that another process is still appending is skipped
+    /// silently. A file that is being rewritten by another process may
+    /// briefly fail to parse; such reads are skipped until the next change. The
+    /// function runs until the process is interrupted (e.g., with Ctrl-C).
+    ///
+    /// ## Arguments
+    ///
+    /// * `view` - The `HistoryView` controlling how records are shown.
+    ///
+    /// ## Returns
+    ///
+    /// An `anyhow::Result<()>` which is only returned early if writing to the
+    /// standard output fails.
+    ///
+    pub fn follow(&self, view: &HistoryView) -> Result<()> {
+        println!("Conversion History:");
+        for (i, record) in self.list.iter().enumerate() {
+            println!("{}", view.line(i + 1, record));
+        }
+        std::io::stdout().flush()?;
+
+        let mut printed: usize = self.list.len();
+        let mut stamp: Option<(u64, SystemTime)> = self.file_stamp();
+        loop {
+            thread::sleep(Self::FOLLOW_INTERVAL);
+            let current_stamp: Option<(u64, SystemTime)> = self.file_stamp();
+            if current_stamp == stamp {
+                continue;
+            }
+            let current: History = match Self::read(self.format, false) {
+                Ok(current) => current,
+                Err(_) => continue,
+            };
+            stamp = current_stamp;
+
+            // The file was replaced by a shorter one; start over from its end.
+            if current.list.len() < printed {
+                printed = current.list.len();
+            }
+            for (i, record) in current.list.iter().enumerate().skip(printed) {
+                current.warn_if_edited(i, record);
+                println!("{}", view.line(i + 1, record));
+            }
+            printed = current.list.len();
+            std::io::stdout().flush()?;
+        }
+    }
+}
+
+/// Quotes a CSV field if it contains a comma, a quote or a line break.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        return format!("\"{}\"", field.replace('"', "\"\""));
+    }
+    return String::from(field);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(value: &str, from: &str, to: &str, input: f64, output: f64) -> HistoryRecord {
+        let mut record: HistoryRecord = HistoryRecord {
+            value: String::from(value),
+            from: Some(String::from(from)),
+            to: Some(String::from(to)),
+            input: Some(input),
+            output: Some(output),
+            note: None,
+            timestamp: None,
+            checksum: None,
+            pinned: false,
+        };
+        record.checksum = Some(record.checksum());
+        return record;
+    }
+
+    fn normalize(history: &mut History) -> Result<(usize, usize)> {
+        return history.normalize(|unit| return locale::unit_name(unit, &locale::Language::En));
+    }
+
+    #[test]
+    fn normalize_rewrites_outdated_symbols() {
+        let mut history: History = History::default();
+        history.list.push(record(
+            "10 kilometer = 6.2137 mile",
+            "kilometer",
+            "mile",
+            10.0,
+            6.2137,
+        ));
+        history.list.push(HistoryRecord {
+            checksum: None,
+            ..record("0 C = 32 F", "C", "F", 0.0, 32.0)
+        });
+
+        history.list.push(record(
+            "10.0 celsius/km = 28.9682 fahrenheit/miles",
+            "celsius",
+            "fahrenheit",
+            10.0,
+            28.9682,
+        ));
+        history.list.push(record(
+            "93784 second = 1 d 2 h 3 min 4 s",
+            "second",
+            "minute",
+            93784.0,
+            1563.0667,
+        ));
+        history.list.push(record(
+            "1 kilometer = 0.62137119 mile",
+            "kilometer",
+            "mile",
+            1.0,
+            0.62137119,
+        ));
+
+        assert_eq!(normalize(&mut history).unwrap(), (5, 0));
+        assert_eq!(history.list[0].value, "10 km = 6.2137 miles");
+        assert_eq!(history.list[0].from.as_deref(), Some("km"));
+        assert!(history.list[0].checksum.is_some() && history.list[0].is_intact());
+        assert_eq!(history.list[1].value, "0 °C = 32 °F");
+        assert_eq!(history.list[1].from.as_deref(), Some("celsius"));
+        assert_eq!(history.list[1].checksum, None);
+        assert_eq!(history.list[2].value, "10.0 °C/km = 28.9682 °F/miles");
+        assert_eq!(history.list[3].value, "93784 s = 1 d 2 h 3 min 4 s");
+        assert_eq!(history.list[3].to.as_deref(), Some("min"));
+        assert_eq!(history.list[4].value, "1 km = 0.62137119 miles");
+    }
+
+    #[test]
+    fn normalize_refuses_tampered_records() {
+        let mut history: History = History::default();
+        history.list.push(record(
+            "10 kilometer = 6.2137 mile",
+            "kilometer",
+            "mile",
+            10.0,
+            6.2137,
+        ));
+        history
+            .list
+            .push(record("1 m = 3.2808 ft", "meter", "foot", 1.0, 3.2808));
+        history.list[1].output = Some(4.0);
+
+        let error: String = normalize(&mut history).unwrap_err().to_string();
+        assert!(error.contains("record(s) 2 do not match"), "{}", error);
+        assert_eq!(history.list[0].value, "10 kilometer = 6.2137 mile");
+        assert!(!history.list[1].is_intact());
+    }
+
+    #[test]
+    fn history_errors_are_classified() {
+        let path: &Path = Path::new("data/history.jsonl");
+        let parse: HistoryError = History::from_lines("{\"value\": 1}\n", path, false)
+            .err()
+            .unwrap();
+        assert!(matches!(parse, HistoryError::ParseLine { line: 1, .. }));
+
+        let denied: HistoryError =
+            HistoryError::write(path, io::Error::from(ErrorKind::PermissionDenied));
+        assert!(matches!(denied, HistoryError::Permission { .. }));
+        assert!(denied.to_string().contains("Check the file permissions"));
+        let full: HistoryError = HistoryError::write(path, io::Error::from(ErrorKind::StorageFull));
+        assert!(matches!(full, HistoryError::Write { .. }));
+    }
+
+    #[test]
+    fn retries_a_transient_lock() {
+        let mut attempts: u32 = 0;
+        let written: io::Result<()> = History::retry_when(
+            |e| e.raw_os_error() == Some(32),
+            || {
+                attempts += 1;
+                if attempts == 1 {
+                    return Err(io::Error::from_raw_os_error(32));
+                }
+                return Ok(());
+            },
+        );
+        assert!(written.is_ok());
+        assert_eq!(attempts, 2);
+    }
+
+    #[test]
+    fn does_not_retry_other_errors() {
+        let mut attempts: u32 = 0;
+        let written: io::Result<()> = History::retry(|| {
+            attempts += 1;
+            return Err(io::Error::from(ErrorKind::PermissionDenied));
+        });
+        assert_eq!(written.unwrap_err().kind(), ErrorKind::PermissionDenied);
+        assert_eq!(attempts, 1);
+        assert!(!History::is_locked(&io::Error::from(
+            ErrorKind::ResourceBusy
+        )));
+        assert_eq!(
+            History::is_locked(&io::Error::from_raw_os_error(33)),
+            cfg!(windows)
+        );
+    }
 }
@@ -3,68 +3,468 @@
 //! This crate contains the core logic for the unit conversion application.
 //! It handles command-line argument parsing, dispatches commands, performs
 //! conversions, and manages conversion history.
+//!
+//! The conversion core is also usable as a library: `convert()` works on any
+//! type implementing the `Convertible` trait, including the built-in `Unit`.
+
+#![allow(clippy::needless_return)]
 
+#[cfg(feature = "history")]
+mod audit;
+#[cfg(feature = "json")]
+mod bulk;
 mod cli;
+mod clipboard;
+mod config;
 mod converter;
+mod custom_units;
+#[cfg(feature = "history")]
 mod history;
+#[cfg(not(feature = "history"))]
+#[path = "no_history.rs"]
+mod history;
+mod locale;
+mod presets;
+mod random;
+mod syslog;
+#[cfg(feature = "history")]
+mod timezone;
 mod units;
 
-use crate::cli::{Cli, Commands};
-use crate::converter::convert;
+#[cfg(feature = "history")]
+use crate::cli::{HistoryAction, HistoryArgs, HistoryGrouping, KeepOccurrence};
+use crate::cli::{
+    CheckArgs, Cli, Commands, ConvertArgs, PhysicalRelation, Precision, RoundingMode, SizeScale,
+    SourceValue, parse_source_value, split_leading_number,
+};
+use crate::config::Config;
+use crate::converter::{
+    best_unit, convert_exact, factor_table_problems, convert_gradient, convert_light,
+    convert_mass_energy, human_data_unit, reference_conversions, set_identity_tolerance,
+    solve_source, validate,
+};
 use crate::history::History;
-use crate::units::{Unit, UnitType, get_enum};
-use anyhow::{Context, Result};
-use clap::Parser;
+#[cfg(feature = "history")]
+use crate::history::HistoryView;
+use crate::locale::{Language, set_charset, unit_name};
+use crate::random::Rng;
+#[cfg(feature = "history")]
+use crate::timezone::TimeZone;
+use crate::units::{ParseOptions, UnitType, get_enum, get_enum_with, get_enums, mismatch_message};
+use anyhow::{Result, bail};
+use clap::error::ErrorKind;
+use clap::{Parser, ValueEnum};
+use std::io::{self, BufRead, IsTerminal, Write};
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+use wasm_bindgen::prelude::wasm_bindgen;
+
+pub use crate::converter::{
+    Conversion, Convertible, base_unit, convert, convert_detailed, convert_many,
+};
+pub use crate::units::{Unit, UnitDimension, UnitSystem};
+
+/// An error whose message has been suppressed by `--quiet-errors`.
+///
+/// The binary prints nothing for this error and only exits with its code.
+#[derive(Debug)]
+pub struct SilentError {
+    pub exit_code: i32,
+}
+
+impl std::fmt::Display for SilentError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "error suppressed by --quiet-errors (exit code {})",
+            self.exit_code
+        )
+    }
+}
+
+impl std::error::Error for SilentError {}
+
+/// Converts a value between two units given by name.
+///
+/// This is a string-in, string-out wrapper around `get_enum()` and `convert()`
+/// for callers that cannot use the Rust types, such as JavaScript through a
+/// WebAssembly binding. Unit names are parsed like `--from` and `--to`. On
+/// `wasm32`, the function is exported to JavaScript with `wasm_bindgen`.
+///
+/// ## Arguments
+///
+/// * `from` - The name or symbol of the source unit (e.g., "celsius", "km").
+/// * `to` - The name or symbol of the target unit.
+/// * `value` - The value to convert.
+///
+/// ## Returns
+///
+/// A `Result<f64, String>` with the converted value, or the error message
+/// (without the "Error: [ERROR]" prefix) if a unit is unknown or the units are
+/// incompatible.
+/// 
+#[cfg(feature = "wasm")]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
+pub fn convert_str(from: &str, to: &str, value: f64) -> std::result::Result<f64, String> {
+    let message = |e: anyhow::Error| -> String {
+        return e
+            .to_string()
+            .trim_start_matches("Error: [ERROR] ")
+            .to_string();
+    };
+    let from: Unit = get_enum(from.to_string(), UnitType::Source).map_err(message)?;
+    let to: Unit = get_enum(to.to_string(), UnitType::Target).map_err(message)?;
+    return convert(&from, &to, &value).map_err(message);
+}
+
+/// Parses arbitrary text the way unit and value arguments are parsed.
+///
+/// This is an entry point for fuzzers. The input goes through the unit parser
+/// (plain, with `--strict-case`, and with `--symbols-only`), the value parser
+/// (including the precision it was written with), and the `--spec` parser. It
+/// must return for any input, valid or not, and never panic.
+///
+/// ## Arguments
+///
+/// * `input` - The text to parse.
+///
+/// ## Returns
+///
+/// An `anyhow::Result<()>` which is `Ok(())` if the input is a valid unit,
+/// value, or spec, and the unit parser's error otherwise.
+/// 
+pub fn fuzz_parse(input: &str) -> Result<()> {
+    let recent: Vec<String> = Vec::new();
+    let unit: Result<Unit> = get_enum(String::from(input), UnitType::Source);
+    for (strict_case, symbols_only) in [(true, false), (false, true)] {
+        let options: ParseOptions = ParseOptions {
+            strict_case,
+            symbols_only,
+            recent: &recent,
+            dimension: None,
+            assumed_dimension: None,
+        };
+        let _ = get_enum_with(String::from(input), UnitType::Target, &options);
+    }
+
+    let value: bool = match parse_source_value(input) {
+        Ok(value) => {
+            let _ = (value.decimal_places(), value.significant_digits());
+            true
+        }
+        Err(_) => false,
+    };
+    let spec: bool = match parse_spec(input) {
+        Ok((_, from, to)) => {
+            let _ = get_enum(from, UnitType::Source);
+            let _ = get_enum(to, UnitType::Target);
+            true
+        }
+        Err(_) => false,
+    };
+
+    if value || spec {
+        return Ok(());
+    }
+    return unit.map(|_| ());
+}
 
 /// Runs the main application logic.
 ///
 /// This function parses command-line arguments, loads the conversion history,
 /// and executes the appropriate command (convert, list, or history).
 /// If a conversion is performed, it saves the updated history to a file.
+/// With `--quiet-errors`, any error is replaced by a `SilentError`. With
+/// `--no-implicit-help` or `UNITCONV_NO_HELP`, a missing command is an error
+/// (exit code 2) instead of a reason to print the help.
 /// 
 pub fn run() -> Result<()> {
-    let cli: Cli = Cli::parse();
-    let mut history: History = History::load().unwrap_or_default();
-    let mut updated: bool = false;
+    // The flags are looked up directly so that they also cover argument errors.
+    let quiet_errors: bool = std::env::args().skip(1).any(|arg| arg == "--quiet-errors");
+    let no_implicit_help: bool = std::env::args()
+        .skip(1)
+        .any(|arg| arg == "--no-implicit-help")
+        || std::env::var("UNITCONV_NO_HELP").is_ok_and(|value| !matches!(value.as_str(), "" | "0"));
+    let cli: Cli = match Cli::try_parse() {
+        Ok(cli) => cli,
+        Err(e)
+            if no_implicit_help
+                && matches!(
+                    e.kind(),
+                    ErrorKind::DisplayHelpOnMissingArgumentOrSubcommand
+                        | ErrorKind::MissingSubcommand
+                ) =>
+        {
+            if !quiet_errors {
+                eprintln!("Error: [ERROR] No command given (see 'unitconv --help').");
+            }
+            return Err(SilentError { exit_code: 2 }.into());
+        }
+        Err(e) if quiet_errors && e.use_stderr() => {
+            return Err(SilentError {
+                exit_code: e.exit_code(),
+            }
+            .into());
+        }
+        Err(e) => e.exit(),
+    };
+
+    match execute(cli) {
+        Err(_) if quiet_errors => return Err(SilentError { exit_code: 1 }.into()),
+        result => return result,
+    }
+}
+
+/// Executes a parsed command.
+///
+/// The history is saved once, after the command has finished. If the command
+/// fails, none of its new records are saved, unless `--commit-partial` was given.
+///
+/// ## Arguments
+///
+/// * `cli` - The parsed `Cli` arguments.
+///
+/// ## Returns
+///
+/// An `anyhow::Result` indicating success or failure.
+/// 
+fn execute(mut cli: Cli) -> Result<()> {
+    if cfg!(debug_assertions) {
+        let duplicates: Vec<String> = Unit::duplicate_names();
+        assert!(
+            duplicates.is_empty(),
+            "Units must have unique symbols, names and aliases, found duplicate {}",
+            duplicates.join(", ")
+        );
+        let problems: Vec<String> = factor_table_problems();
+        assert!(
+            problems.is_empty(),
+            "The factor table is inconsistent: {}",
+            problems.join("; ")
+        );
+    }
+    set_charset(&cli.charset);
+    let config: Config = Config::load()?;
+    if let Commands::Convert(args) = &mut cli.command {
+        args.quiet = cli.quiet_errors;
+        if args.decimals.is_none() {
+            args.decimals = env_decimals()?.or(config.decimals);
+        }
+    }
+    if let Some(tolerance) = config.identity_tolerance {
+        set_identity_tolerance(tolerance);
+    }
+    custom_units::load()?;
+    // Only the commands that read or write the history load it, so that a damaged
+    // history file does not stop commands such as 'list' or 'verify'.
+    let mut history: History = match &cli.command {
+        Commands::Convert(_) => History::load(config.history_format)?,
+        #[cfg(feature = "history")]
+        Commands::History(_) | Commands::SeedHistory { .. } => {
+            History::load(config.history_format)?
+        }
+        _ => History::default(),
+    };
+    let commit_partial: bool = match &cli.command {
+        Commands::Convert(args) => args.commit_partial,
+        _ => false,
+    };
+    // Only real conversions are audited, not sample records from seed-history.
+    #[cfg(feature = "history")]
+    let audit_log: Option<&std::path::Path> = match &cli.command {
+        Commands::Convert(_) => config.audit_log.as_deref(),
+        _ => None,
+    };
 
-    match cli.command {
-        Commands::Convert { from, to, value } => {
-            handle_convert(from, to, value, &mut history, &mut updated)?;
+    let result: Result<()> = match cli.command {
+        Commands::Convert(args) => handle_convert(*args, &mut history),
+        Commands::List { aliases, emoji } => {
+            if aliases {
+                Unit::print_aliases();
+            } else {
+                Unit::print(emoji);
+            }
+            Ok(())
         }
-        Commands::List => Unit::print(),
-        Commands::History => history.print()?,
+        #[cfg(feature = "history")]
+        Commands::History(args) => handle_history(args, &mut history, &config),
+        Commands::Verify => verify(&config),
+        Commands::Check(args) => check(args),
+        Commands::Diag { dimension, value } => diag(&dimension, value),
+        Commands::Preset { name, value } => preset(&name, value),
+        Commands::Config { print: _ } => print_config(&config),
+        #[cfg(feature = "history")]
+        Commands::SeedHistory { count } => seed_history(count, &mut history),
+    };
+
+    // New records are only written if the whole command succeeded, unless
+    // --commit-partial asks to keep the ones made before the failure.
+    if (result.is_ok() || commit_partial) && history.has_pending() {
+        #[cfg(feature = "history")]
+        if let Some(path) = audit_log {
+            audit::append(path, history.pending())?;
+        }
+        history.commit();
+        #[cfg(feature = "history")]
+        if let Some(limit) = config.history_limit {
+            history.rotate(limit);
+        }
+        history.save()?;
     }
 
-    if updated {
-        history
-            .save()
-            .context("Failed to save conversion history.")?;
+    return result;
+}
+
+/// Reads the default number of decimal places from the `UNITCONV_DECIMALS` variable.
+///
+/// The variable is the fallback for `--decimals`: the flag takes precedence over
+/// it, and it takes precedence over `decimals` in the config file and the
+/// built-in default.
+///
+/// ## Returns
+///
+/// An `anyhow::Result<Option<usize>>` which is `None` if the variable is not set,
+/// or an error if it is not a non-negative integer.
+///
+fn env_decimals() -> Result<Option<usize>> {
+    let Ok(decimals) = std::env::var("UNITCONV_DECIMALS") else {
+        return Ok(None);
+    };
+    match decimals.trim().parse() {
+        Ok(decimals) => return Ok(Some(decimals)),
+        Err(_) => bail!(format!(
+            "Error: [ERROR] UNITCONV_DECIMALS must be a non-negative integer, got '{}'.",
+            decimals
+        )),
     }
+}
 
-    return Ok(());
+/// Options controlling how values are rendered by `format_value()`.
+#[derive(Clone, Copy)]
+struct FormatOptions {
+    /// Minimum width of a formatted value; shorter values are left-padded with spaces.
+    pad: usize,
+    /// Maximum number of decimal places shown for source values.
+    source_decimals: usize,
+    /// Maximum number of decimal places shown for target values.
+    decimals: usize,
+    /// How values are rounded to their number of decimal places.
+    rounding: RoundingMode,
+}
+
+impl Default for FormatOptions {
+    /// Creates the options used when no formatting flags are given.
+    fn default() -> Self {
+        return FormatOptions {
+            pad: 0,
+            source_decimals: 6,
+            decimals: 4,
+            rounding: RoundingMode::Standard,
+        };
+    }
+}
+
+/// Rounds a value to a number of decimal places, resolving ties to the even digit.
+///
+/// This is "banker's rounding": 2.5 rounds to 2 and 3.5 rounds to 4, so that rounding
+/// errors do not accumulate in one direction when many values are summed.
+///
+/// ## Arguments
+///
+/// * `value` - The `f64` value to round.
+/// * `decimals` - The number of decimal places to keep.
+///
+/// ## Returns
+///
+/// The rounded `f64`.
+/// 
+fn round_half_even(value: f64, decimals: usize) -> f64 {
+    let scale: f64 = 10f64.powi(decimals as i32);
+    let scaled: f64 = value * scale;
+    // With that many decimals, every f64 is already exact.
+    if !scaled.is_finite() {
+        return value;
+    }
+    let floor: f64 = scaled.floor();
+    let fraction: f64 = scaled - floor;
+
+    // Round up above the halfway point, and at exactly halfway only if the
+    // lower neighbour is odd.
+    let round_up: bool = fraction > 0.5 || (fraction == 0.5 && floor % 2.0 != 0.0);
+    let rounded: f64 = if round_up { floor + 1.0 } else { floor };
+
+    return rounded / scale;
+}
+
+/// Snaps a value to the nearest multiple of an increment (`--round-to`).
+///
+/// ## Arguments
+///
+/// * `value` - The `f64` value to snap.
+/// * `increment` - The positive increment, or `None` to keep the value as it is.
+///
+/// ## Returns
+///
+/// The snapped `f64`, or `value` itself if the multiple is out of range.
+/// 
+fn round_to_increment(value: f64, increment: Option<f64>) -> f64 {
+    let Some(increment) = increment else {
+        return value;
+    };
+    let snapped: f64 = (value / increment).round() * increment;
+    if !snapped.is_finite() {
+        return value;
+    }
+
+    return snapped;
 }
 
+/// The most decimal places `format_value()` shows.
+///
+/// The exact decimal expansion of every `f64` ends by the 1074th decimal place,
+/// so more decimals would only add zeros (and the formatter panics on precisions
+/// above `u16::MAX`).
+const MAX_DECIMALS: usize = 1074;
+
+/// The largest result magnitude that is shown without a warning.
+///
+/// An `f64` holds about 15 significant decimal digits, so the integer digits of
+/// larger results are partly noise (e.g., 1e20 km is printed as
+/// 100000000000000005366162204393472.0 nm).
+const MAX_DISPLAY_MAGNITUDE: f64 = 1e15;
+
 /// Formats a floating-point value into a cleaned-up string.
 ///
-/// - Target values are formatted to a maximum of 4 decimal places.
+/// - Source values are formatted to a maximum of `options.source_decimals` decimal places.
+/// - Target values are formatted to a maximum of `options.decimals` decimal places.
+/// - With `RoundingMode::Banker`, ties are rounded to the even digit beforehand.
 /// - Trailing zeros and unnecessary decimal points are removed.
 /// - Ensures that whole numbers are formatted with `.0`.
+/// - Negative zero, and negative values that round to zero, are shown as `0.0`.
+/// - The result (including any minus sign) is right-aligned to `options.pad` characters.
+/// - Decimals are capped at `MAX_DECIMALS`.
 ///
 /// ## Arguments
 ///
 /// * `value` - The `f64` value to format.
-/// * `unit_type` - The type of unit (`Source` or `Target`) to determine 
-///    formatting precision.
+/// * `unit_type` - The type of unit (`Source` or `Target`) to determine
+///   formatting precision.
+/// * `options` - The `FormatOptions` to apply.
 ///
 /// ## Returns
 ///
 /// A formatted `String`.
 /// 
-fn format_value(value: f64, unit_type: UnitType) -> String {
-    let mut str_value: String = match unit_type {
-        UnitType::Source => value.to_string(),
-        UnitType::Target => format!("{:.4}", value).to_string(),
+fn format_value(value: f64, unit_type: UnitType, options: &FormatOptions) -> String {
+    let decimals: usize = match unit_type {
+        UnitType::Source => options.source_decimals,
+        UnitType::Target => options.decimals,
+    }
+    .min(MAX_DECIMALS);
+    let value: f64 = match options.rounding {
+        RoundingMode::Standard => value,
+        RoundingMode::Banker => round_half_even(value, decimals),
     };
+    let mut str_value: String = format!("{:.*}", decimals, value);
 
     if str_value.contains('.') {
         str_value = str_value.trim_end_matches('0').to_string();
@@ -74,50 +474,1959 @@ fn format_value(value: f64, unit_type: UnitType) -> String {
     } else {
         str_value.push_str(".0");
     }
+    if str_value == "-0.0" {
+        str_value = String::from("0.0");
+    }
+
+    return pad_left(&str_value, options.pad);
+}
+
+/// Right-aligns text to a width by prepending spaces.
+///
+/// Unlike `{:>width$}`, this accepts any width (the formatter panics on widths
+/// above `u16::MAX`, which very long input can reach).
+///
+fn pad_left(text: &str, width: usize) -> String {
+    let padding: usize = width.saturating_sub(text.chars().count());
+    return format!("{}{}", " ".repeat(padding), text);
+}
+
+/// Writes a duration as days, hours, minutes and seconds (e.g., "1 d 2 h 3 min 4 s").
+///
+/// The duration is rounded to whole milliseconds, and parts that are zero are
+/// left out. A negative duration is prefixed with a minus sign; one too long to
+/// count in milliseconds is given in days only.
+///
+/// ## Arguments
+///
+/// * `seconds` - The duration in seconds.
+///
+/// ## Returns
+///
+/// The humanized duration as a `String`.
+/// 
+fn humanize_duration(seconds: f64) -> String {
+    if !seconds.is_finite() {
+        return format!("{} s", seconds);
+    }
+    if seconds.abs() * 1000.0 >= u128::MAX as f64 {
+        return format!("{:e} d", seconds / 86_400.0);
+    }
+
+    let millis: u128 = (seconds.abs() * 1000.0).round() as u128;
+    let mut parts: Vec<String> = Vec::new();
+    let days: u128 = millis / 86_400_000;
+    let hours: u128 = millis / 3_600_000 % 24;
+    let minutes: u128 = millis / 60_000 % 60;
+    for (count, symbol) in [(days, "d"), (hours, "h"), (minutes, "min")] {
+        if count > 0 {
+            parts.push(format!("{} {}", count, symbol));
+        }
+    }
+    let rest: u128 = millis % 60_000;
+    if rest > 0 || parts.is_empty() {
+        let fraction: String = format!("{:03}", rest % 1000);
+        match fraction.trim_end_matches('0') {
+            "" => parts.push(format!("{} s", rest / 1000)),
+            fraction => parts.push(format!("{}.{} s", rest / 1000, fraction)),
+        }
+    }
+
+    let sign: &str = if seconds < 0.0 && millis > 0 { "-" } else { "" };
+    return format!("{}{}", sign, parts.join(" "));
+}
+
+/// Picks the number of decimal places for a result from the precision of its source.
+///
+/// The result keeps at least as many decimal places as the source value was
+/// written with, and enough of them to show as many significant digits as the
+/// source has. "1.5" therefore gives 1 decimal place for 34.7 °F, while "1"
+/// meter still shows "0.001" kilometers.
+///
+/// ## Arguments
+///
+/// * `source` - The source value, as written.
+/// * `result` - The converted value.
+///
+/// ## Returns
+///
+/// The number of decimal places to show for `result`.
+/// 
+fn auto_decimals(source: &SourceValue, result: f64) -> usize {
+    let significant: i64 = source.significant_digits() as i64;
+    let magnitude: i64 = match result {
+        0.0 => 0,
+        result => result.abs().log10().floor() as i64,
+    };
+    let for_significant: usize = (significant - 1 - magnitude).max(0) as usize;
+    return source.decimal_places().max(for_significant);
+}
+
+/// Formats a single conversion as a "value unit = value unit" line.
+///
+/// ## Arguments
+///
+/// * `from` - The source `Unit`.
+/// * `to` - The target `Unit`.
+/// * `value` - The value that was converted.
+/// * `conv_value` - The converted value.
+/// * `format` - The `FormatOptions` used to render the values.
+/// * `lang` - The `Language` used for unit names.
+///
+/// ## Returns
+///
+/// The formatted `String`.
+/// 
+fn format_conversion(
+    from: &Unit,
+    to: &Unit,
+    value: f64,
+    conv_value: f64,
+    format: &FormatOptions,
+    lang: &Language,
+) -> String {
+    return format!(
+        "{} {} = {} {}",
+        format_value(value, UnitType::Source, format),
+        unit_name(from, lang),
+        format_value(conv_value, UnitType::Target, format),
+        unit_name(to, lang)
+    );
+}
+
+/// Formats a conversion as the line printed by the 'convert' command.
+///
+/// This is the "value unit = value unit" line of `format_conversion()`, or a
+/// Markdown or LaTeX math snippet with `--format-markdown` or `--format-latex`,
+/// or a `VARNAME=value` assignment with `--shell-export`.
+///
+/// ## Arguments
+///
+/// * `from` - The source `Unit`.
+/// * `to` - The target `Unit`.
+/// * `value` - The value that was converted.
+/// * `conv_value` - The converted value.
+/// * `format` - The `FormatOptions` used to render the values.
+/// * `args` - The `ConvertArgs`, for the output format and language.
+///
+/// ## Returns
+///
+/// The formatted `String`.
+/// 
+fn output_line(
+    from: &Unit,
+    to: &Unit,
+    value: f64,
+    conv_value: f64,
+    format: &FormatOptions,
+    args: &ConvertArgs,
+) -> String {
+    let snippet = |unit: &Unit, escape: fn(&str) -> String| -> String {
+        return escape(&unit_name(unit, &args.lang));
+    };
+    let source: String = format_value(value, UnitType::Source, format);
+    let target: String = format_value(conv_value, UnitType::Target, format);
+    if let Some(name) = &args.shell_export {
+        return format!("{}={}", name, target.trim());
+    }
+    if args.format_markdown {
+        return format!(
+            "${}\\,{} = {}\\,{}$",
+            source.trim(),
+            snippet(from, escape_markdown),
+            target.trim(),
+            snippet(to, escape_markdown)
+        );
+    }
+    if args.format_latex {
+        return format!(
+            "\\({}\\,{} = {}\\,{}\\)",
+            source.trim(),
+            snippet(from, escape_latex),
+            target.trim(),
+            snippet(to, escape_latex)
+        );
+    }
+
+    return format_conversion(from, to, value, conv_value, format, &args.lang);
+}
+
+/// Escapes a unit name for inline math in Markdown (e.g., "°C" is kept, "a_b" becomes `a\_b`).
+///
+/// Characters that Markdown or the math renderer would interpret are escaped
+/// with a backslash, and spaces are kept as explicit TeX spaces.
+///
+fn escape_markdown(name: &str) -> String {
+    let mut escaped: String = String::new();
+    for c in name.chars() {
+        match c {
+            '\\' | '$' | '_' | '*' | '{' | '}' | '#' | '%' | '&' | '^' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            ' ' => escaped.push_str("\\ "),
+            c => escaped.push(c),
+        }
+    }
+
+    return escaped;
+}
+
+/// Escapes a unit name for LaTeX math mode (e.g., "°C" becomes `^{\circ}\mathrm{C}`).
+///
+/// Letters are set upright with `\mathrm`, and the degree sign, superscripts
+/// and the middle dot are replaced by their math-mode commands.
+///
+fn escape_latex(name: &str) -> String {
+    let mut escaped: String = String::new();
+    let mut upright: String = String::new();
+    let flush = |escaped: &mut String, upright: &mut String| {
+        if !upright.is_empty() {
+            escaped.push_str(&format!("\\mathrm{{{}}}", upright));
+            upright.clear();
+        }
+    };
+    for c in name.chars() {
+        let command: Option<&str> = match c {
+            '°' => Some("^{\\circ}"),
+            '²' => Some("^{2}"),
+            '³' => Some("^{3}"),
+            '·' => Some("\\cdot "),
+            _ => None,
+        };
+        match command {
+            Some(command) => {
+                flush(&mut escaped, &mut upright);
+                escaped.push_str(command);
+            }
+            None => match c {
+                '\\' => upright.push_str("\\backslash "),
+                '$' | '_' | '{' | '}' | '#' | '%' | '&' => {
+                    upright.push('\\');
+                    upright.push(c);
+                }
+                '^' => upright.push_str("\\hat{}"),
+                ' ' => upright.push_str("\\ "),
+                c => upright.push(c),
+            },
+        }
+    }
+    flush(&mut escaped, &mut upright);
+
+    return escaped;
+}
+
+/// Interactively asks the user to pick a unit from a numbered list.
+///
+/// The list is printed to standard output, followed by a prompt. The answer is
+/// read as a single line from `input`, which is the terminal's standard input in
+/// normal use.
+///
+/// ## Arguments
+///
+/// * `choices` - The units the user can choose from.
+/// * `input` - The `BufRead` source the answer is read from.
+///
+/// ## Returns
+///
+/// An `anyhow::Result<Unit>` containing the chosen unit on success, or an error
+/// if the answer is not a number in the list.
+/// 
+fn pick_unit(choices: &[Unit], input: &mut impl BufRead) -> Result<Unit> {
+    println!("Select the source unit:");
+    for (i, unit) in choices.iter().enumerate() {
+        println!("{}. [{}] {}", i + 1, unit.dimension(), unit);
+    }
+    print!("Enter a number (1-{}): ", choices.len());
+    io::stdout().flush()?;
 
-    return str_value;
+    let mut answer: String = String::new();
+    input.read_line(&mut answer)?;
+    let index: usize = match answer.trim().parse::<usize>() {
+        Ok(index) if (1..=choices.len()).contains(&index) => index,
+        _ => bail!(format!(
+            "Error: [ERROR] Invalid selection '{}': expected a number from 1 to {}.",
+            answer.trim(),
+            choices.len()
+        )),
+    };
+
+    return Ok(choices[index - 1].clone());
+}
+
+/// Parses the comma-separated target units of `--to`.
+///
+/// The segments that are not recognized are skipped, with a warning on standard
+/// error unless `--quiet-errors` was given.
+///
+/// ## Arguments
+///
+/// * `to` - The target units given with `--to`.
+/// * `parse` - The `ParseOptions` applied to each segment.
+/// * `quiet` - Whether the warnings are suppressed.
+///
+/// ## Returns
+///
+/// An `anyhow::Result<Vec<Unit>>` with the recognized units, or an error if
+/// none of the segments is a valid unit.
+/// 
+fn target_units(to: &str, parse: &ParseOptions, quiet: bool) -> Result<Vec<Unit>> {
+    let (units, warnings): (Vec<Unit>, Vec<String>) =
+        get_enums(String::from(to), UnitType::Target, parse)?;
+    if !quiet {
+        for warning in warnings.iter() {
+            eprintln!("{}", warning);
+        }
+    }
+    return Ok(units);
 }
 
 /// Handles the 'convert' command logic.
 ///
-/// It parses the source and target units, performs the conversion,
-/// prints the result to the console, and adds the result to the history.
+/// It parses the source unit and the (comma-separated) target units, performs
+/// the conversion for each target, prints each result to the console, and adds
+/// each result to the history. When no source unit is given and standard input
+/// is a terminal, the user picks one among the units compatible with the target.
+/// In batch mode, the values are read from standard input (one per line) instead
+/// of `--value`, and each of them is converted in turn. With `--target-value`, the
+/// source value that converts to the given target value is solved for instead.
+/// A `--spec` such as "100c>f" stands in for `--value`, `--from` and `--to`, and
+/// `--to-base` converts to the base unit of the source's dimension instead of `--to`.
 ///
 /// ## Arguments
 ///
-/// * `from` - The string representation of the source unit.
-/// * `to` - The string representation of the target unit.
-/// * `value` - The numerical value to be converted.
+/// * `args` - The `ConvertArgs` given on the command line.
 /// * `history` - A mutable reference to the `History` struct.
-/// * `updated` - A mutable boolean flag to indicate if the history was modified.
 ///
 /// ## Returns
 ///
 /// An `anyhow::Result` indicating success or failure.
 /// 
-fn handle_convert(
-    from: String,
-    to: String,
-    value: f64,
-    history: &mut History,
-    updated: &mut bool,
-) -> Result<()> {
-    let from: Unit = get_enum(from, UnitType::Source)?;
-    let to: Unit = get_enum(to, UnitType::Target)?;
-    let conv_value: f64 = convert(&from, &to, &value)?;
+fn handle_convert(mut args: ConvertArgs, history: &mut History) -> Result<()> {
+    if args.summary && !args.batch {
+        bail!("Error: [ERROR] --summary can only be used together with --batch.");
+    }
+    if args.relativistic {
+        args.physical = Some(PhysicalRelation::Relativistic);
+    }
+    if let Some(path) = &args.from_json {
+        return convert_json(path, &args, history);
+    }
+    if args.seed.is_some() && !args.random {
+        bail!("Error: [ERROR] --seed can only be used together with --random.");
+    }
+    if args.random {
+        let mut rng: Rng = match args.seed {
+            Some(seed) => Rng::new(seed),
+            None => Rng::from_time(),
+        };
+        let (from, to): (Unit, Unit) = random_pair(&mut rng);
+        eprintln!(
+            "Random [{}] conversion: {} → {}",
+            from.dimension(),
+            from.canonical_name(),
+            to.canonical_name()
+        );
+        args.from = Some(from.canonical_name());
+        args.to = Some(to.canonical_name());
+    }
+    if args.compound
+        && let Some(value) = args.value.take()
+    {
+        let text: String = format!("{} {}", value.text, value.unit.unwrap_or_default());
+        let (value, from): (SourceValue, Unit) = parse_compound(&text, &history.recent_units)?;
+        args.value = Some(value);
+        args.from = Some(from.canonical_name());
+    }
+    if let Some(value) = &mut args.value
+        && let Some(unit) = value.unit.take()
+    {
+        if !args.tolerant_parse {
+            bail!(format!(
+                "Error: [ERROR] Invalid value '{} {}': expected a number (add --tolerant-parse to accept a unit after the value).",
+                value.text, unit
+            ));
+        }
+        if args.from.is_some() {
+            bail!(format!(
+                "Error: [ERROR] The value '{} {}' already names its unit; drop either --from or the unit after the value.",
+                value.text, unit
+            ));
+        }
+        args.from = Some(unit);
+    }
+    if args.tolerant_parse {
+        let collapse = |unit: &String| -> String {
+            return unit.split_whitespace().collect::<Vec<&str>>().join(" ");
+        };
+        args.from = args.from.as_ref().map(collapse);
+        args.to = args.to.as_ref().map(collapse);
+    }
+    if let Some(spec) = &args.spec {
+        let (value, from, to): (SourceValue, String, String) = parse_spec(spec)?;
+        args.value = Some(value);
+        args.from = Some(from);
+        args.to = Some(to);
+    }
+    let parse: ParseOptions = ParseOptions {
+        strict_case: args.strict_case,
+        symbols_only: args.symbols_only,
+        recent: &history.recent_units,
+        dimension: None,
+        assumed_dimension: args.assume_dimension,
+    };
+    let (from, targets): (Unit, Option<Vec<Unit>>) = match &args.from {
+        Some(from) => {
+            let from: Unit = get_enum_with(from.clone(), UnitType::Source, &parse)?;
+            let target_parse: ParseOptions = ParseOptions {
+                dimension: Some(from.dimension()),
+                ..parse
+            };
+            let targets: Option<Vec<Unit>> = match &args.to {
+                Some(to) => Some(target_units(to, &target_parse, args.quiet)?),
+                None if let Some(system) = &args.system => {
+                    match system.canonical_unit(&from.dimension()) {
+                        Some(to) => Some(vec![to]),
+                        None => bail!(format!(
+                            "Error: [ERROR] The {} system has no [{}] unit to convert {} to.",
+                            system.as_str(),
+                            from.dimension(),
+                            from.canonical_name()
+                        )),
+                    }
+                }
+                None if args.to_base => {
+                    // A unit whose kind differs from the dimension's base (torque
+                    // among energy units) is its own base.
+                    let base: Unit = base_unit(&from.dimension());
+                    if base.kind() == from.kind() {
+                        Some(vec![base])
+                    } else {
+                        Some(vec![from.clone()])
+                    }
+                }
+                None => None,
+            };
+            (from, targets)
+        }
+        None => {
+            let targets: Vec<Unit> = match &args.to {
+                Some(to) => target_units(to, &parse, args.quiet)?,
+                None => bail!("Error: [ERROR] No source unit provided (use --from)."),
+            };
+            if !io::stdin().is_terminal() {
+                bail!("Error: [ERROR] No source unit provided (use --from).");
+            }
+            let choices: Vec<Unit> = targets[0].compatible_units();
+            (pick_unit(&choices, &mut io::stdin().lock())?, Some(targets))
+        }
+    };
+    if args.shell_export.is_some() && targets.as_ref().is_some_and(|targets| targets.len() > 1) {
+        bail!("Error: [ERROR] --shell-export needs a single target unit.");
+    }
+    if let Some(dimension) = &args.dimension {
+        let units: Vec<&Unit> = std::iter::once(&from)
+            .chain(targets.iter().flatten())
+            .collect();
+        if let Some(unit) = units.iter().find(|unit| unit.dimension() != *dimension) {
+            bail!(format!(
+                "Error: [ERROR] Expected only [{}] units (--dimension), but {} is a [{}] unit.",
+                dimension,
+                unit.canonical_name(),
+                unit.dimension()
+            ));
+        }
+    }
+    if args.compare_to_baseline {
+        let targets: &[Unit] = targets.as_deref().unwrap_or(&[]);
+        if targets.len() < 2 {
+            bail!("Error: [ERROR] --compare-to-baseline needs at least two target units.");
+        }
+        if targets
+            .iter()
+            .any(|to| to.dimension() != targets[0].dimension())
+        {
+            bail!("Error: [ERROR] --compare-to-baseline needs target units of the same dimension.");
+        }
+    }
+    if args.echo_command {
+        eprintln!("{}", canonical_command(&from, targets.as_deref(), &args));
+    }
+    let format: FormatOptions = FormatOptions {
+        pad: args.pad,
+        source_decimals: args.source_decimals,
+        decimals: args
+            .decimals
+            .unwrap_or(FormatOptions::default().decimals)
+            .min(args.max_precision.unwrap_or(MAX_DECIMALS)),
+        rounding: args.round,
+    };
 
-    let str_result: String = format!(
-        "{} {} = {} {}",
-        format_value(value, UnitType::Source),
-        from.symbol(),
-        format_value(conv_value, UnitType::Target),
-        to.symbol()
-    )
-    .to_string();
+    // Results are collected first so that their "=" signs can be aligned; the
+    // ones made before an error are still printed.
+    let mut output: Vec<String> = Vec::new();
+    let mut printed: Vec<String> = Vec::new();
+    let result: Result<()> = run_conversions(
+        &from,
+        targets.as_deref(),
+        &args,
+        &format,
+        history,
+        &mut output,
+        &mut printed,
+    );
+    print_output(&mut output, &args, &mut printed);
+    if args.clipboard && !printed.is_empty() {
+        clipboard::copy(&printed.join("\n"));
+    }
 
-    println!("{}", &str_result);
-    (*history).add(str_result);
-    *updated = true;
+    return result;
+}
 
-    return Ok(());
+/// Prints the collected result lines.
+///
+/// The lines are aligned on their "=" sign and prefixed with a non-empty
+/// `--label`. In batch mode this is done after each input value, so that its
+/// results are printed as soon as they are produced.
+///
+/// ## Arguments
+///
+/// * `output` - The lines to print, which are moved to `printed`.
+/// * `args` - The `ConvertArgs`, for `--label` and the snippet formats.
+/// * `printed` - The lines printed so far, for `--clipboard`.
+/// 
+fn print_output(output: &mut Vec<String>, args: &ConvertArgs, printed: &mut Vec<String>) {
+    let prefix: String = match args.label.as_deref() {
+        Some(label) if !label.is_empty() => format!("{}: ", label),
+        _ => String::new(),
+    };
+    // Snippets are meant to be pasted elsewhere, so they are not padded.
+    let lines: Vec<String> = match args.format_markdown || args.format_latex {
+        true => std::mem::take(output),
+        false => align_on_equals(&std::mem::take(output)),
+    };
+    for line in lines.into_iter() {
+        let line: String = format!("{}{}", prefix, line);
+        println!("{}", line);
+        printed.push(line);
+    }
+}
+
+/// Converts a JSON array of conversion requests for `--from-json`.
+///
+/// The results are printed as a JSON array, with unrounded values; formatting
+/// options do not apply, and the conversions are not added to the history.
+///
+/// ## Arguments
+///
+/// * `path` - The file holding the requests, or `-` for standard input.
+/// * `args` - The `ConvertArgs` given on the command line, for the unit parsing options.
+/// * `history` - The `History`, whose recent units guide suggestions for unknown units.
+///
+/// ## Returns
+///
+/// An `anyhow::Result` which is an error if the requests cannot be read, but
+/// not if single requests fail.
+/// 
+#[cfg(feature = "json")]
+fn convert_json(path: &std::path::Path, args: &ConvertArgs, history: &History) -> Result<()> {
+    let options: ParseOptions = ParseOptions {
+        strict_case: args.strict_case,
+        symbols_only: args.symbols_only,
+        recent: &history.recent_units,
+        dimension: None,
+        assumed_dimension: args.assume_dimension,
+    };
+    println!("{}", bulk::run(path, &options)?);
+    return Ok(());
+}
+
+/// Reports that `--from-json` is not available without the `json` feature.
+#[cfg(not(feature = "json"))]
+fn convert_json(_path: &std::path::Path, _args: &ConvertArgs, _history: &History) -> Result<()> {
+    bail!(
+        "Error: [ERROR] --from-json is not available in this build (it needs the 'json' feature)."
+    );
+}
+
+/// Reconstructs the canonical `unitconv convert` command for a conversion.
+///
+/// Units are spelled out by their canonical names and the value is given with
+/// `--value` (or `--target-value`, `--batch`), however they were written on the
+/// command line (e.g., in a `--spec`, with symbols, or picked interactively).
+/// Options that change the printed result are kept; the others are left out.
+///
+/// ## Arguments
+///
+/// * `from` - The resolved source `Unit`.
+/// * `targets` - The resolved target units, or `None` with `--best-unit`.
+/// * `args` - The `ConvertArgs` given on the command line.
+///
+/// ## Returns
+///
+/// The command as a `String`, with arguments quoted for a POSIX shell.
+/// 
+fn canonical_command(from: &Unit, targets: Option<&[Unit]>, args: &ConvertArgs) -> String {
+    let mut words: Vec<String> = vec![
+        String::from("unitconv convert --from"),
+        shell_quote(&from.canonical_name()),
+    ];
+    match targets {
+        Some(targets) => {
+            let names: Vec<String> = targets.iter().map(|to| to.canonical_name()).collect();
+            words.push(format!("--to {}", shell_quote(&names.join(","))));
+        }
+        None => match args.human {
+            Some(scale) => words.push(format!("--human {}", value_name(&scale))),
+            None => words.push(String::from("--best-unit")),
+        },
+    }
+    // A negative value is joined with "=", since "--value -40" reads as an option.
+    let value_arg = |name: &str, value: &str| -> String {
+        if value.starts_with('-') {
+            return format!("--{}={}", name, value);
+        }
+        return format!("--{} {}", name, value);
+    };
+    if let Some(target) = args.target_value {
+        words.push(value_arg("target-value", &target.to_string()));
+    } else if let Some(value) = &args.value {
+        words.push(value_arg("value", &value.text));
+    } else if let (Some(start), Some(end), Some(step)) = (args.from_value, args.to_value, args.step)
+    {
+        words.push(value_arg("from-value", &start.to_string()));
+        words.push(value_arg("to-value", &end.to_string()));
+        words.push(format!("--step {}", step));
+    } else if args.batch {
+        words.push(String::from("--batch"));
+    }
+
+    if let Some(physical) = &args.physical {
+        words.push(format!("--physical {}", value_name(physical)));
+    }
+    if let Some(precision) = &args.precision {
+        words.push(format!("--precision {}", value_name(precision)));
+    } else if let Some(decimals) = args.decimals {
+        words.push(format!("--decimals {}", decimals));
+    }
+    if let Some(max_precision) = args.max_precision {
+        words.push(format!("--max-precision {}", max_precision));
+    }
+    if !matches!(args.round, RoundingMode::Standard) {
+        words.push(format!("--round {}", value_name(&args.round)));
+    }
+    if args.exact {
+        words.push(String::from("--exact"));
+    }
+    if let Some(increment) = args.round_to {
+        words.push(format!("--round-to {}", increment));
+    }
+    if args.humanize {
+        words.push(String::from("--humanize"));
+    }
+    if let Ok(Some((per, per_to))) = gradient_lengths(args) {
+        words.push(format!("--per {}", shell_quote(&per.canonical_name())));
+        if per_to != per {
+            words.push(format!(
+                "--per-to {}",
+                shell_quote(&per_to.canonical_name())
+            ));
+        }
+    }
+
+    return words.join(" ");
+}
+
+/// Gets the command-line spelling of a `ValueEnum` value (e.g., "banker").
+fn value_name<T: ValueEnum>(value: &T) -> String {
+    match value.to_possible_value() {
+        Some(possible) => return String::from(possible.get_name()),
+        None => return String::new(),
+    }
+}
+
+/// Quotes a word for a POSIX shell if it contains anything but safe characters.
+fn shell_quote(word: &str) -> String {
+    let is_safe: bool = !word.is_empty()
+        && word
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ',' | '/'));
+    if is_safe {
+        return String::from(word);
+    }
+    return format!("'{}'", word.replace('\'', "'\\''"));
+}
+
+/// Splits a compact conversion spec into its value, source unit and target unit.
+///
+/// The spec has the form "VALUE FROM > TO" or "VALUE FROM to TO", where spaces
+/// are optional around ">" and between the value and the source unit (e.g.,
+/// "100c>f" or "5 km to miles"). The units are returned as written; they are
+/// parsed like `--from` and `--to`.
+///
+/// ## Arguments
+///
+/// * `spec` - The spec given with `--spec`.
+///
+/// ## Returns
+///
+/// A `Result<(SourceValue, String, String)>` with the value, source unit and target unit,
+/// or an error with an example of a valid spec.
+/// 
+fn parse_spec(spec: &str) -> Result<(SourceValue, String, String)> {
+    let invalid = || -> anyhow::Error {
+        return anyhow::anyhow!(format!(
+            "Error: [ERROR] Invalid spec '{}'. Expected 'VALUE FROM > TO' (e.g., '100c>f' or '5 km to miles').",
+            spec
+        ));
+    };
+
+    let lower: String = spec.to_ascii_lowercase();
+    let (source, to): (&str, &str) = match spec.split_once('>') {
+        Some(parts) => parts,
+        None => match lower.find(" to ") {
+            Some(at) => (&spec[..at], &spec[at + 4..]),
+            None => return Err(invalid()),
+        },
+    };
+    let (value, from): (SourceValue, &str) = split_leading_number(source).ok_or_else(invalid)?;
+    let to: &str = to.trim();
+    if from.is_empty() || to.is_empty() || to.contains('>') {
+        return Err(invalid());
+    }
+
+    return Ok((value, String::from(from), String::from(to)));
+}
+
+/// Parses a compound quantity such as "5 ft 11 in" or "10 lb, 4 oz".
+///
+/// Each part is a number followed by a unit, and every unit must have the same
+/// dimension. The parts are summed in the unit of the first one, so that the
+/// conversion is shown from that unit (e.g., "5.916667 ft").
+///
+/// ## Arguments
+///
+/// * `text` - The compound value given with `--value`.
+/// * `recent` - The recently used unit names, used to resolve ambiguous symbols.
+///
+/// ## Returns
+///
+/// A `Result<(SourceValue, Unit)>` with the sum and the unit of the first part,
+/// or an error if a part has no number, no unit, or a unit of another dimension.
+/// 
+fn parse_compound(text: &str, recent: &[String]) -> Result<(SourceValue, Unit)> {
+    let invalid = || -> anyhow::Error {
+        return anyhow::anyhow!(format!(
+            "Error: [ERROR] Invalid compound value '{}'. Expected numbers with units (e.g., '5 ft 11 in' or '10 lb 4 oz').",
+            text.trim()
+        ));
+    };
+
+    let mut parse: ParseOptions = ParseOptions {
+        strict_case: false,
+        symbols_only: false,
+        recent,
+        dimension: None,
+        assumed_dimension: None,
+    };
+    let mut total: Option<(f64, Unit)> = None;
+    let mut rest: &str = text;
+    while !rest.trim().is_empty() {
+        let (value, after): (SourceValue, &str) = split_leading_number(rest).ok_or_else(invalid)?;
+        let end: usize = after
+            .char_indices()
+            .find(|(at, c)| {
+                return (c.is_ascii_digit() || *c == '.')
+                    && after[..*at].ends_with(|c: char| c.is_whitespace() || c == ',');
+            })
+            .map(|(at, _)| at)
+            .unwrap_or(after.len());
+        let unit: &str = after[..end].trim().trim_end_matches(',').trim_end();
+        if unit.is_empty() {
+            return Err(invalid());
+        }
+        rest = &after[end..];
+
+        let unit: Unit = get_enum_with(String::from(unit), UnitType::Source, &parse)?;
+        total = match total {
+            None => {
+                parse.dimension = Some(unit.dimension());
+                Some((value.value, unit))
+            }
+            Some((sum, first)) => Some((sum + convert(&unit, &first, &value.value)?, first)),
+        };
+    }
+
+    let (sum, unit): (f64, Unit) = total.ok_or_else(invalid)?;
+    let value: SourceValue = SourceValue {
+        value: sum,
+        text: sum.to_string(),
+        unit: None,
+    };
+    return Ok((value, unit));
+}
+
+/// Runs the conversions requested by the 'convert' command.
+///
+/// Depending on the arguments, this converts `--value`, every value read from
+/// standard input (`--batch`), a range of values (`--from-value`), or solves for
+/// the source of `--target-value`.
+///
+/// ## Arguments
+///
+/// * `from` - The source `Unit`.
+/// * `targets` - The target units, or `None` to pick the best unit for each value.
+/// * `args` - The `ConvertArgs` given on the command line.
+/// * `format` - The `FormatOptions` used for the printed values.
+/// * `history` - A mutable reference to the `History` struct.
+/// * `output` - The lines to print, to which the results are appended.
+/// * `printed` - The lines printed so far; in batch mode, the results of each
+///   value are printed right away and moved here.
+///
+/// ## Returns
+///
+/// An `anyhow::Result` indicating success or failure.
+/// 
+fn run_conversions(
+    from: &Unit,
+    targets: Option<&[Unit]>,
+    args: &ConvertArgs,
+    format: &FormatOptions,
+    history: &mut History,
+    output: &mut Vec<String>,
+    printed: &mut Vec<String>,
+) -> Result<()> {
+    if let Some(target) = args.target_value {
+        for to in targets.unwrap_or(&[]) {
+            let value: f64 = solve_source(from, to, &target)?;
+            let record_format: FormatOptions = FormatOptions { pad: 0, ..*format };
+            let str_result: String =
+                format_conversion(from, to, value, target, &record_format, &Language::En);
+            let line: String = output_line(from, to, value, target, format, args);
+            emit_result(from, to, value, target, &line, args, output)?;
+            (*history).add(str_result, from, to, value, target, args.note.clone());
+        }
+        return Ok(());
+    }
+    if let (Some(start), Some(end), Some(step)) = (args.from_value, args.to_value, args.step) {
+        let Some(targets) = targets else {
+            bail!("Error: [ERROR] A --from-value table needs target units (use --to).");
+        };
+        return step_table(from, targets, (start, end, step), args, format, output);
+    }
+    if !args.batch {
+        let Some(value) = &args.value else {
+            bail!("Error: [ERROR] No value provided (use --value or --batch).");
+        };
+        convert_value(from, targets, value, args, format, history, output)?;
+        return Ok(());
+    }
+
+    let mut outputs: Vec<f64> = Vec::new();
+    for (i, line) in io::stdin().lock().lines().enumerate() {
+        let line: String = line?;
+        let text: &str = line.trim();
+        if text.is_empty() {
+            continue;
+        }
+        let value: SourceValue = match parse_source_value(text) {
+            Ok(value) => value,
+            Err(_) => {
+                if !args.quiet {
+                    eprintln!(
+                        "Error: [ERROR] Line {}: '{}' is not a number, skipped.",
+                        i + 1,
+                        text
+                    );
+                }
+                continue;
+            }
+        };
+
+        let results: Vec<(Unit, f64)> =
+            convert_value(from, targets, &value, args, format, history, output)?;
+        outputs.extend(results.iter().map(|(_, conv_value)| *conv_value));
+        print_output(output, args, printed);
+    }
+
+    if args.summary {
+        print_summary(&outputs, format);
+    }
+
+    return Ok(());
+}
+
+/// The largest number of rows a `--from-value` table may have.
+const MAX_TABLE_ROWS: usize = 10_000;
+
+/// Converts a range of source values and renders them as a table.
+///
+/// The table has a column for the source unit and one for each target unit,
+/// headed by the unit names, with right-aligned values. Row `i` holds the value
+/// `start + i * step`, up to and including `end`. The rows of the table are
+/// not added to the history.
+///
+/// ## Arguments
+///
+/// * `from` - The source `Unit`.
+/// * `targets` - The target units, one column each.
+/// * `range` - The start, end, and step of the source values.
+/// * `args` - The `ConvertArgs` given on the command line.
+/// * `format` - The `FormatOptions` used for the printed values.
+/// * `output` - The lines to print, to which the table is appended.
+///
+/// ## Returns
+///
+/// An `anyhow::Result` which is an error if the range is invalid or a
+/// conversion fails.
+///
+fn step_table(
+    from: &Unit,
+    targets: &[Unit],
+    range: (f64, f64, f64),
+    args: &ConvertArgs,
+    format: &FormatOptions,
+    output: &mut Vec<String>,
+) -> Result<()> {
+    let (start, end, step): (f64, f64, f64) = range;
+    if !start.is_finite() || !end.is_finite() || start > end {
+        bail!(format!(
+            "Error: [ERROR] --from-value ({}) must be a number no greater than --to-value ({}).",
+            start, end
+        ));
+    }
+    if !step.is_finite() || step <= 0.0 {
+        bail!(format!(
+            "Error: [ERROR] --step must be a positive number, got {}.",
+            step
+        ));
+    }
+    // A tiny relative slack keeps `end` itself when the division rounds just below it.
+    let steps: f64 = ((end - start) / step * (1.0 + 1e-12)).floor();
+    if steps >= MAX_TABLE_ROWS as f64 {
+        bail!(format!(
+            "Error: [ERROR] The table would have more than {} rows; use a larger --step.",
+            MAX_TABLE_ROWS
+        ));
+    }
+
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    let mut header: Vec<String> = vec![unit_name(from, &args.lang)];
+    header.extend(targets.iter().map(|to| unit_name(to, &args.lang)));
+    rows.push(header);
+    for i in 0..=(steps as usize) {
+        let value: f64 = start + i as f64 * step;
+        let mut row: Vec<String> = vec![format_value(value, UnitType::Source, format)];
+        for to in targets.iter() {
+            let conv_value: f64 = match args.physical {
+                Some(PhysicalRelation::Wavelength) => convert_light(from, to, &value)?,
+                Some(PhysicalRelation::Relativistic) => convert_mass_energy(from, to, &value)?,
+                None => convert(from, to, &value)?,
+            };
+            let conv_value: f64 = round_to_increment(conv_value, args.round_to);
+            row.push(format_value(conv_value, UnitType::Target, format));
+        }
+        rows.push(row);
+    }
+
+    let widths: Vec<usize> = (0..rows[0].len())
+        .map(|column| {
+            return rows
+                .iter()
+                .map(|row| row[column].trim().chars().count())
+                .max()
+                .unwrap_or(0);
+        })
+        .collect();
+    for row in rows.iter() {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(widths.iter())
+            .map(|(cell, width)| pad_left(cell.trim(), *width))
+            .collect();
+        output.push(cells.join("  "));
+    }
+
+    return Ok(());
+}
+
+/// Outputs a single conversion result.
+///
+/// Normally the formatted line is appended to `output`, to be aligned and printed
+/// once all conversions are done. In NDJSON mode, the result is instead printed
+/// right away as a one-line JSON object and flushed, so that consumers reading the
+/// stream see each result as soon as it is produced. A non-empty `--label` is
+/// included in the object as `"label"`.
+///
+/// ## Arguments
+///
+/// * `from` - The source `Unit`.
+/// * `to` - The target `Unit`.
+/// * `input` - The value that was converted.
+/// * `result` - The converted value.
+/// * `line` - The formatted conversion line.
+/// * `args` - The `ConvertArgs`, for `--ndjson` and `--label`.
+/// * `output` - The lines to print, to which the formatted line is appended.
+///
+/// ## Returns
+///
+/// An `anyhow::Result` which is an error if writing to the standard output fails.
+/// 
+fn emit_result(
+    from: &Unit,
+    to: &Unit,
+    input: f64,
+    result: f64,
+    line: &str,
+    args: &ConvertArgs,
+    output: &mut Vec<String>,
+) -> Result<()> {
+    if !args.ndjson {
+        output.push(line.to_string());
+        return Ok(());
+    }
+
+    // The object is written by hand so that NDJSON output does not depend on
+    // serde, which is only compiled in with the `history` feature.
+    let mut fields: Vec<String> = vec![
+        format!("\"from\":{}", json_string(&from.to_string())),
+        format!("\"input\":{}", json_number(input)),
+    ];
+    if let Some(label) = args.label.as_deref()
+        && !label.is_empty()
+    {
+        fields.push(format!("\"label\":{}", json_string(label)));
+    }
+    fields.push(format!("\"output\":{}", json_number(result)));
+    fields.push(format!("\"to\":{}", json_string(&to.to_string())));
+    let mut stdout: io::StdoutLock = io::stdout().lock();
+    writeln!(stdout, "{{{}}}", fields.join(","))?;
+    stdout.flush()?;
+    return Ok(());
+}
+
+/// Encodes a string as a JSON string literal, quotes included.
+fn json_string(text: &str) -> String {
+    let mut encoded: String = String::from('"');
+    for c in text.chars() {
+        match c {
+            '"' => encoded.push_str("\\\""),
+            '\\' => encoded.push_str("\\\\"),
+            '\n' => encoded.push_str("\\n"),
+            '\r' => encoded.push_str("\\r"),
+            '\t' => encoded.push_str("\\t"),
+            c if (c as u32) < 0x20 => encoded.push_str(&format!("\\u{:04x}", c as u32)),
+            c => encoded.push(c),
+        }
+    }
+    encoded.push('"');
+
+    return encoded;
+}
+
+/// Encodes a number as JSON; JSON has no NaN or infinity, so those become `null`.
+fn json_number(value: f64) -> String {
+    if !value.is_finite() {
+        return String::from("null");
+    }
+    return format!("{:?}", value);
+}
+
+/// Aligns conversion lines on their "=" sign.
+///
+/// The part left of " = " is right-aligned to the widest one among the lines, so
+/// that the results of a multi-target conversion form one column. Lines
+/// without an "=" (e.g., reciprocals) are kept as they are, and a single line is
+/// never padded.
+///
+/// ## Arguments
+///
+/// * `lines` - The output lines.
+///
+/// ## Returns
+///
+/// A `Vec<String>` with the aligned lines.
+///
+fn align_on_equals(lines: &[String]) -> Vec<String> {
+    let width: usize = lines
+        .iter()
+        .filter_map(|line| line.split_once(" = "))
+        .map(|(left, _)| left.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    return lines
+        .iter()
+        .map(|line| match line.split_once(" = ") {
+            Some((left, right)) => format!("{} = {}", pad_left(left, width), right),
+            None => line.clone(),
+        })
+        .collect();
+}
+
+/// Converts a single value to every target unit, recording each result.
+///
+/// The formatted results (and any reciprocal or baseline lines) are appended to
+/// `output` rather than printed directly.
+///
+/// ## Arguments
+///
+/// * `from` - The source `Unit`.
+/// * `targets` - The target units, or `None` to pick the best unit for the value.
+/// * `input` - The value to convert, as written.
+/// * `args` - The `ConvertArgs` given on the command line.
+/// * `format` - The `FormatOptions` used for the printed values. With
+///   `--precision auto`, its decimals are replaced by `auto_decimals()`, capped
+///   at `--max-precision`.
+/// * `history` - A mutable reference to the `History` struct.
+/// * `output` - The lines to print, to which the results are appended.
+///
+/// ## Returns
+///
+/// An `anyhow::Result<Vec<(Unit, f64)>>` with each target unit and its converted value.
+/// 
+fn convert_value(
+    from: &Unit,
+    targets: Option<&[Unit]>,
+    input: &SourceValue,
+    args: &ConvertArgs,
+    format: &FormatOptions,
+    history: &mut History,
+    output: &mut Vec<String>,
+) -> Result<Vec<(Unit, f64)>> {
+    let value: f64 = input.value;
+    let targets: Vec<Unit> = match targets {
+        Some(targets) => targets.to_vec(),
+        None => match args.human {
+            Some(scale) => vec![human_data_unit(
+                from,
+                &value,
+                matches!(scale, SizeScale::Binary),
+            )?],
+            None => vec![best_unit(from, &value)?],
+        },
+    };
+    let per: Option<(Unit, Unit)> = gradient_lengths(args)?;
+
+    let mut results: Vec<(Unit, f64)> = Vec::new();
+    for to in targets.into_iter() {
+        if args.physical.is_none() && from.dimension() != to.dimension() {
+            bail!(mismatch_message(from, &to, &history.recent_units));
+        }
+        let conv_value: f64 = match (&args.physical, &per) {
+            (Some(PhysicalRelation::Wavelength), _) => convert_light(from, &to, &value)?,
+            (Some(PhysicalRelation::Relativistic), _) => convert_mass_energy(from, &to, &value)?,
+            (None, Some((per, per_to))) => convert_gradient(from, &to, (per, per_to), &value)?,
+            (None, None) => convert(from, &to, &value)?,
+        };
+        let conv_value: f64 = round_to_increment(conv_value, args.round_to);
+        if !conv_value.is_finite() {
+            bail!(format!(
+                "Error: [ERROR] Converting {:e} {} to {} overflows to infinity. Use a larger target unit (e.g., --best-unit) or a smaller value.",
+                value, from, to
+            ));
+        }
+        if conv_value.abs() > MAX_DISPLAY_MAGNITUDE && !args.quiet {
+            eprintln!(
+                "Warning: [WARNING] The result {:.4e} {} has more digits than are significant; only about 15 of them are exact. Read it in scientific notation, or use a larger target unit (e.g., --best-unit).",
+                conv_value, to
+            );
+        }
+
+        let format: FormatOptions = match args.precision {
+            Some(Precision::Auto) => FormatOptions {
+                decimals: auto_decimals(input, conv_value)
+                    .min(args.max_precision.unwrap_or(MAX_DECIMALS)),
+                ..*format
+            },
+            None => *format,
+        };
+        let exact: Option<String> = if args.exact {
+            convert_exact(from, &to, &input.text)
+        } else {
+            None
+        };
+        let humanized: Option<String> = if args.humanize && to.dimension() == UnitDimension::Time {
+            Some(humanize_duration(convert(&to, &Unit::Second, &conv_value)?))
+        } else {
+            None
+        };
+        // The line is built once in the output language and format, and once in
+        // English without padding for the history and the system log.
+        let plain = |format: &FormatOptions, lang: &Language| -> Option<String> {
+            return match (&exact, &humanized, &per) {
+                (Some(exact), _, _) => Some(format!(
+                    "{} {} = {} {}",
+                    input.text.trim(),
+                    unit_name(from, lang),
+                    exact,
+                    unit_name(&to, lang)
+                )),
+                (None, Some(humanized), _) => Some(format!(
+                    "{} {} = {}",
+                    input.text.trim(),
+                    unit_name(from, lang),
+                    humanized
+                )),
+                (None, None, Some((per, per_to))) => Some(format!(
+                    "{} {}/{} = {} {}/{}",
+                    format_value(value, UnitType::Source, format),
+                    unit_name(from, lang),
+                    unit_name(per, lang),
+                    format_value(conv_value, UnitType::Target, format),
+                    unit_name(&to, lang),
+                    unit_name(per_to, lang)
+                )),
+                (None, None, None) => None,
+            };
+        };
+        let record_format: FormatOptions = FormatOptions { pad: 0, ..format };
+        let str_result: String = match plain(&record_format, &Language::En) {
+            Some(plain) => plain,
+            None => format_conversion(from, &to, value, conv_value, &record_format, &Language::En),
+        };
+
+        let line: String = match plain(&format, &args.lang) {
+            Some(plain) => plain,
+            None => output_line(from, &to, value, conv_value, &format, args),
+        };
+        emit_result(from, &to, value, conv_value, &line, args, output)?;
+        if args.show_both_systems
+            && let Some(other) = to.counterpart_unit()
+        {
+            let other_value: f64 = convert(&to, &other, &conv_value)?;
+            let line: String = output_line(from, &other, value, other_value, &format, args);
+            emit_result(from, &other, value, other_value, &line, args, output)?;
+        }
+        if args.with_reciprocal {
+            if conv_value == 0.0 {
+                output.push(String::from("reciprocal: undefined (result is zero)"));
+            } else {
+                let reciprocal: f64 = 1.0 / conv_value;
+                output.push(format!(
+                    "reciprocal: {}",
+                    format_value(reciprocal, UnitType::Target, &format)
+                ));
+            }
+        }
+        if args.log_syslog {
+            syslog::info(&str_result);
+        }
+        (*history).add(str_result, from, &to, value, conv_value, args.note.clone());
+        results.push((to, conv_value));
+    }
+
+    if args.compare_to_baseline {
+        output.extend(baseline_comparison(&results));
+    }
+
+    return Ok(results);
+}
+
+/// Parses the length units of a `--per` gradient.
+///
+/// ## Arguments
+///
+/// * `args` - The `ConvertArgs` given on the command line.
+///
+/// ## Returns
+///
+/// A `Result<Option<(Unit, Unit)>>` with the source length (`--per`) and the
+/// target length (`--per-to`, or the source length if it is not given), or
+/// `None` if the value is not a gradient.
+///
+fn gradient_lengths(args: &ConvertArgs) -> Result<Option<(Unit, Unit)>> {
+    let Some(per) = &args.per else {
+        return Ok(None);
+    };
+    let per: Unit = get_enum(per.clone(), UnitType::Source)?;
+    let per_to: Unit = match &args.per_to {
+        Some(per_to) => get_enum(per_to.clone(), UnitType::Target)?,
+        None => per.clone(),
+    };
+    return Ok(Some((per, per_to)));
+}
+
+/// Prints the count, minimum, maximum, and mean of the converted values to stderr.
+///
+/// With several target units, the values of all targets are summarized together.
+///
+/// ## Arguments
+///
+/// * `outputs` - The converted values.
+/// * `format` - The `FormatOptions` used for the printed statistics.
+/// 
+fn print_summary(outputs: &[f64], format: &FormatOptions) {
+    if outputs.is_empty() {
+        eprintln!("Summary: count 0 (no values converted)");
+        return;
+    }
+
+    let min: f64 = outputs.iter().copied().fold(f64::INFINITY, f64::min);
+    let max: f64 = outputs.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let mean: f64 = outputs.iter().sum::<f64>() / outputs.len() as f64;
+    let show = |value: f64| -> String {
+        return format_value(value, UnitType::Target, format)
+            .trim_start()
+            .to_string();
+    };
+    eprintln!(
+        "Summary: count {}, min {}, max {}, mean {}",
+        outputs.len(),
+        show(min),
+        show(max),
+        show(mean)
+    );
+}
+
+/// Describes the relative difference of each result against the first one.
+///
+/// The first result is the baseline. Every other result is reported as a signed
+/// percentage of the baseline's numeric value, which is useful for comparing two
+/// variants of the same unit (e.g., an old and a corrected factor).
+///
+/// ## Arguments
+///
+/// * `results` - The target units and their converted values, baseline first.
+///
+/// ## Returns
+///
+/// A `Vec<String>` with one comparison line per non-baseline result.
+/// 
+fn baseline_comparison(results: &[(Unit, f64)]) -> Vec<String> {
+    let (baseline_unit, baseline): (&Unit, f64) = (&results[0].0, results[0].1);
+    let mut lines: Vec<String> = Vec::new();
+    for (unit, result) in results.iter().skip(1) {
+        if baseline == 0.0 {
+            lines.push(format!(
+                "{} vs {}: n/a (baseline is zero)",
+                unit, baseline_unit
+            ));
+            continue;
+        }
+        let difference: f64 = (result - baseline) / baseline.abs() * 100.0;
+        lines.push(format!(
+            "{} vs {}: {:+.4}%",
+            unit, baseline_unit, difference
+        ));
+    }
+
+    return lines;
+}
+
+/// Handles the 'history' command logic.
+///
+/// It prints the conversion history, either as a flat list (optionally one page
+/// of it), grouped by a key, filtered to the records whose value lies in a range,
+/// or followed live as new records are appended to the history file. With a
+/// `HistoryAction`, it exports, normalizes or clears the history instead.
+///
+/// ## Arguments
+///
+/// * `args` - The `HistoryArgs` given on the command line.
+/// * `history` - A mutable reference to the loaded `History`.
+/// * `config` - The loaded `Config`, whose timezone and page size are used unless
+///   `--timezone` or `--page-size` is given.
+///
+/// ## Returns
+///
+/// An `anyhow::Result` indicating success or failure.
+/// 
+#[cfg(feature = "history")]
+fn handle_history(args: HistoryArgs, history: &mut History, config: &Config) -> Result<()> {
+    let timezone: TimeZone = match args.timezone.as_ref().or(config.timezone.as_ref()) {
+        Some(name) => TimeZone::parse(name)?,
+        None => TimeZone::local(),
+    };
+    let max_width: Option<usize> = match args.max_width {
+        Some(0) => None,
+        Some(width) => Some(width),
+        None => terminal_width(),
+    };
+    let view: HistoryView = HistoryView {
+        timezone,
+        max_width,
+    };
+
+    match &args.action {
+        Some(HistoryAction::Export { format, file }) => return history.export(format, file),
+        Some(HistoryAction::Normalize) => {
+            let (changed, skipped): (usize, usize) =
+                history.normalize(|unit| return unit_name(unit, &Language::En))?;
+            history.save()?;
+            println!(
+                "Normalized {} record(s); skipped {} record(s) without recognized units.",
+                changed, skipped
+            );
+            return Ok(());
+        }
+        Some(HistoryAction::Clear) => {
+            let removed: usize = history.clear();
+            history.save()?;
+            println!("Cleared {} record(s) from the history.", removed);
+            return Ok(());
+        }
+        Some(HistoryAction::Dedupe { keep }) => {
+            let removed: usize = history.dedupe(matches!(keep, KeepOccurrence::Last));
+            history.save()?;
+            println!("Removed {} duplicate record(s) from the history.", removed);
+            return Ok(());
+        }
+        Some(HistoryAction::Pin { number }) | Some(HistoryAction::Unpin { number }) => {
+            let pinned: bool = matches!(args.action, Some(HistoryAction::Pin { .. }));
+            let record: String = history.set_pinned(*number, pinned)?.to_string();
+            history.save()?;
+            match pinned {
+                true => println!("Pinned record {}: {}", number, record),
+                false => println!("Unpinned record {}: {}", number, record),
+            }
+            return Ok(());
+        }
+        None => {}
+    }
+    if args.follow {
+        return history.follow(&view);
+    }
+    if let Some(range) = &args.value_range {
+        return history.print_in_range(range, &args.match_field, &view);
+    }
+    if let Some(page) = args.page {
+        let page_size: usize = args
+            .page_size
+            .or(config.page_size)
+            .unwrap_or(Config::DEFAULT_PAGE_SIZE);
+        return history.print_page(page, page_size, &view);
+    }
+
+    match args.group_by {
+        Some(HistoryGrouping::Dimension) => history.print_by_dimension(&view)?,
+        None => history.print(&view)?,
+    }
+
+    return Ok(());
+}
+
+/// Detects the width of the terminal that standard output is printed to.
+///
+/// The `COLUMNS` environment variable is used if set; otherwise the size is
+/// queried from `stty`.
+///
+/// ## Returns
+///
+/// An `Option<usize>` with the number of columns, or `None` if standard output
+/// is not a terminal or the width cannot be determined.
+///
+#[cfg(feature = "history")]
+fn terminal_width() -> Option<usize> {
+    if !io::stdout().is_terminal() {
+        return None;
+    }
+    if let Some(columns) = std::env::var("COLUMNS")
+        .ok()
+        .and_then(|c| c.trim().parse().ok())
+    {
+        return Some(columns);
+    }
+
+    let tty: std::fs::File = std::fs::File::open("/dev/tty").ok()?;
+    let output: std::process::Output = std::process::Command::new("stty")
+        .arg("size")
+        .stdin(tty)
+        .output()
+        .ok()?;
+    // `stty size` prints "<rows> <columns>".
+    let size: String = String::from_utf8(output.stdout).ok()?;
+    let columns: usize = size.split_whitespace().nth(1)?.parse().ok()?;
+    match columns {
+        0 => return None,
+        columns => return Some(columns),
+    }
+}
+
+/// Handles the 'check' command logic.
+///
+/// It parses both units and checks that they can be converted into each other
+/// (see `validate()`). Nothing is printed unless `--verbose` is given; the result
+/// is reported through the exit code.
+///
+/// ## Arguments
+///
+/// * `args` - The `CheckArgs` given on the command line.
+///
+/// ## Returns
+///
+/// An `anyhow::Result` which is `Ok(())` if the units are compatible, or an error
+/// (a `SilentError` without `--verbose`) if they are not.
+/// 
+fn check(args: CheckArgs) -> Result<()> {
+    let result: Result<()> = get_enum(args.from, UnitType::Source).and_then(|from| {
+        let to: Unit = get_enum(args.to, UnitType::Target)?;
+        validate(&from, &to)?;
+        if args.verbose {
+            println!("{} and {} are compatible.", from, to);
+        }
+        return Ok(());
+    });
+
+    match result {
+        Err(_) if !args.verbose => return Err(SilentError { exit_code: 1 }.into()),
+        result => return result,
+    }
+}
+
+/// Handles the 'verify' command logic.
+///
+/// It runs every known reference conversion (see `reference_conversions()`)
+/// through `convert()` and reports each one whose result deviates from the
+/// expected value by more than the relative tolerance configured for its
+/// dimension (see `Config::tolerance_for()`). It also converts 1 of every unit
+/// into its base unit and back, which must give 1 again. `convert()` returns the
+/// value of a same-unit conversion unchanged, so this round trip is the only
+/// check that a unit's factor is consistent with itself.
+///
+/// ## Arguments
+///
+/// * `config` - The loaded `Config`, which provides the tolerances.
+///
+/// ## Returns
+///
+/// An `anyhow::Result` which is an error if any reference conversion or round
+/// trip failed.
+/// 
+fn verify(config: &Config) -> Result<()> {
+    let references: Vec<(Unit, Unit, f64, f64)> = reference_conversions();
+    let mut failures: usize = 0;
+    for (from, to, value, expected) in references.iter() {
+        let actual: f64 = convert(from, to, value)?;
+        let deviation: f64 = ((actual - expected) / expected.abs().max(1.0)).abs();
+        if deviation > config.tolerance_for(&from.dimension()) {
+            failures += 1;
+            println!(
+                "FAIL {} {} = {} {} (expected {} {})",
+                value, from, actual, to, expected, to
+            );
+        }
+    }
+
+    let units: Vec<Unit> = UnitDimension::all_dimensions()
+        .iter()
+        .flat_map(Unit::units_in)
+        .collect();
+    for unit in units.iter() {
+        let round_trip: f64 = unit.from_base(unit.to_base(1.0));
+        if (round_trip - 1.0).abs() > config.tolerance_for(&unit.dimension()) {
+            failures += 1;
+            println!(
+                "FAIL 1 {} → {} → {} = {} (expected 1)",
+                unit,
+                base_unit(&unit.dimension()),
+                unit,
+                round_trip
+            );
+        }
+    }
+
+    let checks: usize = references.len() + units.len();
+    if failures > 0 {
+        bail!(format!(
+            "Error: [ERROR] {} of {} checks failed.",
+            failures, checks
+        ));
+    }
+    println!(
+        "All {} reference conversions and {} unit round trips passed.",
+        references.len(),
+        units.len()
+    );
+    return Ok(());
+}
+
+/// Handles the 'preset' command logic.
+///
+/// It looks up the preset by name and converts `value` along each of its
+/// (from, to) pairs, printing the results as an aligned block under the preset's
+/// description. Preset conversions are not added to the history.
+///
+/// ## Arguments
+///
+/// * `name` - The name of the preset.
+/// * `value` - The value to convert.
+///
+/// ## Returns
+///
+/// An `anyhow::Result` which is an error if there is no preset with that name.
+/// 
+fn preset(name: &str, value: f64) -> Result<()> {
+    let Some(preset) = presets::find(name) else {
+        let names: Vec<&str> = presets::presets().iter().map(|p| p.name).collect();
+        bail!(format!(
+            "Error: [ERROR] Unknown preset '{}'. Available presets: {}.",
+            name,
+            names.join(", ")
+        ));
+    };
+
+    let format: FormatOptions = FormatOptions::default();
+    let mut lines: Vec<String> = Vec::new();
+    for (from, to) in preset.pairs.iter() {
+        let conv_value: f64 = convert(from, to, &value)?;
+        lines.push(format_conversion(
+            from,
+            to,
+            value,
+            conv_value,
+            &format,
+            &Language::En,
+        ));
+    }
+
+    println!("{} ({}):", preset.description, preset.name);
+    for line in align_on_equals(&lines) {
+        println!("    {}", line);
+    }
+    return Ok(());
+}
+
+/// Handles the 'diag' command logic.
+///
+/// It converts `value` from every unit of the dimension (see `Unit::units_in()`)
+/// into the dimension's base unit and back with `convert()`, and prints a table
+/// with the value in the base unit, the value after the round trip, and the
+/// absolute error of the round trip.
+///
+/// ## Arguments
+///
+/// * `dimension` - The `UnitDimension` to diagnose.
+/// * `value` - The value converted from each unit.
+///
+/// ## Returns
+///
+/// An `anyhow::Result` which is an error if a conversion fails.
+/// 
+fn diag(dimension: &UnitDimension, value: f64) -> Result<()> {
+    let base: Unit = base_unit(dimension);
+    let mut rows: Vec<[String; 4]> = Vec::new();
+    for unit in Unit::units_in(dimension).iter() {
+        // Units of another kind (torque among energy units) are their own base.
+        let base: &Unit = if unit.kind() == base.kind() {
+            &base
+        } else {
+            unit
+        };
+        let in_base: f64 = convert(unit, base, &value)?;
+        let back: f64 = convert(base, unit, &in_base)?;
+        rows.push([
+            unit.to_string(),
+            format!("{} {}", in_base, base),
+            back.to_string(),
+            format!("{:e}", (back - value).abs()),
+        ]);
+    }
+
+    let header: [String; 4] = [
+        String::from("unit"),
+        String::from("in base unit"),
+        String::from("back"),
+        String::from("abs error"),
+    ];
+    let widths: Vec<usize> = (0..4)
+        .map(|column| {
+            return rows
+                .iter()
+                .chain([&header])
+                .map(|row| row[column].chars().count())
+                .max()
+                .unwrap_or(0);
+        })
+        .collect();
+    println!("Round trip of {} through the base unit ({}):", value, base);
+    for row in [&header].into_iter().chain(rows.iter()) {
+        println!(
+            "{:<w0$}  {:>w1$}  {:>w2$}  {:>w3$}",
+            row[0],
+            row[1],
+            row[2],
+            row[3],
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2],
+            w3 = widths[3]
+        );
+    }
+
+    return Ok(());
+}
+
+/// Handles the 'config --print' command.
+///
+/// The default number of decimal places is resolved like that of `convert`
+/// without `--decimals`, from `UNITCONV_DECIMALS`, the config file, or the
+/// built-in default.
+///
+/// ## Arguments
+///
+/// * `config` - The loaded `Config`.
+///
+/// ## Returns
+///
+/// An `anyhow::Result` indicating success or failure.
+/// 
+fn print_config(config: &Config) -> Result<()> {
+    let decimals: usize = env_decimals()?
+        .or(config.decimals)
+        .unwrap_or(FormatOptions::default().decimals);
+    println!("{}", config.to_toml(decimals));
+    return Ok(());
+}
+
+/// Handles the hidden 'seed-history' development command.
+///
+/// It generates random but valid conversions (a random dimension, two random
+/// units of that dimension, and a random value), runs each through `convert()`,
+/// and adds them to the history. Conversions whose result would be shown as 0.0
+/// (e.g., megabytes to pebibytes) are drawn again, so that every sample is useful.
+///
+/// ## Arguments
+///
+/// * `count` - The number of records to generate.
+/// * `history` - A mutable reference to the `History` struct.
+///
+/// ## Returns
+///
+/// An `anyhow::Result` indicating success or failure.
+/// 
+#[cfg(feature = "history")]
+fn seed_history(count: usize, history: &mut History) -> Result<()> {
+    let mut rng: Rng = Rng::from_time();
+    let format: FormatOptions = FormatOptions::default();
+
+    for _ in 0..count {
+        let (from, to, value, conv_value): (Unit, Unit, f64, f64) = loop {
+            let (from, to): (Unit, Unit) = random_pair(&mut rng);
+            let value: f64 = (rng.range_f64(0.0, 1000.0) * 100.0).round() / 100.0;
+            let conv_value: f64 = convert(&from, &to, &value)?;
+            if format_value(conv_value, UnitType::Target, &format) != "0.0" {
+                break (from, to, value, conv_value);
+            }
+        };
+
+        let str_result: String =
+            format_conversion(&from, &to, value, conv_value, &format, &Language::En);
+        (*history).add(str_result, &from, &to, value, conv_value, None);
+    }
+
+    println!("Added {} sample conversions to the history.", count);
+    return Ok(());
+}
+
+/// Picks two distinct units of a random dimension.
+///
+/// Units whose kind differs from their dimension (e.g., torque among energy
+/// units) are left out, so that the pair can always be converted.
+///
+/// ## Arguments
+///
+/// * `rng` - The `Rng` used for the choice.
+///
+/// ## Returns
+///
+/// The source and target `Unit`.
+/// 
+fn random_pair(rng: &mut Rng) -> (Unit, Unit) {
+    let dimensions: [UnitDimension; 11] = UnitDimension::all_dimensions();
+    let dimension: &UnitDimension = &dimensions[rng.below(dimensions.len())];
+    let units: Vec<Unit> = Unit::units_in(dimension)
+        .into_iter()
+        .filter(|unit| unit.kind() == dimension.as_str())
+        .collect();
+    // Pick two distinct units: skip over the source index when picking the target.
+    let from_index: usize = rng.below(units.len());
+    let mut to_index: usize = rng.below(units.len() - 1);
+    if to_index >= from_index {
+        to_index += 1;
+    }
+    return (units[from_index].clone(), units[to_index].clone());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pad_right_aligns_values() {
+        let padded: FormatOptions = FormatOptions {
+            pad: 8,
+            ..FormatOptions::default()
+        };
+        assert_eq!(format_value(5.0, UnitType::Target, &padded), "     5.0");
+        assert_eq!(format_value(-5.0, UnitType::Target, &padded), "    -5.0");
+        assert_eq!(
+            format_value(123456789.0, UnitType::Target, &padded),
+            "123456789.0"
+        );
+    }
+
+    #[test]
+    fn picked_unit_is_read_from_the_input() {
+        let choices: [Unit; 3] = [Unit::Celsius, Unit::Fahrenheit, Unit::Kelvin];
+        let picked: Unit = pick_unit(&choices, &mut "3\n".as_bytes()).unwrap();
+        assert!(picked == Unit::Kelvin);
+        assert!(pick_unit(&choices, &mut "4\n".as_bytes()).is_err());
+        assert!(pick_unit(&choices, &mut "kelvin\n".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn source_values_are_rounded_to_source_decimals() {
+        let default: FormatOptions = FormatOptions::default();
+        assert_eq!(
+            format_value(99.99999999, UnitType::Source, &default),
+            "100.0"
+        );
+        assert_eq!(
+            format_value(1.23456789, UnitType::Source, &default),
+            "1.234568"
+        );
+        assert_eq!(format_value(12.0, UnitType::Source, &default), "12.0");
+        let two: FormatOptions = FormatOptions {
+            source_decimals: 2,
+            ..default
+        };
+        assert_eq!(format_value(1.23456789, UnitType::Source, &two), "1.23");
+        assert_eq!(format_value(1.23456789, UnitType::Target, &two), "1.2346");
+    }
+
+    #[test]
+    fn banker_rounding_resolves_ties_to_even() {
+        let banker: FormatOptions = FormatOptions {
+            decimals: 0,
+            rounding: RoundingMode::Banker,
+            ..FormatOptions::default()
+        };
+        assert_eq!(format_value(2.5, UnitType::Target, &banker), "2.0");
+        assert_eq!(format_value(3.5, UnitType::Target, &banker), "4.0");
+        assert_eq!(format_value(-2.5, UnitType::Target, &banker), "-2.0");
+        assert_eq!(round_half_even(0.125, 2), 0.12);
+        assert_eq!(round_half_even(0.375, 2), 0.38);
+    }
+
+    #[test]
+    fn results_are_aligned_on_the_equals_sign() {
+        let lines: Vec<String> = vec![
+            String::from("1.0 km = 1000.0 m"),
+            String::from("25.5 km = 25500.0 m"),
+            String::from("100.0 km = 100000.0 m"),
+        ];
+        let aligned: Vec<String> = align_on_equals(&lines);
+        assert_eq!(
+            aligned,
+            [
+                "  1.0 km = 1000.0 m",
+                " 25.5 km = 25500.0 m",
+                "100.0 km = 100000.0 m"
+            ]
+        );
+        let columns: Vec<usize> = aligned.iter().map(|line| line.find('=').unwrap()).collect();
+        assert_eq!(columns, [9, 9, 9]);
+
+        let single: Vec<String> = vec![String::from("1.0 km = 1000.0 m")];
+        assert_eq!(align_on_equals(&single), single);
+    }
+
+    #[test]
+    fn specs_are_split_into_value_and_units() {
+        let (value, from, to): (SourceValue, String, String) = parse_spec("100c>f").unwrap();
+        assert_eq!((value.value, from.as_str(), to.as_str()), (100.0, "c", "f"));
+        let (value, from, to): (SourceValue, String, String) = parse_spec("5km>miles").unwrap();
+        assert_eq!(
+            (value.value, from.as_str(), to.as_str()),
+            (5.0, "km", "miles")
+        );
+        let (value, from, to): (SourceValue, String, String) = parse_spec("5 km to miles").unwrap();
+        assert_eq!(
+            (value.value, from.as_str(), to.as_str()),
+            (5.0, "km", "miles")
+        );
+        assert_eq!(
+            parse_spec("km>miles").err().unwrap().to_string(),
+            "Error: [ERROR] Invalid spec 'km>miles'. Expected 'VALUE FROM > TO' (e.g., '100c>f' or '5 km to miles')."
+        );
+    }
+
+    #[test]
+    fn auto_precision_follows_the_source_value() {
+        let short: SourceValue = cli::parse_source_value("1.5").unwrap();
+        let long: SourceValue = cli::parse_source_value("1.23456").unwrap();
+        assert_eq!(auto_decimals(&short, 0.932057), 2);
+        assert_eq!(auto_decimals(&long, 0.767120), 6);
+        let meter: SourceValue = cli::parse_source_value("1").unwrap();
+        assert_eq!(auto_decimals(&meter, 0.001), 3);
+    }
+
+    #[test]
+    fn negative_zero_is_printed_without_a_sign() {
+        let default: FormatOptions = FormatOptions::default();
+        assert_eq!(format_value(-0.0, UnitType::Target, &default), "0.0");
+        assert_eq!(format_value(-0.00001, UnitType::Target, &default), "0.0");
+        assert_eq!(format_value(-0.0, UnitType::Source, &default), "0.0");
+    }
+
+    #[test]
+    fn fuzz_parse_never_panics() {
+        let pieces: [&str; 24] = [
+            "1", "0", "9", ".", "-", "+", "e", "E", "^", ">", ",", " ", "to", "km", "c", "°", "µ",
+            "²", "square ", "/", "100", "inf", "NaN", "🌡",
+        ];
+        let mut rng: Rng = Rng::new(454);
+        for _ in 0..5000 {
+            let length: usize = rng.below(8);
+            let input: String = (0..length)
+                .map(|_| pieces[rng.below(pieces.len())])
+                .collect();
+            let _ = fuzz_parse(&input);
+        }
+        for input in [
+            "", " ", "^", "^2", "m^", ">", "1>", ">f", "1e", "1e999", "°", "-.", "1.5.5",
+        ] {
+            let _ = fuzz_parse(input);
+        }
+        assert!(fuzz_parse("100c>f").is_ok());
+        assert!(fuzz_parse("km").is_ok());
+    }
+
+    #[test]
+    fn durations_are_humanized() {
+        assert_eq!(humanize_duration(93784.0), "1 d 2 h 3 min 4 s");
+        assert_eq!(humanize_duration(3600.0), "1 h");
+        assert_eq!(humanize_duration(0.0), "0 s");
+    }
+
+    #[test]
+    fn values_snap_to_the_increment() {
+        assert_eq!(round_to_increment(2.6, Some(0.25)), 2.5);
+        assert_eq!(round_to_increment(2.65, Some(0.25)), 2.75);
+        assert_eq!(round_to_increment(2.6, None), 2.6);
+    }
+
+    #[test]
+    fn compound_values_are_summed_in_the_first_unit() {
+        let (height, unit): (SourceValue, Unit) = parse_compound("5 feet 11 inches", &[]).unwrap();
+        assert!(unit == Unit::Foot);
+        assert!((height.value - 71.0 / 12.0).abs() < 1e-12);
+        let (weight, unit): (SourceValue, Unit) = parse_compound("10 lb 4 oz", &[]).unwrap();
+        assert!(unit == Unit::Pound);
+        assert_eq!(weight.value, 10.25);
+        assert!(parse_compound("5 ft 2 kg", &[]).is_err());
+    }
+
+    #[test]
+    fn random_pair_is_deterministic_for_a_seed() {
+        let (from, to): (Unit, Unit) = random_pair(&mut Rng::new(42));
+        assert!(from == Unit::Kelvin && to == Unit::Celsius);
+        for seed in 0..50 {
+            let (from, to): (Unit, Unit) = random_pair(&mut Rng::new(seed));
+            let (again_from, again_to): (Unit, Unit) = random_pair(&mut Rng::new(seed));
+            assert!(from == again_from && to == again_to);
+            assert!(from != to && from.dimension() == to.dimension());
+        }
+    }
 }
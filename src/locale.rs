@@ -0,0 +1,210 @@
+//! # Localization Module
+//!
+//! This module provides translated unit names for conversion output. Parsing always
+//! uses the English canonical names (see `units::get_enum()`); translations only affect
+//! how results are displayed. Units without a translation fall back to their symbol.
+//! On terminals that cannot show Unicode, symbols and names are written in ASCII.
+
+use crate::units::Unit;
+use clap::ValueEnum;
+use std::sync::OnceLock;
+
+/// Languages available for unit names in the output.
+#[derive(Clone, ValueEnum)]
+pub enum Language {
+    /// English (unit symbols, the default output).
+    En,
+    /// Spanish.
+    Es,
+    /// German.
+    De,
+}
+
+/// Character sets in which unit symbols can be written.
+#[derive(Clone, ValueEnum)]
+pub enum Charset {
+    /// Detect whether the terminal supports Unicode (the default).
+    Auto,
+    /// Always use Unicode symbols (e.g., °C, m²).
+    Unicode,
+    /// Always use ASCII symbols (e.g., C, m^2).
+    Ascii,
+}
+
+/// Whether output is restricted to ASCII, set once by `set_charset()`.
+static ASCII_ONLY: OnceLock<bool> = OnceLock::new();
+
+/// Sets the character set used for unit symbols and names in the output.
+///
+/// With `Charset::Auto`, the terminal is checked by `terminal_supports_unicode()`.
+/// Only the first call has an effect.
+///
+/// ## Arguments
+///
+/// * `charset` - The `Charset` chosen on the command line.
+///
+pub fn set_charset(charset: &Charset) {
+    let ascii: bool = match charset {
+        Charset::Auto => !terminal_supports_unicode(),
+        Charset::Unicode => false,
+        Charset::Ascii => true,
+    };
+    let _ = ASCII_ONLY.set(ascii);
+}
+
+/// Checks whether output is restricted to ASCII (see `set_charset()`).
+///
+/// ## Returns
+///
+/// `true` if unit symbols must be written in ASCII.
+///
+pub fn ascii_only() -> bool {
+    return ASCII_ONLY.get().copied().unwrap_or(false);
+}
+
+/// Makes a best-effort guess at whether the terminal can display Unicode.
+///
+/// On Unix, the first non-empty of `LC_ALL`, `LC_CTYPE` and `LANG` must name a
+/// UTF-8 codeset; if none is set, Unicode is assumed. On Windows, the active
+/// console code page (from `chcp`) must be 65001 (UTF-8).
+///
+/// ## Returns
+///
+/// `true` if Unicode output is expected to display correctly.
+///
+fn terminal_supports_unicode() -> bool {
+    if cfg!(windows) {
+        let output = std::process::Command::new("cmd")
+            .args(["/C", "chcp"])
+            .output();
+        match output {
+            Ok(output) => return String::from_utf8_lossy(&output.stdout).contains("65001"),
+            Err(_) => return true,
+        }
+    }
+
+    let locale: Option<String> = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.is_empty());
+    match locale {
+        Some(locale) => {
+            let locale: String = locale.to_ascii_lowercase();
+            return locale.contains("utf-8") || locale.contains("utf8");
+        }
+        None => return true,
+    }
+}
+
+/// Replaces the non-ASCII characters of a symbol or name with ASCII.
+///
+/// ## Arguments
+///
+/// * `text` - A unit symbol or translated name (e.g., `°C`, `m²`, `kilómetros`).
+///
+/// ## Returns
+///
+/// A `String` such as `C`, `m^2` or `kilometros`. Characters without an ASCII
+/// form are replaced by `?`.
+///
+pub fn to_ascii(text: &str) -> String {
+    let mut ascii: String = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '°' => {}
+            '²' => ascii.push_str("^2"),
+            '³' => ascii.push_str("^3"),
+            '·' => ascii.push('*'),
+            'µ' | 'μ' => ascii.push('u'),
+            'á' | 'à' | 'â' | 'ä' => ascii.push('a'),
+            'é' | 'è' | 'ê' | 'ë' => ascii.push('e'),
+            'í' | 'ì' | 'î' | 'ï' => ascii.push('i'),
+            'ó' | 'ò' | 'ô' | 'ö' => ascii.push('o'),
+            'ú' | 'ù' | 'û' | 'ü' => ascii.push('u'),
+            'ñ' => ascii.push('n'),
+            'ß' => ascii.push_str("ss"),
+            c if c.is_ascii() => ascii.push(c),
+            _ => ascii.push('?'),
+        }
+    }
+    return ascii;
+}
+
+/// Looks up the translated name of a unit in the embedded translation table.
+///
+/// ## Arguments
+///
+/// * `unit` - The `Unit` to translate.
+/// * `language` - The `Language` to translate into.
+///
+/// ## Returns
+///
+/// An `Option<&'static str>` with the translated name, or `None` if the table has
+/// no entry for the unit in that language.
+///
+fn translate(unit: &Unit, language: &Language) -> Option<&'static str> {
+    let name: &'static str = match (language, unit) {
+        // Spanish
+        (Language::Es, Unit::Celsius) => "grados Celsius",
+        (Language::Es, Unit::Fahrenheit) => "grados Fahrenheit",
+        (Language::Es, Unit::Kelvin) => "kelvin",
+        (Language::Es, Unit::Centimeter) => "centímetros",
+        (Language::Es, Unit::Inch) => "pulgadas",
+        (Language::Es, Unit::Foot) => "pies",
+        (Language::Es, Unit::Kilometer) => "kilómetros",
+        (Language::Es, Unit::Mile) => "millas",
+        (Language::Es, Unit::Nanometer) => "nanómetros",
+        (Language::Es, Unit::Millimeter) => "milímetros",
+        (Language::Es, Unit::Meter) => "metros",
+        (Language::Es, Unit::Hertz) => "hercios",
+        (Language::Es, Unit::Kilohertz) => "kilohercios",
+        (Language::Es, Unit::Megahertz) => "megahercios",
+        (Language::Es, Unit::Gigahertz) => "gigahercios",
+        (Language::Es, Unit::Terahertz) => "terahercios",
+        // German
+        (Language::De, Unit::Celsius) => "Grad Celsius",
+        (Language::De, Unit::Fahrenheit) => "Grad Fahrenheit",
+        (Language::De, Unit::Kelvin) => "Kelvin",
+        (Language::De, Unit::Centimeter) => "Zentimeter",
+        (Language::De, Unit::Inch) => "Zoll",
+        (Language::De, Unit::Foot) => "Fuß",
+        (Language::De, Unit::Kilometer) => "Kilometer",
+        (Language::De, Unit::Mile) => "Meilen",
+        (Language::De, Unit::Nanometer) => "Nanometer",
+        (Language::De, Unit::Millimeter) => "Millimeter",
+        (Language::De, Unit::Meter) => "Meter",
+        (Language::De, Unit::Hertz) => "Hertz",
+        (Language::De, Unit::Kilohertz) => "Kilohertz",
+        (Language::De, Unit::Megahertz) => "Megahertz",
+        (Language::De, Unit::Gigahertz) => "Gigahertz",
+        (Language::De, Unit::Terahertz) => "Terahertz",
+        _ => return None,
+    };
+
+    return Some(name);
+}
+
+/// Gets the name used to display a unit in the given language.
+///
+/// English output uses the unit's symbol, as does any unit missing from the
+/// translation table. The name is written in ASCII if `ascii_only()` is set.
+///
+/// ## Arguments
+///
+/// * `unit` - The `Unit` to display.
+/// * `language` - The `Language` of the output.
+///
+/// ## Returns
+///
+/// A `String` with the localized unit name.
+///
+pub fn unit_name(unit: &Unit, language: &Language) -> String {
+    let name: String = match translate(unit, language) {
+        Some(name) => String::from(name),
+        None => unit.symbol(),
+    };
+    match ascii_only() {
+        true => return to_ascii(&name),
+        false => return name,
+    }
+}
@@ -4,15 +4,23 @@
 //! Its primary role is to execute the main application logic defined in the `unitconv` 
 //! library and handle any potential errors by printing them to standard error.
 
-use unitconv::run;
+use std::process;
+use unitconv::{SilentError, run};
 
 /// The main entry point of the application.
 ///
 /// Executes the command-line interface logic from the `run` function. If any errors
-/// occur during execution, they are caught and printed to `stderr`.
+/// occur during execution, they are caught and printed to `stderr`, and the process
+/// exits with a non-zero code. Errors suppressed by `--quiet-errors` only set the code.
 /// 
 fn main() {
     if let Err(e) = run() {
-        eprintln!("{}", e);
+        match e.downcast_ref::<SilentError>() {
+            Some(silent) => process::exit(silent.exit_code),
+            None => {
+                eprintln!("{}", e);
+                process::exit(1);
+            }
+        }
     }
 }
@@ -0,0 +1,49 @@
+//! # Conversion History Stub
+//!
+//! This module replaces the `history` module when the `history` feature is
+//! disabled. It offers the part of the `History` interface that conversions use,
+//! but keeps nothing: no file is read or written, and added records are dropped.
+//! This lets the rest of the tool build without serde and the history file.
+
+use crate::config::HistoryFormat;
+use crate::units::Unit;
+use anyhow::Result;
+
+/// A conversion history that never stores anything.
+#[derive(Default)]
+pub struct History {
+    /// Always empty, since no conversions are remembered.
+    pub recent_units: Vec<String>,
+}
+
+impl History {
+    /// Creates an empty history; there is no history file to load.
+    pub fn load(_format: HistoryFormat) -> Result<Self> {
+        return Ok(Self::default());
+    }
+
+    /// Drops a conversion record.
+    pub fn add(
+        &mut self,
+        _record: String,
+        _from: &Unit,
+        _to: &Unit,
+        _input: f64,
+        _output: f64,
+        _note: Option<String>,
+    ) {
+    }
+
+    /// Reports that no records are waiting to be committed.
+    pub fn has_pending(&self) -> bool {
+        return false;
+    }
+
+    /// Does nothing, since no records are ever pending.
+    pub fn commit(&mut self) {}
+
+    /// Does nothing, since there is no history file.
+    pub fn save(&self) -> Result<()> {
+        return Ok(());
+    }
+}
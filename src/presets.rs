@@ -0,0 +1,82 @@
+//! # Presets Module
+//!
+//! This module defines presets: named bundles of common conversions for users who
+//! do not know the unit names yet. A preset converts one value along each of its
+//! (from, to) pairs, e.g. the "weather" preset reads 25 as both 25 °C and 25 km/h.
+
+use crate::units::Unit;
+
+/// A named bundle of conversions.
+pub struct Preset {
+    /// The name used on the command line (e.g., "weather").
+    pub name: &'static str,
+    /// A short description shown as the heading of the preset's output.
+    pub description: &'static str,
+    /// The conversions to run, as (from, to) pairs.
+    pub pairs: Vec<(Unit, Unit)>,
+}
+
+/// Provides all available presets.
+///
+/// ## Returns
+///
+/// A `Vec<Preset>` with every preset, in the order in which they are listed.
+///
+pub fn presets() -> Vec<Preset> {
+    return vec![
+        Preset {
+            name: "weather",
+            description: "Temperature and wind speed",
+            pairs: vec![
+                (Unit::Celsius, Unit::Fahrenheit),
+                (Unit::Fahrenheit, Unit::Celsius),
+                (Unit::KilometerPerHour, Unit::MilePerHour),
+                (Unit::MilePerHour, Unit::KilometerPerHour),
+            ],
+        },
+        Preset {
+            name: "travel",
+            description: "Distances and speed limits",
+            pairs: vec![
+                (Unit::Kilometer, Unit::Mile),
+                (Unit::Mile, Unit::Kilometer),
+                (Unit::KilometerPerHour, Unit::MilePerHour),
+                (Unit::MilePerHour, Unit::KilometerPerHour),
+            ],
+        },
+        Preset {
+            name: "storage",
+            description: "Decimal and binary data sizes",
+            pairs: vec![
+                (Unit::Gigabyte, Unit::Gibibyte),
+                (Unit::Gibibyte, Unit::Gigabyte),
+                (Unit::Terabyte, Unit::Tebibyte),
+            ],
+        },
+        Preset {
+            name: "screen",
+            description: "Typographic and screen sizes",
+            pairs: vec![
+                (Unit::Point, Unit::Pixel),
+                (Unit::Pixel, Unit::Point),
+                (Unit::Inch, Unit::Centimeter),
+            ],
+        },
+    ];
+}
+
+/// Finds a preset by name, ignoring case.
+///
+/// ## Arguments
+///
+/// * `name` - The preset name given on the command line.
+///
+/// ## Returns
+///
+/// An `Option<Preset>` which is `None` if there is no preset with that name.
+///
+pub fn find(name: &str) -> Option<Preset> {
+    return presets()
+        .into_iter()
+        .find(|preset| preset.name.eq_ignore_ascii_case(name.trim()));
+}
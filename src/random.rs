@@ -0,0 +1,85 @@
+//! # Random Number Module
+//!
+//! This module provides a small, dependency-free pseudo-random number generator
+//! (xorshift64*). It is only meant for generating sample data and demos, never
+//! for anything security-related.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A seedable xorshift64* pseudo-random number generator.
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// Creates a generator from a fixed seed, producing a reproducible sequence.
+    ///
+    /// ## Arguments
+    ///
+    /// * `seed` - The seed value. A seed of zero is replaced by a fixed non-zero constant.
+    ///
+    /// ## Returns
+    ///
+    /// A new `Rng`.
+    ///
+    pub fn new(seed: u64) -> Self {
+        let state: u64 = match seed {
+            0 => 0x9E37_79B9_7F4A_7C15,
+            seed => seed,
+        };
+        return Rng { state };
+    }
+
+    /// Creates a generator seeded from the current time and process ID.
+    ///
+    /// ## Returns
+    ///
+    /// A new `Rng` with an unpredictable seed.
+    ///
+    pub fn from_time() -> Self {
+        let nanos: u64 = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_nanos() as u64)
+            .unwrap_or_default();
+        return Self::new(nanos ^ ((std::process::id() as u64) << 32));
+    }
+
+    /// Generates the next pseudo-random `u64`.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        return self.state.wrapping_mul(0x2545_F491_4F6C_DD1D);
+    }
+
+    /// Generates a pseudo-random index in `0..bound`.
+    ///
+    /// ## Arguments
+    ///
+    /// * `bound` - The exclusive upper bound; must be greater than zero.
+    ///
+    /// ## Returns
+    ///
+    /// A `usize` smaller than `bound`.
+    ///
+    pub fn below(&mut self, bound: usize) -> usize {
+        return (self.next_u64() % bound as u64) as usize;
+    }
+
+    /// Generates a pseudo-random `f64` in `low..high`.
+    ///
+    /// ## Arguments
+    ///
+    /// * `low` - The inclusive lower bound.
+    /// * `high` - The exclusive upper bound.
+    ///
+    /// ## Returns
+    ///
+    /// An `f64` within the range.
+    ///
+    #[cfg(feature = "history")]
+    pub fn range_f64(&mut self, low: f64, high: f64) -> f64 {
+        let unit: f64 = (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+        return low + unit * (high - low);
+    }
+}
@@ -0,0 +1,167 @@
+//! # System Log Module
+//!
+//! This module sends conversions to the system log for `--log-syslog`, using the
+//! `syslog` crate (the `syslog` feature). Messages are written in the BSD syslog
+//! format (RFC 3164) to the local log socket, which both syslog daemons and
+//! systemd-journald listen on. When the feature is disabled, on platforms without
+//! a local log socket, or when no log daemon is listening, logging is a no-op and
+//! a single warning is printed.
+
+use std::sync::{Mutex, OnceLock};
+
+/// A destination for log messages.
+trait Sink: Send {
+    /// Writes a message at info level.
+    ///
+    /// ## Returns
+    ///
+    /// `true` if the message was written.
+    ///
+    fn info(&mut self, message: &str) -> bool;
+}
+
+#[cfg(feature = "syslog")]
+impl Sink for syslog::Logger<syslog::LoggerBackend, syslog::Formatter3164> {
+    fn info(&mut self, message: &str) -> bool {
+        return syslog::Logger::info(self, message).is_ok();
+    }
+}
+
+/// The system log, along with whether the warning about it being unavailable
+/// has been printed.
+struct SystemLog {
+    sink: Option<Box<dyn Sink>>,
+    warned: bool,
+}
+
+impl SystemLog {
+    /// Logs a message, printing a warning the first time it cannot be written.
+    ///
+    /// ## Arguments
+    ///
+    /// * `message` - The message to log.
+    ///
+    fn info(&mut self, message: &str) {
+        let sent: bool = match &mut self.sink {
+            Some(sink) => sink.info(message),
+            None => false,
+        };
+        if !sent && !self.warned {
+            self.warned = true;
+            if cfg!(feature = "syslog") {
+                eprintln!(
+                    "Warning: [WARNING] The system log is not available on this system; --log-syslog is ignored."
+                );
+            } else {
+                eprintln!(
+                    "Warning: [WARNING] This build has no system log support (the 'syslog' feature is disabled); --log-syslog is ignored."
+                );
+            }
+        }
+    }
+}
+
+/// The system log, connected by the first message.
+static LOG: OnceLock<Mutex<SystemLog>> = OnceLock::new();
+
+/// Logs a message to the system log at info level.
+///
+/// ## Arguments
+///
+/// * `message` - The message to log (e.g., a formatted conversion).
+///
+pub fn info(message: &str) {
+    let log: &Mutex<SystemLog> = LOG.get_or_init(|| {
+        return Mutex::new(SystemLog {
+            sink: connect(),
+            warned: false,
+        });
+    });
+    if let Ok(mut log) = log.lock() {
+        log.info(message);
+    }
+}
+
+/// Connects to the local log socket, with the "user" facility.
+///
+/// ## Returns
+///
+/// An `Option<Box<dyn Sink>>` which is `None` if no log daemon is listening.
+///
+#[cfg(feature = "syslog")]
+fn connect() -> Option<Box<dyn Sink>> {
+    let formatter: syslog::Formatter3164 = syslog::Formatter3164 {
+        facility: syslog::Facility::LOG_USER,
+        hostname: None,
+        process: String::from("unitconv"),
+        pid: std::process::id(),
+    };
+    let logger = syslog::unix(formatter).ok()?;
+    return Some(Box::new(logger));
+}
+
+/// Reports that there is no system log, since the `syslog` feature is disabled.
+#[cfg(not(feature = "syslog"))]
+fn connect() -> Option<Box<dyn Sink>> {
+    return None;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    /// A sink that records the messages it is given, or rejects them all.
+    struct MockSink {
+        messages: Arc<Mutex<Vec<String>>>,
+        available: bool,
+    }
+
+    impl Sink for MockSink {
+        fn info(&mut self, message: &str) -> bool {
+            self.messages.lock().unwrap().push(String::from(message));
+            return self.available;
+        }
+    }
+
+    fn mock_log(available: bool) -> (SystemLog, Arc<Mutex<Vec<String>>>) {
+        let messages: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(Vec::new()));
+        let sink: MockSink = MockSink {
+            messages: Arc::clone(&messages),
+            available,
+        };
+        let log: SystemLog = SystemLog {
+            sink: Some(Box::new(sink)),
+            warned: false,
+        };
+        return (log, messages);
+    }
+
+    #[test]
+    fn logs_each_message() {
+        let (mut log, messages) = mock_log(true);
+        log.info("10 km = 6.2137 mi");
+        log.info("0 °C = 32.0 °F");
+        assert_eq!(
+            *messages.lock().unwrap(),
+            ["10 km = 6.2137 mi", "0 °C = 32.0 °F"]
+        );
+        assert!(!log.warned);
+    }
+
+    #[test]
+    fn warns_once_when_the_log_is_unavailable() {
+        let (mut log, messages) = mock_log(false);
+        log.info("10 km = 6.2137 mi");
+        assert!(log.warned);
+        log.info("0 °C = 32.0 °F");
+        assert_eq!(messages.lock().unwrap().len(), 2);
+
+        let mut missing: SystemLog = SystemLog {
+            sink: None,
+            warned: false,
+        };
+        missing.info("10 km = 6.2137 mi");
+        assert!(missing.warned);
+    }
+}
@@ -0,0 +1,187 @@
+//! # Time Zone Module
+//!
+//! This module formats the UTC timestamps stored in the conversion history in a
+//! chosen time zone. IANA zones come from the `chrono-tz` database, which is built
+//! into the binary, so they work the same on every platform; the local zone is
+//! looked up by `chrono`. Besides IANA names, UTC and fixed offsets are supported.
+
+use anyhow::{Result, bail};
+use chrono::{DateTime, FixedOffset, Local, Offset, TimeZone as _, Utc};
+use chrono_tz::Tz;
+use std::env;
+
+/// A time zone in which history timestamps can be displayed.
+pub enum TimeZone {
+    /// Coordinated Universal Time.
+    Utc,
+    /// A fixed offset from UTC.
+    Fixed(FixedOffset),
+    /// A zone of the IANA time zone database (e.g., America/New_York).
+    Named(Tz),
+    /// The zone the system is configured with.
+    Local,
+}
+
+impl TimeZone {
+    /// Parses a time zone name.
+    ///
+    /// ## Arguments
+    ///
+    /// * `name` - `local`, `UTC`, a fixed offset such as `+07:00`, or an IANA
+    ///   name such as `America/New_York`.
+    ///
+    /// ## Returns
+    ///
+    /// A `Result<TimeZone>` which is the parsed zone, or an error if the name is
+    /// not a known time zone.
+    ///
+    pub fn parse(name: &str) -> Result<TimeZone> {
+        let name: &str = name.trim();
+        if name.eq_ignore_ascii_case("local") {
+            return Ok(Self::local());
+        }
+        if name.eq_ignore_ascii_case("utc") || name == "Z" {
+            return Ok(TimeZone::Utc);
+        }
+        if let Some(offset) = parse_offset(name) {
+            return Ok(TimeZone::Fixed(offset));
+        }
+        if let Ok(zone) = name.parse::<Tz>() {
+            return Ok(TimeZone::Named(zone));
+        }
+
+        bail!(format!(
+            "Error: [ERROR] Unknown timezone '{}'. Use 'local', 'UTC', an offset like '+07:00', or an IANA name like 'America/New_York'.",
+            name
+        ));
+    }
+
+    /// Gets the local time zone of the system.
+    ///
+    /// The `TZ` environment variable takes precedence if it names a zone that
+    /// `parse()` accepts; otherwise the zone of the system is used.
+    ///
+    /// ## Returns
+    ///
+    /// The local `TimeZone`.
+    ///
+    pub fn local() -> TimeZone {
+        if let Ok(tz) = env::var("TZ") {
+            let tz: &str = tz.trim_start_matches(':');
+            if !tz.is_empty()
+                && !tz.eq_ignore_ascii_case("local")
+                && let Ok(zone) = Self::parse(tz)
+            {
+                return zone;
+            }
+        }
+
+        return TimeZone::Local;
+    }
+
+    /// Gets the UTC offset of the zone at a point in time.
+    ///
+    /// ## Arguments
+    ///
+    /// * `timestamp` - The point in time, in UNIX seconds.
+    ///
+    /// ## Returns
+    ///
+    /// The UTC offset in seconds.
+    ///
+    pub fn offset_at(&self, timestamp: i64) -> i64 {
+        let Some(utc) = DateTime::<Utc>::from_timestamp(timestamp, 0) else {
+            return 0;
+        };
+        let offset: FixedOffset = match self {
+            TimeZone::Utc => return 0,
+            TimeZone::Fixed(offset) => *offset,
+            TimeZone::Named(zone) => zone.offset_from_utc_datetime(&utc.naive_utc()).fix(),
+            TimeZone::Local => Local.offset_from_utc_datetime(&utc.naive_utc()).fix(),
+        };
+        return offset.local_minus_utc() as i64;
+    }
+
+    /// Formats a UTC timestamp as local time in this zone.
+    ///
+    /// ## Arguments
+    ///
+    /// * `timestamp` - The point in time, in UNIX seconds.
+    ///
+    /// ## Returns
+    ///
+    /// A `String` such as `2024-07-04 08:00:00 -04:00`, or the plain number of
+    /// seconds if the timestamp is out of the supported range.
+    ///
+    pub fn format(&self, timestamp: i64) -> String {
+        let (Some(utc), Some(offset)) = (
+            DateTime::<Utc>::from_timestamp(timestamp, 0),
+            FixedOffset::east_opt(self.offset_at(timestamp) as i32),
+        ) else {
+            return timestamp.to_string();
+        };
+        return utc
+            .with_timezone(&offset)
+            .format("%Y-%m-%d %H:%M:%S %:z")
+            .to_string();
+    }
+}
+
+/// Parses a fixed UTC offset such as `+07:00`, `-0530` or `+09`.
+///
+/// ## Returns
+///
+/// An `Option<FixedOffset>` with the offset, or `None` if `text` is not an offset.
+///
+fn parse_offset(text: &str) -> Option<FixedOffset> {
+    let text: &str = text
+        .strip_prefix("UTC")
+        .or(text.strip_prefix("utc"))
+        .unwrap_or(text);
+    let sign: i32 = match text.chars().next()? {
+        '+' => 1,
+        '-' => -1,
+        _ => return None,
+    };
+    let digits: String = text[1..].replace(':', "");
+    if !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let (hours, minutes): (i32, i32) = match digits.len() {
+        1 | 2 => (digits.parse().ok()?, 0),
+        4 => (digits[0..2].parse().ok()?, digits[2..4].parse().ok()?),
+        _ => return None,
+    };
+    if hours > 14 || minutes > 59 {
+        return None;
+    }
+
+    return FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_york_follows_daylight_saving_time() {
+        let zone: TimeZone = TimeZone::parse("America/New_York").unwrap();
+        // 2024-07-04 12:00:00 UTC and 2024-01-15 12:00:00 UTC.
+        assert_eq!(zone.offset_at(1_720_094_400), -4 * 3600);
+        assert_eq!(zone.format(1_720_094_400), "2024-07-04 08:00:00 -04:00");
+        assert_eq!(zone.offset_at(1_705_320_000), -5 * 3600);
+        assert_eq!(zone.format(1_705_320_000), "2024-01-15 07:00:00 -05:00");
+    }
+
+    #[test]
+    fn parses_fixed_offsets_and_utc() {
+        assert_eq!(TimeZone::parse("+05:30").unwrap().offset_at(0), 19_800);
+        assert_eq!(TimeZone::parse("UTC-0800").unwrap().offset_at(0), -28_800);
+        assert_eq!(
+            TimeZone::parse("Z").unwrap().format(0),
+            "1970-01-01 00:00:00 +00:00"
+        );
+        assert!(TimeZone::parse("+15:00").is_err());
+        assert!(TimeZone::parse("Mars/Base").is_err());
+    }
+}
@@ -3,10 +3,16 @@
 //! This module defines all the supported units, their properties, and related utilities.
 //! It includes enums for `Unit`, `UnitDimension`, and helpers for parsing and displaying them.
 
+use crate::converter::{Factor, unit_factor};
+use crate::custom_units::{self, CustomId, CustomUnit};
+use crate::locale;
 use anyhow::{Result, bail};
+use clap::ValueEnum;
+use std::cmp::Ordering;
 use std::fmt::{Display, Formatter};
 
 /// Differentiates between a source unit and a target unit
+#[derive(Clone, Copy)]
 pub enum UnitType {
     Source,
     Target,
@@ -22,19 +28,245 @@ impl Display for UnitType {
     }
 }
 
+/// Options controlling how unit strings are parsed by `get_enum_with()`.
+#[derive(Clone, Copy, Default)]
+pub struct ParseOptions<'a> {
+    /// Requires the casing the tool prints (e.g., "km", "°C", "MHz") instead of
+    /// matching case-insensitively (see `canonical_spelling()`).
+    pub strict_case: bool,
+    /// Accepts only unit symbols and abbreviations (e.g., "km", "°C"), rejecting
+    /// full unit names such as "kilometer".
+    pub symbols_only: bool,
+    /// Recently used unit names, most recent first, which are preferred when
+    /// suggesting a correction for an unrecognized unit.
+    pub recent: &'a [String],
+    /// The dimension whose units are preferred over all others when suggesting a
+    /// correction (e.g., the source's dimension when parsing target units).
+    pub dimension: Option<UnitDimension>,
+    /// The dimension every unit must belong to. A symbol shared by units of
+    /// several dimensions (e.g., "m" for meter and minute) resolves to the unit
+    /// in this dimension, and a unit outside of it is rejected.
+    pub assumed_dimension: Option<UnitDimension>,
+}
+
 /// Dimension category of a unit.
-#[derive(PartialEq)]
+#[derive(Clone, Copy, PartialEq)]
 pub enum UnitDimension {
     Temperature,
     Length,
+    Frequency,
+    Data,
+    Energy,
+    Speed,
+    FuelEconomy,
+    Time,
+    Mass,
+    Area,
+    Volume,
+}
+
+impl UnitDimension {
+    /// Provides a list of all supported `UnitDimension` variants.
+    ///
+    /// ## Returns
+    ///
+    /// An array of all `UnitDimension` variants.
+    ///
+    pub fn all_dimensions() -> [UnitDimension; 11] {
+        [
+            UnitDimension::Temperature,
+            UnitDimension::Length,
+            UnitDimension::Frequency,
+            UnitDimension::Data,
+            UnitDimension::Energy,
+            UnitDimension::Speed,
+            UnitDimension::FuelEconomy,
+            UnitDimension::Time,
+            UnitDimension::Mass,
+            UnitDimension::Area,
+            UnitDimension::Volume,
+        ]
+    }
+
+    /// Gets the lowercase name of the dimension (e.g., "temperature", "length").
+    ///
+    /// ## Returns
+    ///
+    /// A static string slice with the dimension's name.
+    ///
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UnitDimension::Temperature => return "temperature",
+            UnitDimension::Length => return "length",
+            UnitDimension::Frequency => return "frequency",
+            UnitDimension::Data => return "data",
+            UnitDimension::Energy => return "energy",
+            UnitDimension::Speed => return "speed",
+            UnitDimension::FuelEconomy => return "fuel",
+            UnitDimension::Time => return "time",
+            UnitDimension::Mass => return "mass",
+            UnitDimension::Area => return "area",
+            UnitDimension::Volume => return "volume",
+        }
+    }
+
+    /// Gets an icon for the dimension, shown by `list --emoji`.
+    ///
+    /// ## Returns
+    ///
+    /// A static string slice with a single emoji character.
+    ///
+    pub fn emoji(&self) -> &'static str {
+        match self {
+            UnitDimension::Temperature => return "🌡",
+            UnitDimension::Length => return "📏",
+            UnitDimension::Frequency => return "📶",
+            UnitDimension::Data => return "💾",
+            UnitDimension::Energy => return "⚡",
+            UnitDimension::Speed => return "💨",
+            UnitDimension::FuelEconomy => return "⛽",
+            UnitDimension::Time => return "⏱",
+            UnitDimension::Mass => return "⚖",
+            UnitDimension::Area => return "📐",
+            UnitDimension::Volume => return "🧊",
+        }
+    }
 }
 
 impl Display for UnitDimension {
-    /// Formats the enum into a lowercase string representation ("temperature" or "length").
+    /// Formats the enum into a lowercase string representation (e.g., "temperature", "length").
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A system of units, for `--system`.
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+pub enum UnitSystem {
+    /// The International System of Units (meter, kilogram, second, kelvin).
+    Si,
+    /// The centimeter-gram-second system.
+    Cgs,
+    /// The British imperial system (inch, pound, degree Fahrenheit).
+    Imperial,
+}
+
+impl UnitSystem {
+    /// Gets the name of the system, as accepted by `--system`.
+    ///
+    /// ## Returns
+    ///
+    /// A static string slice with the system's name.
+    ///
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UnitSystem::Si => return "si",
+            UnitSystem::Cgs => return "cgs",
+            UnitSystem::Imperial => return "imperial",
+        }
+    }
+
+    /// Lists the built-in units that belong to the system.
+    ///
+    /// The first unit of each dimension is the system's canonical unit for it.
+    /// Units of time and frequency are shared by all three systems. Area and
+    /// volume units belong to the systems of their length unit.
+    ///
+    /// ## Returns
+    ///
+    /// A `Vec<Unit>` with the member units, canonical units first.
+    ///
+    pub fn units(&self) -> Vec<Unit> {
+        match self {
+            UnitSystem::Si => {
+                return vec![
+                    Unit::Kelvin,
+                    Unit::Celsius,
+                    Unit::Meter,
+                    Unit::Nanometer,
+                    Unit::Millimeter,
+                    Unit::Centimeter,
+                    Unit::Kilometer,
+                    Unit::Hertz,
+                    Unit::Kilohertz,
+                    Unit::Megahertz,
+                    Unit::Gigahertz,
+                    Unit::Terahertz,
+                    Unit::Joule,
+                    Unit::Kilojoule,
+                    Unit::NewtonMeter,
+                    Unit::MeterPerSecond,
+                    Unit::Second,
+                    Unit::Millisecond,
+                    Unit::Kilogram,
+                    Unit::Milligram,
+                    Unit::Gram,
+                ];
+            }
+            UnitSystem::Cgs => {
+                return vec![
+                    Unit::Kelvin,
+                    Unit::Centimeter,
+                    Unit::Hertz,
+                    Unit::Second,
+                    Unit::Millisecond,
+                    Unit::Gram,
+                    Unit::Milligram,
+                ];
+            }
+            UnitSystem::Imperial => {
+                return vec![
+                    Unit::Fahrenheit,
+                    Unit::Inch,
+                    Unit::Foot,
+                    Unit::Mile,
+                    Unit::Hertz,
+                    Unit::MilePerHour,
+                    Unit::MilePerImperialGallon,
+                    Unit::Second,
+                    Unit::Pound,
+                    Unit::Ounce,
+                ];
+            }
+        }
+    }
+
+    /// Gets the system that values in this system are usually compared with:
+    /// imperial for SI and CGS, and SI for imperial.
+    pub fn counterpart(&self) -> UnitSystem {
         match self {
-            UnitDimension::Temperature => write!(f, "temperature"),
-            UnitDimension::Length => write!(f, "length"),
+            UnitSystem::Si | UnitSystem::Cgs => return UnitSystem::Imperial,
+            UnitSystem::Imperial => return UnitSystem::Si,
+        }
+    }
+
+    /// Gets the canonical unit of the system for a dimension.
+    ///
+    /// ## Arguments
+    ///
+    /// * `dimension` - The dimension of the value to express in this system.
+    ///
+    /// ## Returns
+    ///
+    /// An `Option<Unit>` which is `None` if the system has no unit of that
+    /// dimension (e.g., data sizes, or energy in CGS).
+    ///
+    pub fn canonical_unit(&self, dimension: &UnitDimension) -> Option<Unit> {
+        match dimension {
+            UnitDimension::Area => {
+                let length: Unit = self.canonical_unit(&UnitDimension::Length)?;
+                return Some(Unit::Area(Box::new(length)));
+            }
+            UnitDimension::Volume => {
+                let length: Unit = self.canonical_unit(&UnitDimension::Length)?;
+                return Some(Unit::Volume(Box::new(length)));
+            }
+            _ => {
+                return self
+                    .units()
+                    .into_iter()
+                    .find(|unit| unit.dimension() == *dimension);
+            }
         }
     }
 }
@@ -47,8 +279,64 @@ pub enum Unit {
     Kelvin,
     Centimeter,
     Inch,
+    Foot,
     Kilometer,
     Mile,
+    Nanometer,
+    Millimeter,
+    Meter,
+    Point,
+    Pica,
+    Pixel,
+    Hertz,
+    Kilohertz,
+    Megahertz,
+    Gigahertz,
+    Terahertz,
+    Bit,
+    Byte,
+    Kilobyte,
+    Megabyte,
+    Gigabyte,
+    Terabyte,
+    Petabyte,
+    Kibibyte,
+    Mebibyte,
+    Gibibyte,
+    Tebibyte,
+    Pebibyte,
+    Joule,
+    Kilojoule,
+    NewtonMeter,
+    MeterPerSecond,
+    KilometerPerHour,
+    MilePerHour,
+    Knot,
+    KilometerPerLiter,
+    /// Fuel consumption, the reciprocal of fuel economy (see `Factor::inverse`).
+    LiterPer100Kilometer,
+    /// Miles per US gallon.
+    MilePerGallon,
+    /// Miles per imperial gallon.
+    MilePerImperialGallon,
+    Millisecond,
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Week,
+    Milligram,
+    Gram,
+    Kilogram,
+    Tonne,
+    Ounce,
+    Pound,
+    /// A length unit squared (e.g., m^2).
+    Area(Box<Unit>),
+    /// A length unit cubed (e.g., cm^3).
+    Volume(Box<Unit>),
+    /// A unit loaded from the custom-units file.
+    Custom(CustomId),
 }
 
 impl Display for Unit {
@@ -60,8 +348,58 @@ impl Display for Unit {
             Unit::Kelvin => write!(f, "kelvin"),
             Unit::Centimeter => write!(f, "cm"),
             Unit::Inch => write!(f, "inch"),
+            Unit::Foot => write!(f, "ft"),
             Unit::Kilometer => write!(f, "km"),
             Unit::Mile => write!(f, "miles"),
+            Unit::Nanometer => write!(f, "nm"),
+            Unit::Millimeter => write!(f, "mm"),
+            Unit::Meter => write!(f, "m"),
+            Unit::Point => write!(f, "pt"),
+            Unit::Pica => write!(f, "pc"),
+            Unit::Pixel => write!(f, "px"),
+            Unit::Hertz => write!(f, "hz"),
+            Unit::Kilohertz => write!(f, "khz"),
+            Unit::Megahertz => write!(f, "mhz"),
+            Unit::Gigahertz => write!(f, "ghz"),
+            Unit::Terahertz => write!(f, "thz"),
+            Unit::Bit => write!(f, "bit"),
+            Unit::Byte => write!(f, "byte"),
+            Unit::Kilobyte => write!(f, "kb"),
+            Unit::Megabyte => write!(f, "mb"),
+            Unit::Gigabyte => write!(f, "gb"),
+            Unit::Terabyte => write!(f, "tb"),
+            Unit::Petabyte => write!(f, "pb"),
+            Unit::Kibibyte => write!(f, "kib"),
+            Unit::Mebibyte => write!(f, "mib"),
+            Unit::Gibibyte => write!(f, "gib"),
+            Unit::Tebibyte => write!(f, "tib"),
+            Unit::Pebibyte => write!(f, "pib"),
+            Unit::Joule => write!(f, "j"),
+            Unit::Kilojoule => write!(f, "kj"),
+            Unit::NewtonMeter => write!(f, "n-m"),
+            Unit::MeterPerSecond => write!(f, "m/s"),
+            Unit::KilometerPerHour => write!(f, "km/h"),
+            Unit::MilePerHour => write!(f, "mph"),
+            Unit::Knot => write!(f, "kn"),
+            Unit::KilometerPerLiter => write!(f, "km/l"),
+            Unit::LiterPer100Kilometer => write!(f, "l/100km"),
+            Unit::MilePerGallon => write!(f, "mpg"),
+            Unit::MilePerImperialGallon => write!(f, "mpg-imp"),
+            Unit::Millisecond => write!(f, "ms"),
+            Unit::Second => write!(f, "s"),
+            Unit::Minute => write!(f, "min"),
+            Unit::Hour => write!(f, "h"),
+            Unit::Day => write!(f, "d"),
+            Unit::Week => write!(f, "wk"),
+            Unit::Milligram => write!(f, "mg"),
+            Unit::Gram => write!(f, "g"),
+            Unit::Kilogram => write!(f, "kg"),
+            Unit::Tonne => write!(f, "t"),
+            Unit::Ounce => write!(f, "oz"),
+            Unit::Pound => write!(f, "lb"),
+            Unit::Area(length) => write!(f, "{}^2", length),
+            Unit::Volume(length) => write!(f, "{}^3", length),
+            Unit::Custom(_) => write!(f, "{}", self.custom().name),
         }
     }
 }
@@ -79,8 +417,133 @@ impl Unit {
     pub fn dimension(&self) -> UnitDimension {
         match self {
             Unit::Celsius | Unit::Fahrenheit | Unit::Kelvin => UnitDimension::Temperature,
-            Unit::Centimeter | Unit::Inch | Unit::Kilometer | Unit::Mile => UnitDimension::Length,
+            Unit::Centimeter
+            | Unit::Inch
+            | Unit::Foot
+            | Unit::Kilometer
+            | Unit::Mile
+            | Unit::Nanometer
+            | Unit::Millimeter
+            | Unit::Meter
+            | Unit::Point
+            | Unit::Pica
+            | Unit::Pixel => UnitDimension::Length,
+            Unit::Hertz | Unit::Kilohertz | Unit::Megahertz | Unit::Gigahertz | Unit::Terahertz => {
+                UnitDimension::Frequency
+            }
+            Unit::Bit
+            | Unit::Byte
+            | Unit::Kilobyte
+            | Unit::Megabyte
+            | Unit::Gigabyte
+            | Unit::Terabyte
+            | Unit::Petabyte
+            | Unit::Kibibyte
+            | Unit::Mebibyte
+            | Unit::Gibibyte
+            | Unit::Tebibyte
+            | Unit::Pebibyte => UnitDimension::Data,
+            Unit::Joule | Unit::Kilojoule | Unit::NewtonMeter => UnitDimension::Energy,
+            Unit::MeterPerSecond | Unit::KilometerPerHour | Unit::MilePerHour | Unit::Knot => {
+                UnitDimension::Speed
+            }
+            Unit::KilometerPerLiter
+            | Unit::LiterPer100Kilometer
+            | Unit::MilePerGallon
+            | Unit::MilePerImperialGallon => UnitDimension::FuelEconomy,
+            Unit::Millisecond
+            | Unit::Second
+            | Unit::Minute
+            | Unit::Hour
+            | Unit::Day
+            | Unit::Week => UnitDimension::Time,
+            Unit::Milligram
+            | Unit::Gram
+            | Unit::Kilogram
+            | Unit::Tonne
+            | Unit::Ounce
+            | Unit::Pound => UnitDimension::Mass,
+            Unit::Area(_) => UnitDimension::Area,
+            Unit::Volume(_) => UnitDimension::Volume,
+            Unit::Custom(_) => self.custom().dimension,
+        }
+    }
+
+    /// Gets the kind of quantity the unit measures.
+    ///
+    /// Units of one dimension normally measure the same kind of quantity, which is
+    /// then named after the dimension. Torque is the exception: it shares the
+    /// dimension (and the numeric scale) of energy, but is a different physical
+    /// quantity, so the two must never be converted into each other.
+    ///
+    /// ## Returns
+    ///
+    /// A static string slice with the quantity kind (e.g., "length", "torque").
+    ///
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Unit::NewtonMeter => return "torque",
+            _ => return self.dimension().as_str(),
+        }
+    }
+
+    /// Compares the magnitude of two units of the same quantity.
+    ///
+    /// Units are ordered by their factor relative to the base unit, so that a
+    /// millimeter is less than a centimeter. For temperatures this is the size
+    /// of a degree, without the offset of the scale's zero point.
+    ///
+    /// ## Arguments
+    ///
+    /// * `other` - The `Unit` to compare with.
+    ///
+    /// ## Returns
+    ///
+    /// An `Option<Ordering>` which is `None` if the units measure different
+    /// dimensions or quantity kinds (see `Unit::kind()`).
+    ///
+    pub fn cmp_magnitude(&self, other: &Unit) -> Option<Ordering> {
+        if self.dimension() != other.dimension() || self.kind() != other.kind() {
+            return None;
         }
+        return Some(self.magnitude().total_cmp(&other.magnitude()));
+    }
+
+    /// Gets the size of one unit in the base unit of its dimension, ignoring offsets.
+    fn magnitude(&self) -> f64 {
+        let factor: Factor = unit_factor(self);
+        return factor.numerator / factor.denominator;
+    }
+
+    /// Provides the supported units of a dimension, from the smallest to the largest.
+    ///
+    /// ## Arguments
+    ///
+    /// * `dimension` - The `UnitDimension` to filter by.
+    ///
+    /// ## Returns
+    ///
+    /// A `Vec<Unit>` with the units of `units_in()`, sorted by magnitude (see
+    /// `cmp_magnitude()`); units of the same magnitude keep their order.
+    ///
+    pub fn sorted_units_in(dimension: &UnitDimension) -> Vec<Unit> {
+        let mut units: Vec<Unit> = Self::units_in(dimension);
+        units.sort_by(|a, b| a.magnitude().total_cmp(&b.magnitude()));
+        return units;
+    }
+
+    /// Provides the supported units that this unit can be converted to.
+    ///
+    /// ## Returns
+    ///
+    /// A `Vec<Unit>` with every unit of the same dimension and quantity kind,
+    /// including this unit itself.
+    ///
+    pub fn compatible_units(&self) -> Vec<Unit> {
+        return Self::units_in(&self.dimension())
+            .into_iter()
+            .filter(|unit| unit.kind() == self.kind())
+            .collect();
     }
 
     /// Gets the common symbol for the unit.
@@ -99,49 +562,561 @@ impl Unit {
             Unit::Kelvin => return String::from("K"),
             Unit::Centimeter => return String::from("cm"),
             Unit::Inch => return String::from("inch"),
+            Unit::Foot => return String::from("ft"),
             Unit::Kilometer => return String::from("km"),
             Unit::Mile => return String::from("miles"),
+            Unit::Nanometer => return String::from("nm"),
+            Unit::Millimeter => return String::from("mm"),
+            Unit::Meter => return String::from("m"),
+            Unit::Point => return String::from("pt"),
+            Unit::Pica => return String::from("pc"),
+            Unit::Pixel => return String::from("px"),
+            Unit::Hertz => return String::from("Hz"),
+            Unit::Kilohertz => return String::from("kHz"),
+            Unit::Megahertz => return String::from("MHz"),
+            Unit::Gigahertz => return String::from("GHz"),
+            Unit::Terahertz => return String::from("THz"),
+            Unit::Bit => return String::from("bit"),
+            Unit::Byte => return String::from("B"),
+            Unit::Kilobyte => return String::from("KB"),
+            Unit::Megabyte => return String::from("MB"),
+            Unit::Gigabyte => return String::from("GB"),
+            Unit::Terabyte => return String::from("TB"),
+            Unit::Petabyte => return String::from("PB"),
+            Unit::Kibibyte => return String::from("KiB"),
+            Unit::Mebibyte => return String::from("MiB"),
+            Unit::Gibibyte => return String::from("GiB"),
+            Unit::Tebibyte => return String::from("TiB"),
+            Unit::Pebibyte => return String::from("PiB"),
+            Unit::Joule => return String::from("J"),
+            Unit::Kilojoule => return String::from("kJ"),
+            Unit::NewtonMeter => return String::from("N·m"),
+            Unit::MeterPerSecond => return String::from("m/s"),
+            Unit::KilometerPerHour => return String::from("km/h"),
+            Unit::MilePerHour => return String::from("mph"),
+            Unit::Knot => return String::from("kn"),
+            Unit::KilometerPerLiter => return String::from("km/L"),
+            Unit::LiterPer100Kilometer => return String::from("L/100km"),
+            Unit::MilePerGallon => return String::from("mpg"),
+            Unit::MilePerImperialGallon => return String::from("mpg-imp"),
+            Unit::Millisecond => return String::from("ms"),
+            Unit::Second => return String::from("s"),
+            Unit::Minute => return String::from("min"),
+            Unit::Hour => return String::from("h"),
+            Unit::Day => return String::from("d"),
+            Unit::Week => return String::from("wk"),
+            Unit::Milligram => return String::from("mg"),
+            Unit::Gram => return String::from("g"),
+            Unit::Kilogram => return String::from("kg"),
+            Unit::Tonne => return String::from("t"),
+            Unit::Ounce => return String::from("oz"),
+            Unit::Pound => return String::from("lb"),
+            Unit::Area(length) => return format!("{}²", length.symbol()),
+            Unit::Volume(length) => return format!("{}³", length.symbol()),
+            Unit::Custom(_) => return self.custom().symbol.clone(),
+        }
+    }
+
+    /// Gets the full canonical name of the unit.
+    ///
+    /// This method returns the unit's singular English name, such as "celsius" or
+    /// "kilometer". Canonical names are always accepted by `get_enum()`.
+    ///
+    /// ## Returns
+    ///
+    /// A `String` with the unit's canonical name.
+    ///
+    pub fn canonical_name(&self) -> String {
+        match self {
+            Unit::Celsius => return String::from("celsius"),
+            Unit::Fahrenheit => return String::from("fahrenheit"),
+            Unit::Kelvin => return String::from("kelvin"),
+            Unit::Centimeter => return String::from("centimeter"),
+            Unit::Inch => return String::from("inch"),
+            Unit::Foot => return String::from("foot"),
+            Unit::Kilometer => return String::from("kilometer"),
+            Unit::Mile => return String::from("mile"),
+            Unit::Nanometer => return String::from("nanometer"),
+            Unit::Millimeter => return String::from("millimeter"),
+            Unit::Meter => return String::from("meter"),
+            Unit::Point => return String::from("point"),
+            Unit::Pica => return String::from("pica"),
+            Unit::Pixel => return String::from("pixel"),
+            Unit::Hertz => return String::from("hertz"),
+            Unit::Kilohertz => return String::from("kilohertz"),
+            Unit::Megahertz => return String::from("megahertz"),
+            Unit::Gigahertz => return String::from("gigahertz"),
+            Unit::Terahertz => return String::from("terahertz"),
+            Unit::Bit => return String::from("bit"),
+            Unit::Byte => return String::from("byte"),
+            Unit::Kilobyte => return String::from("kilobyte"),
+            Unit::Megabyte => return String::from("megabyte"),
+            Unit::Gigabyte => return String::from("gigabyte"),
+            Unit::Terabyte => return String::from("terabyte"),
+            Unit::Petabyte => return String::from("petabyte"),
+            Unit::Kibibyte => return String::from("kibibyte"),
+            Unit::Mebibyte => return String::from("mebibyte"),
+            Unit::Gibibyte => return String::from("gibibyte"),
+            Unit::Tebibyte => return String::from("tebibyte"),
+            Unit::Pebibyte => return String::from("pebibyte"),
+            Unit::Joule => return String::from("joule"),
+            Unit::Kilojoule => return String::from("kilojoule"),
+            Unit::NewtonMeter => return String::from("newton-meter"),
+            Unit::MeterPerSecond => return String::from("meter per second"),
+            Unit::KilometerPerHour => return String::from("kilometer per hour"),
+            Unit::MilePerHour => return String::from("mile per hour"),
+            Unit::Knot => return String::from("knot"),
+            Unit::KilometerPerLiter => return String::from("kilometer per liter"),
+            Unit::LiterPer100Kilometer => return String::from("liter per 100 kilometers"),
+            Unit::MilePerGallon => return String::from("mile per gallon"),
+            Unit::MilePerImperialGallon => return String::from("mile per imperial gallon"),
+            Unit::Millisecond => return String::from("millisecond"),
+            Unit::Second => return String::from("second"),
+            Unit::Minute => return String::from("minute"),
+            Unit::Hour => return String::from("hour"),
+            Unit::Day => return String::from("day"),
+            Unit::Week => return String::from("week"),
+            Unit::Milligram => return String::from("milligram"),
+            Unit::Gram => return String::from("gram"),
+            Unit::Kilogram => return String::from("kilogram"),
+            Unit::Tonne => return String::from("tonne"),
+            Unit::Ounce => return String::from("ounce"),
+            Unit::Pound => return String::from("pound"),
+            Unit::Area(length) => return format!("square {}", length.canonical_name()),
+            Unit::Volume(length) => return format!("cubic {}", length.canonical_name()),
+            Unit::Custom(_) => return self.custom().name.clone(),
+        }
+    }
+
+    /// Gets the definition of a custom unit.
+    ///
+    /// ## Returns
+    ///
+    /// The `CustomUnit` that a `Unit::Custom` refers to.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if called on a built-in unit.
+    ///
+    pub fn custom(&self) -> &'static CustomUnit {
+        match self {
+            Unit::Custom(id) => return id.unit(),
+            _ => panic!("'{}' is not a custom unit", self),
         }
     }
 
     /// Provides a list of all supported `Unit` variants.
     ///
     /// This static method returns a fixed-size array containing one instance of every
-    /// unit defined in the `Unit` enum.
+    /// unit defined in the `Unit` enum. Area and volume units are built from length
+    /// units (see `units_in()`) and are not part of this list.
     ///
     /// ## Returns
     ///
     /// An array of all `Unit` variants.
     ///
-    pub fn all_units() -> [Unit; 7] {
+    pub fn all_units() -> [Unit; 54] {
         [
             Unit::Celsius,
             Unit::Fahrenheit,
             Unit::Kelvin,
             Unit::Centimeter,
             Unit::Inch,
+            Unit::Foot,
             Unit::Kilometer,
             Unit::Mile,
+            Unit::Nanometer,
+            Unit::Millimeter,
+            Unit::Meter,
+            Unit::Point,
+            Unit::Pica,
+            Unit::Pixel,
+            Unit::Hertz,
+            Unit::Kilohertz,
+            Unit::Megahertz,
+            Unit::Gigahertz,
+            Unit::Terahertz,
+            Unit::Bit,
+            Unit::Byte,
+            Unit::Kilobyte,
+            Unit::Megabyte,
+            Unit::Gigabyte,
+            Unit::Terabyte,
+            Unit::Petabyte,
+            Unit::Kibibyte,
+            Unit::Mebibyte,
+            Unit::Gibibyte,
+            Unit::Tebibyte,
+            Unit::Pebibyte,
+            Unit::Joule,
+            Unit::Kilojoule,
+            Unit::NewtonMeter,
+            Unit::MeterPerSecond,
+            Unit::KilometerPerHour,
+            Unit::MilePerHour,
+            Unit::Knot,
+            Unit::KilometerPerLiter,
+            Unit::LiterPer100Kilometer,
+            Unit::MilePerGallon,
+            Unit::MilePerImperialGallon,
+            Unit::Millisecond,
+            Unit::Second,
+            Unit::Minute,
+            Unit::Hour,
+            Unit::Day,
+            Unit::Week,
+            Unit::Milligram,
+            Unit::Gram,
+            Unit::Kilogram,
+            Unit::Tonne,
+            Unit::Ounce,
+            Unit::Pound,
         ]
     }
 
+    /// Gets the systems of units the unit belongs to (see `UnitSystem::units()`).
+    ///
+    /// ## Returns
+    ///
+    /// A `Vec<UnitSystem>` which is empty for units outside of every system,
+    /// such as data sizes and custom units.
+    ///
+    pub fn systems(&self) -> Vec<UnitSystem> {
+        let unit: &Unit = match self {
+            Unit::Area(length) | Unit::Volume(length) => length,
+            unit => unit,
+        };
+        return [UnitSystem::Si, UnitSystem::Cgs, UnitSystem::Imperial]
+            .into_iter()
+            .filter(|system| system.units().contains(unit))
+            .collect();
+    }
+
+    /// Gets the unit in which a value of this unit is shown in the other common
+    /// system (see `UnitSystem::counterpart()`), e.g. inch for meter.
+    ///
+    /// ## Returns
+    ///
+    /// An `Option<Unit>` which is `None` if the unit belongs to no system, or the
+    /// counterpart system has no unit of its dimension or uses this same unit
+    /// (as for seconds).
+    ///
+    pub fn counterpart_unit(&self) -> Option<Unit> {
+        let system: UnitSystem = *self.systems().first()?;
+        let unit: Unit = system.counterpart().canonical_unit(&self.dimension())?;
+        if unit == *self {
+            return None;
+        }
+        return Some(unit);
+    }
+
+    /// Finds symbols, canonical names and aliases that are shared by more than one unit.
+    ///
+    /// Two units with the same symbol or canonical name could not be told apart,
+    /// neither in the output nor when parsing. An alias may be shared by units of
+    /// different dimensions (e.g., "m" for meter and minute), since
+    /// `--assume-dimension` picks between them, but not by two units of the same
+    /// dimension, and the symbol and name of a unit must parse back to that unit
+    /// without a dimension. The list is expected to be empty.
+    ///
+    /// ## Returns
+    ///
+    /// A `Vec<String>` describing each clash, such as "symbol 'm' (meter, minute)".
+    ///
+    pub fn duplicate_names() -> Vec<String> {
+        let units: [Unit; 54] = Self::all_units();
+        let mut duplicates: Vec<String> = Vec::new();
+        for (label, name) in [
+            ("symbol", Unit::symbol as fn(&Unit) -> String),
+            ("name", Unit::canonical_name),
+        ] {
+            let mut seen: Vec<String> = Vec::new();
+            for unit in units.iter() {
+                let value: String = name(unit);
+                if seen.contains(&value) {
+                    continue;
+                }
+                let owners: Vec<String> = units
+                    .iter()
+                    .filter(|other| name(other) == value)
+                    .map(|other| other.canonical_name())
+                    .collect();
+                if owners.len() > 1 {
+                    duplicates.push(format!("{} '{}' ({})", label, value, owners.join(", ")));
+                }
+                seen.push(value);
+            }
+        }
+
+        let mut seen: Vec<&str> = Vec::new();
+        for (alias, _) in SYMBOL_ALIASES.iter().chain(WORD_ALIASES.iter()) {
+            if seen.contains(alias) {
+                continue;
+            }
+            seen.push(alias);
+            let mut owners: Vec<&Unit> = Vec::new();
+            for (other, unit) in SYMBOL_ALIASES.iter().chain(WORD_ALIASES.iter()) {
+                if other == alias && !owners.contains(&unit) {
+                    owners.push(unit);
+                }
+            }
+            let clashes: bool = owners.iter().enumerate().any(|(i, unit)| {
+                return owners[i + 1..]
+                    .iter()
+                    .any(|other| other.dimension() == unit.dimension());
+            });
+            if clashes {
+                let names: Vec<String> = owners.iter().map(|unit| unit.canonical_name()).collect();
+                duplicates.push(format!("alias '{}' ({})", alias, names.join(", ")));
+            }
+        }
+
+        for unit in units.iter() {
+            for (label, value) in [("symbol", unit.symbol()), ("name", unit.canonical_name())] {
+                if let Some(parsed) = parse_alias(&value.to_lowercase(), None)
+                    && parsed != *unit
+                {
+                    duplicates.push(format!(
+                        "{} '{}' ({}, {})",
+                        label,
+                        value,
+                        unit.canonical_name(),
+                        parsed.canonical_name()
+                    ));
+                }
+            }
+        }
+
+        return duplicates;
+    }
+
+    /// Provides the supported units belonging to a dimension.
+    ///
+    /// ## Arguments
+    ///
+    /// * `dimension` - The `UnitDimension` to filter by.
+    ///
+    /// ## Returns
+    ///
+    /// A `Vec<Unit>` with every unit of that dimension, in `all_units()` order and
+    /// followed by the custom units. For area and volume, these are the squares and
+    /// cubes of the length units, followed by the custom area or volume units.
+    ///
+    pub fn units_in(dimension: &UnitDimension) -> Vec<Unit> {
+        let lengths = || Self::units_in(&UnitDimension::Length).into_iter();
+        let mut units: Vec<Unit> = match dimension {
+            UnitDimension::Area => lengths().map(|l| Unit::Area(Box::new(l))).collect(),
+            UnitDimension::Volume => lengths().map(|l| Unit::Volume(Box::new(l))).collect(),
+            _ => Self::all_units()
+                .into_iter()
+                .filter(|unit| unit.dimension() == *dimension)
+                .collect(),
+        };
+        units.extend(
+            Self::custom_units()
+                .into_iter()
+                .filter(|unit| unit.dimension() == *dimension),
+        );
+        return units;
+    }
+
+    /// Provides the units loaded from the custom-units file.
+    ///
+    /// ## Returns
+    ///
+    /// A `Vec<Unit>` with one `Unit::Custom` per loaded unit, in file order.
+    ///
+    pub fn custom_units() -> Vec<Unit> {
+        return custom_units::units();
+    }
+
     /// Prints a formatted list of all supported units to the console.
     ///
-    /// This static method iterates over all available units, printing each one's name
-    /// and dimension in a human-readable, numbered list to standard output.
+    /// This static method iterates over all available units, printing each one's
+    /// dimension, canonical name, and symbol in a human-readable, numbered list to
+    /// standard output. The columns are aligned to the longest entry.
+    ///
+    /// ## Arguments
     ///
-    pub fn print() {
+    /// * `emoji` - Whether to prefix each dimension with its icon (see `UnitDimension::emoji()`).
+    ///   Icons are left out when the output is restricted to ASCII.
+    ///
+    pub fn print(emoji: bool) {
+        let mut units: Vec<Unit> = Self::all_units().to_vec();
+        units.extend(Self::custom_units());
+        let number_width: usize = units.len().to_string().len();
+        let dimension_width: usize = units
+            .iter()
+            .map(|unit| unit.kind().len() + 2)
+            .max()
+            .unwrap_or(0);
+        let name_width: usize = units
+            .iter()
+            .map(|unit| unit.canonical_name().chars().count())
+            .max()
+            .unwrap_or(0);
+
         println!("Supported units:");
-        for (i, unit) in Self::all_units().iter().enumerate() {
-            println!("{}. [{}] {}", i + 1, unit.dimension(), unit.to_string());
+        for (i, unit) in units.iter().enumerate() {
+            let icon: String = match emoji && !locale::ascii_only() {
+                true => format!("{} ", unit.dimension().emoji()),
+                false => String::new(),
+            };
+            let symbol: String = match locale::ascii_only() {
+                true => locale::to_ascii(&unit.symbol()),
+                false => unit.symbol(),
+            };
+            println!(
+                "{:>number_width$}. {}{:<dimension_width$} {:<name_width$} ({})",
+                i + 1,
+                icon,
+                format!("[{}]", unit.kind()),
+                unit.canonical_name(),
+                symbol
+            );
         }
+        println!("\nArea and volume: append ^2 or ^3 to any length unit (e.g., m^2, cm^3).");
+    }
+
+    /// Gets every string accepted as this unit, symbols first, then full names.
+    ///
+    /// Built-in units take their aliases from the tables `get_enum()` parses
+    /// with; custom units accept their symbol and their name.
+    ///
+    /// ## Returns
+    ///
+    /// A `Vec<String>` with the aliases in lowercase (as written for custom units).
+    ///
+    pub fn aliases(&self) -> Vec<String> {
+        if let Unit::Custom(_) = self {
+            let custom: &CustomUnit = self.custom();
+            let mut aliases: Vec<String> = vec![custom.symbol.clone()];
+            if custom.name != custom.symbol {
+                aliases.push(custom.name.clone());
+            }
+            return aliases;
+        }
+
+        return SYMBOL_ALIASES
+            .iter()
+            .chain(WORD_ALIASES.iter())
+            .filter(|(_, unit)| unit == self)
+            .map(|(alias, _)| String::from(*alias))
+            .collect();
+    }
+
+    /// Prints every supported unit with all the strings accepted for it.
+    ///
+    /// Each line holds the unit's canonical name followed by its aliases (see
+    /// `aliases()`), which makes the flexible parsing of unit arguments discoverable.
+    ///
+    pub fn print_aliases() {
+        let mut units: Vec<Unit> = Self::all_units().to_vec();
+        units.extend(Self::custom_units());
+        let number_width: usize = units.len().to_string().len();
+        let name_width: usize = units
+            .iter()
+            .map(|unit| unit.canonical_name().chars().count())
+            .max()
+            .unwrap_or(0);
+
+        println!("Accepted unit names (case-insensitive):");
+        for (i, unit) in units.iter().enumerate() {
+            println!(
+                "{:>number_width$}. {:<name_width$}  {}",
+                i + 1,
+                unit.canonical_name(),
+                unit.aliases().join(", ")
+            );
+        }
+        println!("\nArea and volume: append ^2 or ^3 to any length unit (e.g., m^2, cm^3).");
+    }
+}
+
+/// Splits a unit string with an exponent into its base unit and exponent.
+///
+/// Recognizes "m^2", "m²", "m³", "square m", and "cubic m" style input.
+///
+/// ## Arguments
+///
+/// * `unit` - The lowercased unit string.
+///
+/// ## Returns
+///
+/// An `Option` with the base unit and the exponent, or `None` if the string has no exponent.
+///
+fn split_exponent(unit: &str) -> Option<(&str, &str)> {
+    if let Some((base, exponent)) = unit.split_once('^') {
+        return Some((base.trim(), exponent.trim()));
+    }
+    if let Some(base) = unit.strip_suffix('²') {
+        return Some((base, "2"));
+    }
+    if let Some(base) = unit.strip_suffix('³') {
+        return Some((base, "3"));
+    }
+    if let Some(base) = unit.strip_prefix("square ") {
+        return Some((base, "2"));
+    }
+    if let Some(base) = unit.strip_prefix("cubic ") {
+        return Some((base, "3"));
+    }
+    return None;
+}
+
+/// Parses a length unit raised to a power into an area or volume `Unit`.
+///
+/// ## Arguments
+///
+/// * `unit` - The original unit string, for error messages.
+/// * `base` - The base unit part of the string.
+/// * `exponent` - The exponent part of the string.
+/// * `unit_type` - The `UnitType` (Source/Target) for creating a specific error message.
+/// * `options` - The `ParseOptions` used to parse the base unit.
+///
+/// ## Returns
+///
+/// An `anyhow::Result<Unit>` containing an `Area` or `Volume` unit on success, or an
+/// error if the base is not a length unit or the exponent is not 2 or 3.
+///
+fn get_power_enum(
+    unit: &str,
+    base: &str,
+    exponent: &str,
+    unit_type: UnitType,
+    options: &ParseOptions,
+) -> Result<Unit> {
+    // Only length units take a power, so a nested one ("square square m") can be
+    // rejected before parsing, which also bounds the recursion on long input.
+    if split_exponent(base).is_some() {
+        bail!(format!(
+            "Error: [ERROR] {} unit '{}' raises a unit to a power more than once.",
+            unit_type, unit
+        ));
+    }
+    let base: Unit = get_enum_with(base.to_string(), unit_type, options)?;
+    if base.dimension() != UnitDimension::Length {
+        bail!(format!(
+            "Error: [ERROR] {} unit '{}' raises a non-length unit to a power.",
+            unit_type, unit
+        ));
+    }
+
+    match exponent {
+        "2" => return Ok(Unit::Area(Box::new(base))),
+        "3" => return Ok(Unit::Volume(Box::new(base))),
+        _ => bail!(format!(
+            "Error: [ERROR] {} unit '{}' has an unsupported exponent (use 2 for area or 3 for volume).",
+            unit_type, unit
+        )),
     }
 }
 
 /// Parses a string into a `Unit` enum.
 ///
-/// The matching is case-insensitive. If the string does not match any known unit,
-/// an error is returned.
+/// The matching is case-insensitive. A length unit followed by an exponent
+/// (e.g., "m^2", "cm^3") is parsed into an area or volume unit. If the string does
+/// not match any known unit, an error is returned.
 ///
 /// ## Arguments
 ///
@@ -154,18 +1129,755 @@ impl Unit {
 /// or an error if the unit is not recognized.
 ///
 pub fn get_enum(unit: String, unit_type: UnitType) -> Result<Unit> {
-    match unit.to_lowercase().as_str() {
-        "celsius" => return Ok(Unit::Celsius),
-        "fahrenheit" => return Ok(Unit::Fahrenheit),
-        "kelvin" => return Ok(Unit::Kelvin),
-        "cm" => return Ok(Unit::Centimeter),
-        "inch" => return Ok(Unit::Inch),
-        "km" => return Ok(Unit::Kilometer),
-        "miles" => return Ok(Unit::Mile),
-        _ => bail!(format!(
-            "Error: [ERROR] {} unit '{}' not recognized.",
-            unit_type.to_string(),
-            unit
-        )),
+    return get_enum_with(unit, unit_type, &ParseOptions::default());
+}
+
+/// Parses a string into a `Unit` enum using the given `ParseOptions`.
+///
+/// This behaves like `get_enum()`, except that with `strict_case` the string must use
+/// the casing in which units are printed (see `canonical_spelling()`). A string
+/// that only differs in casing is rejected with a hint showing the expected spelling.
+/// With `symbols_only`, full unit names are rejected with a hint showing the unit's
+/// symbol. Surrounding whitespace is ignored, and an empty string is reported as a
+/// missing unit.
+///
+/// ## Arguments
+///
+/// * `unit` - The `String` to parse.
+/// * `unit_type` - The `UnitType` (Source/Target) for creating a specific error message.
+/// * `options` - The `ParseOptions` to apply.
+///
+/// ## Returns
+///
+/// An `anyhow::Result<Unit>` containing the corresponding `Unit` variant on success,
+/// or an error if the unit is not recognized or is incorrectly cased.
+///
+pub fn get_enum_with(unit: String, unit_type: UnitType, options: &ParseOptions) -> Result<Unit> {
+    // An empty argument usually comes from an unset shell variable.
+    let unit: String = String::from(unit.trim());
+    if unit.is_empty() {
+        bail!(format!(
+            "Error: [ERROR] No {} unit provided.",
+            unit_type.to_string().to_lowercase()
+        ));
+    }
+    let lowered: String = unit.to_lowercase();
+    if options.strict_case
+        && get_enum(lowered.clone(), unit_type).is_ok()
+        && let Some(spelling) = canonical_spelling(&unit)
+    {
+        bail!(format!(
+            "Error: [ERROR] {} unit '{}' does not use the expected casing (--strict-case). Did you mean '{}'?",
+            unit_type, unit, spelling
+        ));
+    }
+    if let Some((base, exponent)) = split_exponent(&lowered) {
+        if options.symbols_only && (lowered.starts_with("square ") || lowered.starts_with("cubic "))
+        {
+            let symbol: String = match parse_symbol(base).or_else(|| parse_word(base)) {
+                Some(base) => base.symbol(),
+                None => base.to_string(),
+            };
+            bail!(format!(
+                "Error: [ERROR] {} unit '{}' is a full word, but --symbols-only requires a symbol (e.g., '{}^{}').",
+                unit_type, unit, symbol, exponent
+            ));
+        }
+        // The base of a power is a length, whatever dimension is assumed. Its
+        // casing was already checked as part of the whole string.
+        let base_options: ParseOptions = ParseOptions {
+            strict_case: false,
+            assumed_dimension: options.assumed_dimension.map(|_| UnitDimension::Length),
+            ..*options
+        };
+        let power: Unit = get_power_enum(&unit, base, exponent, unit_type, &base_options)?;
+        if let Some(dimension) = options.assumed_dimension
+            && power.dimension() != dimension
+        {
+            bail!(not_in_dimension(&unit, &power, dimension, unit_type));
+        }
+        return Ok(power);
+    }
+
+    if options.symbols_only
+        && let Some(word) = parse_word(&lowered)
+    {
+        bail!(format!(
+            "Error: [ERROR] {} unit '{}' is a full word, but --symbols-only requires a symbol (e.g., '{}').",
+            unit_type,
+            unit,
+            word.symbol()
+        ));
+    }
+
+    if let Some(unit) = parse_alias(&lowered, options.assumed_dimension) {
+        return Ok(unit);
+    }
+    if let Some(dimension) = options.assumed_dimension
+        && let Some(other) = parse_alias(&lowered, None)
+    {
+        bail!(not_in_dimension(&unit, &other, dimension, unit_type));
+    }
+    let suggestion: Option<(String, Unit)> = suggest(&lowered, options.recent, options.dimension);
+    let mut message: String = format!(
+        "Error: [ERROR] {} unit '{}' not recognized.",
+        unit_type, unit
+    );
+    if let Some((name, _)) = &suggestion {
+        message.push_str(&format!(" Did you mean '{}'?", name));
+    }
+    // Without a close match of the expected dimension, list that dimension's units.
+    if let Some(dimension) = options.dimension
+        && suggestion.is_none_or(|(_, unit)| unit.dimension() != dimension)
+    {
+        message.push(' ');
+        message.push_str(&dimension_hint(&dimension));
+    }
+    bail!(message);
+}
+
+/// Formats the error for a unit outside of the dimension given by `--assume-dimension`.
+fn not_in_dimension(
+    unit: &str,
+    found: &Unit,
+    dimension: UnitDimension,
+    unit_type: UnitType,
+) -> String {
+    return format!(
+        "Error: [ERROR] {} unit '{}' is not a [{}] unit (--assume-dimension), but [{}] {}. {}",
+        unit_type,
+        unit,
+        dimension,
+        found.dimension(),
+        found.canonical_name(),
+        dimension_hint(&dimension)
+    );
+}
+
+/// Explains why two units cannot be converted and what could be meant instead.
+///
+/// This combines the dimension mismatch with a typo suggestion: if one of the
+/// target's names or symbols is a small edit away from that of a unit of the
+/// source's dimension (e.g., "min" for "mi"), that unit is suggested. The units
+/// of the source's dimension are listed as well.
+///
+/// ## Arguments
+///
+/// * `from` - The source `Unit`.
+/// * `to` - The target `Unit`, of a different dimension.
+/// * `recent` - Recently used unit names, most recent first.
+///
+/// ## Returns
+///
+/// The error message as a `String`.
+///
+pub fn mismatch_message(from: &Unit, to: &Unit, recent: &[String]) -> String {
+    let mut message: String = format!(
+        "Error: [ERROR] Cannot convert between different unit categories: [{}] {} → [{}] {}.",
+        from.dimension(),
+        from,
+        to.dimension(),
+        to
+    );
+    let names: Vec<(String, Unit)> = Unit::units_in(&from.dimension())
+        .into_iter()
+        .flat_map(|unit| {
+            unit.aliases()
+                .into_iter()
+                .map(move |alias| (alias, unit.clone()))
+        })
+        .collect();
+    let suggestion: Option<(String, Unit)> = to
+        .aliases()
+        .iter()
+        .find_map(|alias| closest(alias, recent, None, names.clone()));
+    if let Some((name, _)) = suggestion {
+        message.push_str(&format!(" Did you mean '{}'?", name));
+    }
+    message.push(' ');
+    message.push_str(&dimension_hint(&from.dimension()));
+    // A source symbol shared with the target's dimension was probably meant as that.
+    if let Some(alias) = from
+        .aliases()
+        .into_iter()
+        .find(|alias| parse_alias(alias, Some(to.dimension())).is_some())
+    {
+        message.push_str(&format!(
+            " '{}' is also a [{}] unit; add --assume-dimension {} to read it as one.",
+            alias,
+            to.dimension(),
+            to.dimension()
+        ));
+    }
+
+    return message;
+}
+
+/// Lists the units of a dimension, e.g. "[time] units: ms, s, min, h, d, wk.".
+fn dimension_hint(dimension: &UnitDimension) -> String {
+    let symbols: Vec<String> = Unit::units_in(dimension)
+        .iter()
+        .map(|unit| unit.to_string())
+        .collect();
+    return format!("[{}] units: {}.", dimension, symbols.join(", "));
+}
+
+/// Finds the expected casing of a unit string for `--strict-case`.
+///
+/// A unit is spelled as expected if it is written exactly like a symbol or full
+/// name that the tool prints (e.g., "°C", "MHz", "KiB", "kilometer"), or like one
+/// of the lowercase aliases that is not just a lowercased symbol (e.g., "c" or
+/// "miles", but not "mhz"). The base of an area or volume is checked the same
+/// way, and its power must be written in lowercase ("KM^2" becomes "km^2").
+///
+/// ## Arguments
+///
+/// * `unit` - The trimmed unit string, which must be a known unit when lowercased.
+///
+/// ## Returns
+///
+/// An `Option<String>` with the expected spelling, or `None` if `unit` already
+/// uses it.
+///
+fn canonical_spelling(unit: &str) -> Option<String> {
+    let lowered: String = unit.to_lowercase();
+    if lowered.len() == unit.len()
+        && let Some((base, _)) = split_exponent(&lowered)
+    {
+        let start: usize = base.as_ptr() as usize - lowered.as_ptr() as usize;
+        let end: usize = start + base.len();
+        let base: String = canonical_spelling(&unit[start..end])
+            .unwrap_or_else(|| String::from(&unit[start..end]));
+        let spelling: String = format!("{}{}{}", &lowered[..start], base, &lowered[end..]);
+        return (spelling != unit).then_some(spelling);
+    }
+
+    let mut units: Vec<Unit> = Unit::all_units().to_vec();
+    units.extend(custom_units::units());
+    let printed: Vec<String> = units
+        .iter()
+        .flat_map(|known| [known.symbol(), known.canonical_name()])
+        .filter(|spelling| spelling.to_lowercase() == lowered)
+        .collect();
+    if printed.iter().any(|spelling| spelling == unit) {
+        return None;
+    }
+    match printed.into_iter().next() {
+        Some(spelling) => return Some(spelling),
+        None => return (lowered != unit).then_some(lowered),
+    }
+}
+
+/// Suggests a known unit name for a misspelled one.
+///
+/// Every short and full unit name within a small edit distance of the input is a
+/// candidate. Names of units in `dimension` are preferred over all others, then
+/// recently used names (the most recent first); otherwise the closest name wins.
+///
+/// ## Arguments
+///
+/// * `unit` - The unrecognized, lowercased unit string.
+/// * `recent` - Recently used unit names, most recent first.
+/// * `dimension` - The dimension whose units are preferred, if any.
+///
+/// ## Returns
+///
+/// An `Option<(String, Unit)>` with the suggested name and its unit, or `None` if
+/// no name is close enough.
+///
+fn suggest(
+    unit: &str,
+    recent: &[String],
+    dimension: Option<UnitDimension>,
+) -> Option<(String, Unit)> {
+    let mut names: Vec<(String, Unit)> = Vec::new();
+    for known in Unit::all_units().into_iter().chain(Unit::custom_units()) {
+        names.push((known.to_string(), known.clone()));
+        names.push((known.canonical_name(), known));
+    }
+
+    return closest(unit, recent, dimension, names);
+}
+
+/// Finds the name closest to a misspelled unit among the given candidates.
+///
+/// Names that are too far from `unit` are skipped; the others are ranked as
+/// described in `suggest()`.
+///
+/// ## Returns
+///
+/// An `Option<(String, Unit)>` with the closest name and its unit, or `None`.
+///
+fn closest(
+    unit: &str,
+    recent: &[String],
+    dimension: Option<UnitDimension>,
+    names: Vec<(String, Unit)>,
+) -> Option<(String, Unit)> {
+    let max_distance: usize = match unit.chars().count() {
+        0..=2 => return None,
+        3..=5 => 1,
+        _ => 2,
+    };
+
+    return names
+        .into_iter()
+        .filter_map(|(name, known)| {
+            let distance: usize = edit_distance(unit, &name);
+            if distance > max_distance {
+                return None;
+            }
+            let other_dimension: bool = dimension.is_some_and(|d| known.dimension() != d);
+            let rank: usize = match recent.iter().position(|used| *used == name) {
+                Some(position) => position,
+                None => recent.len(),
+            };
+            return Some(((other_dimension, rank, distance), (name, known)));
+        })
+        .min_by_key(|(key, _)| *key)
+        .map(|(_, suggestion)| suggestion);
+}
+
+/// Computes the Levenshtein distance (single-character edits) between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current: Vec<usize> = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution: usize = previous[j] + usize::from(a_char != *b_char);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+
+    return previous[b.len()];
+}
+
+/// The lowercase symbols and abbreviations of the built-in units, as (alias, unit).
+const SYMBOL_ALIASES: &[(&str, Unit)] = &[
+    ("°c", Unit::Celsius),
+    ("c", Unit::Celsius),
+    ("°f", Unit::Fahrenheit),
+    ("f", Unit::Fahrenheit),
+    ("k", Unit::Kelvin),
+    ("cm", Unit::Centimeter),
+    ("in", Unit::Inch),
+    ("ft", Unit::Foot),
+    ("km", Unit::Kilometer),
+    ("mi", Unit::Mile),
+    ("nm", Unit::Nanometer),
+    ("mm", Unit::Millimeter),
+    ("m", Unit::Meter),
+    ("pt", Unit::Point),
+    ("pc", Unit::Pica),
+    ("px", Unit::Pixel),
+    ("hz", Unit::Hertz),
+    ("khz", Unit::Kilohertz),
+    ("mhz", Unit::Megahertz),
+    ("ghz", Unit::Gigahertz),
+    ("thz", Unit::Terahertz),
+    ("bit", Unit::Bit),
+    ("b", Unit::Byte),
+    ("kb", Unit::Kilobyte),
+    ("mb", Unit::Megabyte),
+    ("gb", Unit::Gigabyte),
+    ("tb", Unit::Terabyte),
+    ("pb", Unit::Petabyte),
+    ("kib", Unit::Kibibyte),
+    ("mib", Unit::Mebibyte),
+    ("gib", Unit::Gibibyte),
+    ("tib", Unit::Tebibyte),
+    ("pib", Unit::Pebibyte),
+    ("j", Unit::Joule),
+    ("kj", Unit::Kilojoule),
+    ("n-m", Unit::NewtonMeter),
+    ("n·m", Unit::NewtonMeter),
+    ("m/s", Unit::MeterPerSecond),
+    ("km/h", Unit::KilometerPerHour),
+    ("kph", Unit::KilometerPerHour),
+    ("mph", Unit::MilePerHour),
+    ("kn", Unit::Knot),
+    ("kt", Unit::Knot),
+    ("km/l", Unit::KilometerPerLiter),
+    ("kmpl", Unit::KilometerPerLiter),
+    ("l/100km", Unit::LiterPer100Kilometer),
+    ("l/100 km", Unit::LiterPer100Kilometer),
+    ("mpg", Unit::MilePerGallon),
+    ("mpg-us", Unit::MilePerGallon),
+    ("mpg-imp", Unit::MilePerImperialGallon),
+    ("mpg-uk", Unit::MilePerImperialGallon),
+    ("ms", Unit::Millisecond),
+    ("s", Unit::Second),
+    ("sec", Unit::Second),
+    ("min", Unit::Minute),
+    // Shared with the meter, which it only stands for under --assume-dimension time.
+    ("m", Unit::Minute),
+    ("h", Unit::Hour),
+    ("hr", Unit::Hour),
+    ("d", Unit::Day),
+    ("wk", Unit::Week),
+    ("mg", Unit::Milligram),
+    ("g", Unit::Gram),
+    ("kg", Unit::Kilogram),
+    ("t", Unit::Tonne),
+    ("oz", Unit::Ounce),
+    ("lb", Unit::Pound),
+    ("lbs", Unit::Pound),
+];
+
+/// The lowercase full names of the built-in units, including plurals, as (alias, unit).
+const WORD_ALIASES: &[(&str, Unit)] = &[
+    ("celsius", Unit::Celsius),
+    ("centigrade", Unit::Celsius),
+    ("fahrenheit", Unit::Fahrenheit),
+    ("kelvin", Unit::Kelvin),
+    ("centimeter", Unit::Centimeter),
+    ("inch", Unit::Inch),
+    ("inches", Unit::Inch),
+    ("foot", Unit::Foot),
+    ("feet", Unit::Foot),
+    ("kilometer", Unit::Kilometer),
+    ("mile", Unit::Mile),
+    ("miles", Unit::Mile),
+    ("nanometer", Unit::Nanometer),
+    ("millimeter", Unit::Millimeter),
+    ("meter", Unit::Meter),
+    ("point", Unit::Point),
+    ("pica", Unit::Pica),
+    ("pixel", Unit::Pixel),
+    ("hertz", Unit::Hertz),
+    ("kilohertz", Unit::Kilohertz),
+    ("megahertz", Unit::Megahertz),
+    ("gigahertz", Unit::Gigahertz),
+    ("terahertz", Unit::Terahertz),
+    ("byte", Unit::Byte),
+    ("kilobyte", Unit::Kilobyte),
+    ("megabyte", Unit::Megabyte),
+    ("gigabyte", Unit::Gigabyte),
+    ("terabyte", Unit::Terabyte),
+    ("petabyte", Unit::Petabyte),
+    ("kibibyte", Unit::Kibibyte),
+    ("mebibyte", Unit::Mebibyte),
+    ("gibibyte", Unit::Gibibyte),
+    ("tebibyte", Unit::Tebibyte),
+    ("pebibyte", Unit::Pebibyte),
+    ("joule", Unit::Joule),
+    ("kilojoule", Unit::Kilojoule),
+    ("meter per second", Unit::MeterPerSecond),
+    ("meters per second", Unit::MeterPerSecond),
+    ("kilometer per hour", Unit::KilometerPerHour),
+    ("kilometers per hour", Unit::KilometerPerHour),
+    ("mile per hour", Unit::MilePerHour),
+    ("miles per hour", Unit::MilePerHour),
+    ("knot", Unit::Knot),
+    ("knots", Unit::Knot),
+    ("kilometer per liter", Unit::KilometerPerLiter),
+    ("kilometers per liter", Unit::KilometerPerLiter),
+    ("liter per 100 kilometers", Unit::LiterPer100Kilometer),
+    ("liters per 100 kilometers", Unit::LiterPer100Kilometer),
+    ("mile per gallon", Unit::MilePerGallon),
+    ("miles per gallon", Unit::MilePerGallon),
+    ("mile per imperial gallon", Unit::MilePerImperialGallon),
+    ("miles per imperial gallon", Unit::MilePerImperialGallon),
+    ("millisecond", Unit::Millisecond),
+    ("milliseconds", Unit::Millisecond),
+    ("second", Unit::Second),
+    ("seconds", Unit::Second),
+    ("minute", Unit::Minute),
+    ("minutes", Unit::Minute),
+    ("hour", Unit::Hour),
+    ("hours", Unit::Hour),
+    ("day", Unit::Day),
+    ("days", Unit::Day),
+    ("week", Unit::Week),
+    ("weeks", Unit::Week),
+    ("milligram", Unit::Milligram),
+    ("milligrams", Unit::Milligram),
+    ("gram", Unit::Gram),
+    ("grams", Unit::Gram),
+    ("kilogram", Unit::Kilogram),
+    ("kilograms", Unit::Kilogram),
+    ("tonne", Unit::Tonne),
+    ("tonnes", Unit::Tonne),
+    ("ounce", Unit::Ounce),
+    ("ounces", Unit::Ounce),
+    ("pound", Unit::Pound),
+    ("pounds", Unit::Pound),
+    ("newton-meter", Unit::NewtonMeter),
+];
+
+/// Matches a lowercase unit symbol or abbreviation (e.g., "km", "°c", "c", "kib").
+///
+/// ## Returns
+///
+/// An `Option<Unit>` which is `None` if the string is not a known symbol.
+///
+fn parse_symbol(unit: &str) -> Option<Unit> {
+    match SYMBOL_ALIASES.iter().find(|(alias, _)| *alias == unit) {
+        Some((_, known)) => return Some(known.clone()),
+        None => return custom_units::find_symbol(unit),
+    }
+}
+
+/// Matches a lowercase unit symbol or full name, optionally within a dimension.
+///
+/// Symbols are tried before full names, and built-in units before custom ones.
+/// A string that several units share (e.g., "m" for meter and minute) resolves
+/// to the first of them, or to the first one in `dimension` if it is given.
+///
+/// ## Returns
+///
+/// An `Option<Unit>` which is `None` if no unit (of `dimension`) matches.
+///
+fn parse_alias(unit: &str, dimension: Option<UnitDimension>) -> Option<Unit> {
+    let matching = |aliases: &'static [(&str, Unit)]| {
+        return aliases
+            .iter()
+            .filter(move |(alias, _)| *alias == unit)
+            .map(|(_, known)| known.clone());
+    };
+    return matching(SYMBOL_ALIASES)
+        .chain(custom_units::find_symbol(unit))
+        .chain(matching(WORD_ALIASES))
+        .chain(custom_units::find_name(unit))
+        .find(|known| dimension.is_none_or(|d| known.dimension() == d));
+}
+
+/// Matches a lowercase full unit name (e.g., "kilometer", "celsius", "miles").
+///
+/// ## Returns
+///
+/// An `Option<Unit>` which is `None` if the string is not a known unit name.
+///
+fn parse_word(unit: &str) -> Option<Unit> {
+    match WORD_ALIASES.iter().find(|(alias, _)| *alias == unit) {
+        Some((_, known)) => return Some(known.clone()),
+        None => return custom_units::find_name(unit),
+    }
+}
+
+/// Parses a comma-separated list of units into `Unit` enums.
+///
+/// Segments are trimmed and empty segments (e.g., from a trailing comma) are
+/// dropped, so a plain single unit such as "celsius" parses as a one-element list.
+/// Segments that are not recognized are skipped and returned as warnings, for the
+/// caller to report; an error is returned only if no segment yields a valid unit.
+///
+/// ## Arguments
+///
+/// * `units` - The comma-separated `String` to parse.
+/// * `unit_type` - The `UnitType` (Source/Target) for creating a specific error message.
+/// * `options` - The `ParseOptions` applied to each segment.
+///
+/// ## Returns
+///
+/// An `anyhow::Result<(Vec<Unit>, Vec<String>)>` containing the recognized units in
+/// order and a warning for each skipped segment on success, or an error if none of
+/// the segments is a valid unit.
+///
+pub fn get_enums(
+    units: String,
+    unit_type: UnitType,
+    options: &ParseOptions,
+) -> Result<(Vec<Unit>, Vec<String>)> {
+    let segments: Vec<&str> = units
+        .split(',')
+        .map(|segment| segment.trim())
+        .filter(|segment| !segment.is_empty())
+        .collect();
+    if segments.is_empty() {
+        bail!(format!(
+            "Error: [ERROR] No {} unit provided.",
+            unit_type.to_string().to_lowercase()
+        ));
+    }
+
+    let mut parsed: Vec<Unit> = Vec::new();
+    let mut errors: Vec<anyhow::Error> = Vec::new();
+    for segment in segments.iter() {
+        match get_enum_with(segment.to_string(), unit_type, options) {
+            Ok(unit) => parsed.push(unit),
+            Err(e) => errors.push(e),
+        }
+    }
+
+    if parsed.is_empty() {
+        let messages: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+        bail!(messages.join("\n"));
+    }
+    let warnings: Vec<String> = errors
+        .iter()
+        .map(|e| {
+            let message: String = e.to_string();
+            let message: &str = message.strip_prefix("Error: [ERROR] ").unwrap_or(&message);
+            return format!("Warning: [WARNING] {} It is skipped.", message);
+        })
+        .collect();
+
+    return Ok((parsed, warnings));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn symbols_names_and_aliases_are_unique() {
+        assert!(
+            Unit::duplicate_names().is_empty(),
+            "{:?}",
+            Unit::duplicate_names()
+        );
+    }
+
+    #[test]
+    fn shared_alias_resolves_by_assumed_dimension() {
+        let time: ParseOptions = ParseOptions {
+            assumed_dimension: Some(UnitDimension::Time),
+            ..ParseOptions::default()
+        };
+        assert!(get_enum(String::from("m"), UnitType::Source).unwrap() == Unit::Meter);
+        assert!(get_enum_with(String::from("m"), UnitType::Source, &time).unwrap() == Unit::Minute);
+    }
+
+    #[test]
+    fn unit_lists_drop_empty_segments() {
+        let options: ParseOptions = ParseOptions::default();
+        let parse = |units: &str| {
+            return get_enums(String::from(units), UnitType::Target, &options)
+                .map(|(units, _)| units);
+        };
+        assert!(parse("celsius").unwrap() == [Unit::Celsius]);
+        assert!(parse("celsius,").unwrap() == [Unit::Celsius]);
+        assert!(parse(" celsius , kelvin ").unwrap() == [Unit::Celsius, Unit::Kelvin]);
+        assert!(parse(",,").is_err());
+
+        let (units, warnings): (Vec<Unit>, Vec<String>) =
+            get_enums(String::from("celsius,xx"), UnitType::Target, &options).unwrap();
+        assert!(units == [Unit::Celsius]);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with("Warning: [WARNING] Target unit 'xx' not recognized."));
+    }
+
+    #[test]
+    fn exponents_parse_to_area_and_volume_units() {
+        let area: Unit = get_enum(String::from("m^2"), UnitType::Source).unwrap();
+        assert!(area == Unit::Area(Box::new(Unit::Meter)));
+        assert!(area.dimension() == UnitDimension::Area);
+        let volume: Unit = get_enum(String::from("cm^3"), UnitType::Source).unwrap();
+        assert!(volume == Unit::Volume(Box::new(Unit::Centimeter)));
+        assert!(volume.dimension() == UnitDimension::Volume);
+
+        let square_km: Unit = get_enum(String::from("km^2"), UnitType::Target).unwrap();
+        assert_eq!(crate::convert(&area, &square_km, &1e6).unwrap(), 1.0);
+        let cubic_m: Unit = get_enum(String::from("m^3"), UnitType::Target).unwrap();
+        assert_eq!(crate::convert(&volume, &cubic_m, &1e6).unwrap(), 1.0);
+        assert!(get_enum(String::from("m^4"), UnitType::Source).is_err());
+    }
+
+    #[test]
+    fn strict_case_requires_the_printed_casing() {
+        let strict: ParseOptions = ParseOptions {
+            strict_case: true,
+            ..ParseOptions::default()
+        };
+        let parse = |unit: &str| get_enum_with(String::from(unit), UnitType::Source, &strict);
+        for unit in ["km", "°C", "MHz", "K", "KiB", "kilometer", "km^2"] {
+            assert!(parse(unit).is_ok(), "{}", unit);
+        }
+        let error: String = parse("KM").err().unwrap().to_string();
+        assert_eq!(
+            error,
+            "Error: [ERROR] Source unit 'KM' does not use the expected casing (--strict-case). Did you mean 'km'?"
+        );
+        assert!(
+            parse("mhz")
+                .err()
+                .unwrap()
+                .to_string()
+                .ends_with("Did you mean 'MHz'?")
+        );
+        assert!(
+            parse("KM^2")
+                .err()
+                .unwrap()
+                .to_string()
+                .ends_with("Did you mean 'km^2'?")
+        );
+        assert!(get_enum(String::from("KM"), UnitType::Source).is_ok());
+    }
+
+    #[test]
+    fn symbols_only_rejects_full_words() {
+        let symbols: ParseOptions = ParseOptions {
+            symbols_only: true,
+            ..ParseOptions::default()
+        };
+        let parse = |unit: &str| get_enum_with(String::from(unit), UnitType::Source, &symbols);
+        for unit in ["c", "°C", "km", "m^2"] {
+            assert!(parse(unit).is_ok(), "{}", unit);
+        }
+        assert_eq!(
+            parse("celsius").err().unwrap().to_string(),
+            "Error: [ERROR] Source unit 'celsius' is a full word, but --symbols-only requires a symbol (e.g., '°C')."
+        );
+        assert!(
+            parse("square meter")
+                .err()
+                .unwrap()
+                .to_string()
+                .ends_with("(e.g., 'm^2').")
+        );
+        assert!(get_enum(String::from("celsius"), UnitType::Source).is_ok());
+    }
+
+    #[test]
+    fn empty_unit_is_reported_as_missing() {
+        for unit in ["", "   "] {
+            assert_eq!(
+                get_enum(String::from(unit), UnitType::Source)
+                    .err()
+                    .unwrap()
+                    .to_string(),
+                "Error: [ERROR] No source unit provided."
+            );
+        }
+        assert_eq!(
+            get_enum(String::from(" "), UnitType::Target)
+                .err()
+                .unwrap()
+                .to_string(),
+            "Error: [ERROR] No target unit provided."
+        );
+        assert!(get_enum(String::from(" km "), UnitType::Source).unwrap() == Unit::Kilometer);
+    }
+
+    #[test]
+    fn mismatch_message_suggests_units_of_the_source_dimension() {
+        assert_eq!(
+            mismatch_message(&Unit::Kilometer, &Unit::Minute, &[]),
+            "Error: [ERROR] Cannot convert between different unit categories: [length] km → [time] min. Did you mean 'in'? [length] units: cm, inch, ft, km, miles, nm, mm, m, pt, pc, px."
+        );
+        assert_eq!(
+            mismatch_message(&Unit::Kilometer, &Unit::Celsius, &[]),
+            "Error: [ERROR] Cannot convert between different unit categories: [length] km → [temperature] celsius. [length] units: cm, inch, ft, km, miles, nm, mm, m, pt, pc, px."
+        );
+    }
+
+    #[test]
+    fn length_units_sort_by_magnitude() {
+        let sorted: Vec<Unit> = Unit::sorted_units_in(&UnitDimension::Length);
+        let position = |unit: Unit| sorted.iter().position(|other| *other == unit).unwrap();
+        assert!(position(Unit::Millimeter) < position(Unit::Centimeter));
+        assert!(position(Unit::Centimeter) < position(Unit::Meter));
+        assert!(position(Unit::Meter) < position(Unit::Kilometer));
+        assert!(sorted[0] == Unit::Nanometer);
+        assert_eq!(
+            Unit::Meter.cmp_magnitude(&Unit::Kilometer),
+            Some(Ordering::Less)
+        );
+        assert_eq!(Unit::Meter.cmp_magnitude(&Unit::Second), None);
+        assert_eq!(Unit::Joule.cmp_magnitude(&Unit::NewtonMeter), None);
     }
 }
@@ -0,0 +1,208 @@
+//! Tests of the commands other than `convert` and `history`, run through the
+//! `unitconv` binary.
+
+#![allow(clippy::needless_return)]
+
+mod common;
+
+use common::{Run, finish, run, run_in, unitconv};
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn list_shows_an_aligned_symbol_column() {
+    let result: Run = run(&["list"]);
+    let rows: Vec<&str> = result
+        .stdout
+        .lines()
+        .filter(|line| line.contains(". ["))
+        .collect();
+    assert_eq!(rows.len(), 54);
+    assert!(rows.contains(&" 8. [length]      mile                     (miles)"));
+    let column: usize = rows[0].find('(').unwrap();
+    for row in rows {
+        assert_eq!(row.find('('), Some(column), "{}", row);
+        assert!(row.ends_with(')'), "{}", row);
+    }
+}
+
+#[test]
+fn quiet_errors_keeps_the_exit_code_only() {
+    let quiet: Run = run(&[
+        "--quiet-errors",
+        "convert",
+        "--from",
+        "km",
+        "--to",
+        "celsius",
+        "--value",
+        "1",
+    ]);
+    assert_eq!(quiet.code, 1);
+    assert_eq!(quiet.stderr, "");
+    assert_eq!(quiet.stdout, "");
+
+    let loud: Run = run(&["convert", "--from", "km", "--to", "celsius", "--value", "1"]);
+    assert_eq!(loud.code, 1);
+    assert!(
+        loud.stderr
+            .starts_with("Error: [ERROR] Cannot convert between different unit categories")
+    );
+}
+
+#[test]
+fn quiet_errors_hides_skipped_target_units() {
+    let args: [&str; 7] = ["convert", "--from", "km", "--to", "m,xx", "--value", "1"];
+    let loud: Run = run(&args);
+    assert_eq!(loud.code, 0);
+    assert_eq!(loud.stdout, "1.0 km = 1000.0 m\n");
+    assert!(
+        loud.stderr
+            .starts_with("Warning: [WARNING] Target unit 'xx' not recognized."),
+        "{}",
+        loud.stderr
+    );
+    assert!(
+        loud.stderr.ends_with(" It is skipped.\n"),
+        "{}",
+        loud.stderr
+    );
+
+    let quiet: Run = run(&[&["--quiet-errors"], &args[..]].concat());
+    assert_eq!(quiet.code, 0);
+    assert_eq!(quiet.stdout, "1.0 km = 1000.0 m\n");
+    assert_eq!(quiet.stderr, "");
+}
+
+#[test]
+fn verify_finds_no_wrong_factors() {
+    let result: Run = run(&["verify"]);
+    assert_eq!(result.code, 0, "{}", result.stdout);
+    assert_eq!(
+        result.stdout,
+        "All 46 reference conversions and 76 unit round trips passed.\n"
+    );
+    assert_eq!(result.stderr, "");
+}
+
+#[test]
+fn check_reports_compatibility_through_the_exit_code() {
+    let compatible: Run = run(&["check", "--from", "celsius", "--to", "kelvin"]);
+    assert_eq!(compatible.code, 0);
+    assert_eq!(compatible.stdout, "");
+    assert_eq!(compatible.stderr, "");
+
+    let incompatible: Run = run(&["check", "--from", "celsius", "--to", "km"]);
+    assert_ne!(incompatible.code, 0);
+    assert_eq!(incompatible.stdout, "");
+    assert_eq!(incompatible.stderr, "");
+}
+
+#[test]
+fn diag_lists_a_round_trip_per_unit() {
+    let result: Run = run(&["diag", "temperature"]);
+    assert_eq!(result.code, 0);
+    let lines: Vec<&str> = result.stdout.lines().collect();
+    assert_eq!(lines[0], "Round trip of 1 through the base unit (celsius):");
+    let rows: Vec<&str> = lines[2..].to_vec();
+    assert_eq!(rows.len(), 3, "{}", result.stdout);
+    for (row, unit) in rows.iter().zip(["celsius", "fahrenheit", "kelvin"]) {
+        assert!(row.starts_with(unit), "{}", row);
+        let error: f64 = row.split_whitespace().last().unwrap().parse().unwrap();
+        assert!(error < 1e-12, "{}", row);
+    }
+}
+
+#[test]
+fn weather_preset_includes_fahrenheit() {
+    let result: Run = run(&["preset", "weather", "--value", "25"]);
+    assert_eq!(result.code, 0);
+    assert!(
+        result
+            .stdout
+            .lines()
+            .any(|line| line.trim() == "25.0 °C = 77.0 °F"),
+        "{}",
+        result.stdout
+    );
+
+    let unknown: Run = run(&["preset", "nope", "--value", "1"]);
+    assert_eq!(
+        unknown.stderr,
+        "Error: [ERROR] Unknown preset 'nope'. Available presets: weather, travel, storage, screen.\n"
+    );
+}
+
+#[test]
+fn list_aliases_shows_every_accepted_name() {
+    let result: Run = run(&["list", "--aliases"]);
+    let celsius: &str = result
+        .stdout
+        .lines()
+        .find(|line| line.trim_start().starts_with("1. celsius"))
+        .unwrap();
+    let aliases: Vec<&str> = celsius
+        .trim_start_matches([' ', '1', '.'])
+        .trim_start_matches("celsius")
+        .trim()
+        .split(", ")
+        .collect();
+    assert!(aliases.contains(&"c"), "{}", celsius);
+    assert!(aliases.contains(&"centigrade"), "{}", celsius);
+}
+
+#[test]
+fn emoji_prefixes_each_dimension() {
+    let plain: Run = run(&["list"]);
+    assert!(!plain.stdout.contains('🌡'), "{}", plain.stdout);
+
+    let result: Run = run(&["list", "--emoji"]);
+    let temperature: Vec<&str> = result
+        .stdout
+        .lines()
+        .filter(|line| line.contains("[temperature]"))
+        .collect();
+    assert_eq!(temperature.len(), 3);
+    assert!(
+        temperature
+            .iter()
+            .all(|line| line.contains("🌡 [temperature]"))
+    );
+}
+
+#[test]
+fn config_print_round_trips_through_the_config_file() {
+    let dir: TempDir = TempDir::new().unwrap();
+    let printed: Run = run_in(dir.path(), &["config", "--print"]);
+    assert_eq!(printed.code, 0);
+    assert!(
+        printed.stdout.lines().any(|line| line == "decimals = 4"),
+        "{}",
+        printed.stdout
+    );
+
+    fs::create_dir(dir.path().join("data")).unwrap();
+    fs::write(dir.path().join("data/config.toml"), &printed.stdout).unwrap();
+    let reloaded: Run = run_in(dir.path(), &["config", "--print"]);
+    assert_eq!(reloaded.stderr, "");
+    assert_eq!(reloaded.stdout, printed.stdout);
+}
+
+#[test]
+fn missing_command_is_a_one_line_error_without_implicit_help() {
+    let flag: Run = run(&["--no-implicit-help"]);
+    assert_eq!(flag.code, 2);
+    assert_eq!(
+        flag.stderr,
+        "Error: [ERROR] No command given (see 'unitconv --help').\n"
+    );
+    assert_eq!(flag.stdout, "");
+
+    let dir: TempDir = TempDir::new().unwrap();
+    let env: Run = finish(unitconv(dir.path()).env("UNITCONV_NO_HELP", "1"), "");
+    assert_eq!(env.code, 2);
+    assert_eq!(env.stderr, flag.stderr);
+
+    let help: Run = run(&[]);
+    assert!(help.stderr.contains("Usage:"), "{}", help.stderr);
+}
@@ -0,0 +1,73 @@
+//! Helpers for the tests that run the `unitconv` binary.
+//!
+//! Every run happens in a working directory of the test's choosing, usually a
+//! new empty one, so that the `data` directory (history, configuration and custom
+//! units) starts out empty. The locale and time zone are fixed, so that the output
+//! does not depend on the machine running the tests.
+
+// Each test file uses only some of the helpers.
+#![allow(dead_code)]
+
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use tempfile::TempDir;
+
+/// The outcome of running the binary.
+pub struct Run {
+    pub stdout: String,
+    pub stderr: String,
+    pub code: i32,
+}
+
+/// Builds a command running `unitconv` in a directory.
+pub fn unitconv(dir: &Path) -> Command {
+    let mut command: Command = Command::new(env!("CARGO_BIN_EXE_unitconv"));
+    command
+        .current_dir(dir)
+        .env("LC_ALL", "C.UTF-8")
+        .env("TZ", "UTC")
+        .env_remove("UNITCONV_DECIMALS")
+        .env_remove("UNITCONV_NO_HELP")
+        .env_remove("COLUMNS");
+    return command;
+}
+
+/// Runs a command with the given text on its standard input.
+pub fn finish(command: &mut Command, stdin: &str) -> Run {
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    return Run {
+        stdout: String::from_utf8(output.stdout).unwrap(),
+        stderr: String::from_utf8(output.stderr).unwrap(),
+        code: output.status.code().unwrap_or(-1),
+    };
+}
+
+/// Runs `unitconv` with the given arguments in a directory.
+pub fn run_in(dir: &Path, args: &[&str]) -> Run {
+    return finish(unitconv(dir).args(args), "");
+}
+
+/// Runs `unitconv` with the given arguments in a new empty directory.
+pub fn run(args: &[&str]) -> Run {
+    let dir: TempDir = TempDir::new().unwrap();
+    return run_in(dir.path(), args);
+}
+
+/// Runs `unitconv` with the given arguments and standard input in a new empty directory.
+pub fn run_with_input(args: &[&str], stdin: &str) -> Run {
+    let dir: TempDir = TempDir::new().unwrap();
+    return finish(unitconv(dir.path()).args(args), stdin);
+}
@@ -0,0 +1,793 @@
+//! Tests of the `convert` command, run through the `unitconv` binary.
+
+#![allow(clippy::needless_return)]
+
+mod common;
+
+use common::{Run, finish, run, run_in, run_with_input, unitconv};
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::process::{Child, ChildStdin, ChildStdout, Stdio};
+use tempfile::TempDir;
+
+#[test]
+fn best_unit_picks_a_readable_unit() {
+    let large: Run = run(&[
+        "convert",
+        "--from",
+        "cm",
+        "--value",
+        "1500000",
+        "--best-unit",
+    ]);
+    assert_eq!(large.stdout, "1500000.0 cm = 15.0 km\n");
+    let small: Run = run(&["convert", "--from", "cm", "--value", "0.5", "--best-unit"]);
+    assert_eq!(small.stdout, "0.5 cm = 5.0 mm\n");
+}
+
+#[test]
+fn lang_translates_unit_names_in_the_output() {
+    let spanish: Run = run(&[
+        "convert",
+        "--from",
+        "celsius",
+        "--to",
+        "fahrenheit",
+        "--value",
+        "100",
+        "--lang",
+        "es",
+    ]);
+    assert_eq!(
+        spanish.stdout,
+        "100.0 grados Celsius = 212.0 grados Fahrenheit\n"
+    );
+    let german: Run = run(&[
+        "convert",
+        "--from",
+        "celsius",
+        "--to",
+        "fahrenheit",
+        "--value",
+        "100",
+        "--lang",
+        "de",
+    ]);
+    assert_eq!(
+        german.stdout,
+        "100.0 Grad Celsius = 212.0 Grad Fahrenheit\n"
+    );
+}
+
+#[test]
+fn missing_source_unit_is_an_error_without_a_terminal() {
+    let result: Run = run(&["convert", "--value", "100", "--to", "fahrenheit"]);
+    assert_eq!(result.code, 1);
+    assert_eq!(
+        result.stderr,
+        "Error: [ERROR] No source unit provided (use --from).\n"
+    );
+    assert_eq!(result.stdout, "");
+}
+
+#[test]
+fn compare_to_baseline_reports_the_difference() {
+    let result: Run = run(&[
+        "convert",
+        "--from",
+        "km",
+        "--to",
+        "m,cm",
+        "--value",
+        "1.5",
+        "--compare-to-baseline",
+    ]);
+    assert_eq!(
+        result.stdout,
+        "1.5 km = 1500.0 m\n1.5 km = 150000.0 cm\ncm vs m: +9900.0000%\n"
+    );
+}
+
+#[test]
+fn with_reciprocal_prints_the_inverse() {
+    let result: Run = run(&[
+        "convert",
+        "--from",
+        "cm",
+        "--to",
+        "m",
+        "--value",
+        "400",
+        "--with-reciprocal",
+    ]);
+    assert_eq!(result.stdout, "400.0 cm = 4.0 m\nreciprocal: 0.25\n");
+    let zero: Run = run(&[
+        "convert",
+        "--from",
+        "cm",
+        "--to",
+        "m",
+        "--value",
+        "0",
+        "--with-reciprocal",
+    ]);
+    assert_eq!(
+        zero.stdout,
+        "0.0 cm = 0.0 m\nreciprocal: undefined (result is zero)\n"
+    );
+}
+
+#[test]
+fn summary_reports_statistics_of_a_batch() {
+    let args: [&str; 7] = [
+        "convert",
+        "--from",
+        "km",
+        "--to",
+        "m",
+        "--batch",
+        "--summary",
+    ];
+    let result: Run = run_with_input(&args, "1\n2\n4\n");
+    assert_eq!(result.code, 0);
+    assert_eq!(
+        result.stdout,
+        "1.0 km = 1000.0 m\n2.0 km = 2000.0 m\n4.0 km = 4000.0 m\n"
+    );
+    assert_eq!(
+        result.stderr,
+        "Summary: count 3, min 1000.0, max 4000.0, mean 2333.3333\n"
+    );
+
+    let empty: Run = run_with_input(&args, "");
+    assert_eq!(empty.code, 0);
+    assert_eq!(empty.stderr, "Summary: count 0 (no values converted)\n");
+}
+
+#[test]
+fn batch_prints_each_result_as_it_is_read() {
+    let dir: TempDir = TempDir::new().unwrap();
+    let mut child: Child = unitconv(dir.path())
+        .args(["convert", "--from", "km", "--to", "m", "--batch"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut stdin: ChildStdin = child.stdin.take().unwrap();
+    let mut stdout: BufReader<ChildStdout> = BufReader::new(child.stdout.take().unwrap());
+
+    let mut line: String = String::new();
+    writeln!(stdin, "1").unwrap();
+    stdout.read_line(&mut line).unwrap();
+    assert_eq!(line, "1.0 km = 1000.0 m\n");
+
+    line.clear();
+    writeln!(stdin, "100").unwrap();
+    drop(stdin);
+    stdout.read_line(&mut line).unwrap();
+    assert_eq!(line, "100.0 km = 100000.0 m\n");
+    assert!(child.wait().unwrap().success());
+}
+
+#[test]
+fn decimals_fall_back_to_the_environment() {
+    let dir: TempDir = TempDir::new().unwrap();
+    let args: [&str; 7] = ["convert", "--from", "km", "--to", "miles", "--value", "10"];
+    let from_env: Run = finish(
+        unitconv(dir.path())
+            .env("UNITCONV_DECIMALS", "2")
+            .args(args),
+        "",
+    );
+    assert_eq!(from_env.stdout, "10.0 km = 6.21 miles\n");
+    let from_flag: Run = finish(
+        unitconv(dir.path())
+            .env("UNITCONV_DECIMALS", "2")
+            .args(args)
+            .args(["--decimals", "5"]),
+        "",
+    );
+    assert_eq!(from_flag.stdout, "10.0 km = 6.21371 miles\n");
+    let built_in: Run = run_in(dir.path(), &args);
+    assert_eq!(built_in.stdout, "10.0 km = 6.2137 miles\n");
+}
+
+#[test]
+fn target_value_finds_the_source_value() {
+    let result: Run = run(&[
+        "convert",
+        "--from",
+        "celsius",
+        "--to",
+        "fahrenheit",
+        "--target-value",
+        "212",
+    ]);
+    assert_eq!(result.code, 0);
+    assert_eq!(result.stdout, "100.0 °C = 212.0 °F\n");
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn ndjson_prints_one_object_per_line() {
+    let result: Run = run_with_input(
+        &[
+            "convert", "--from", "km", "--to", "m", "--batch", "--ndjson",
+        ],
+        "1\n2.5\n",
+    );
+    assert_eq!(result.code, 0);
+    let objects: Vec<serde_json::Value> = result
+        .stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(objects.len(), 2);
+    assert_eq!(
+        objects[1],
+        serde_json::json!({"input": 2.5, "from": "km", "to": "m", "output": 2500.0})
+    );
+    assert_eq!(objects[0]["output"], 1000.0);
+}
+
+#[test]
+fn label_prefixes_every_result() {
+    let single: Run = run(&[
+        "convert", "--from", "c", "--to", "f", "--value", "100", "--label", "job42",
+    ]);
+    assert_eq!(single.stdout, "job42: 100.0 °C = 212.0 °F\n");
+    let batch: Run = run_with_input(
+        &[
+            "convert", "--from", "km", "--to", "m", "--batch", "--label", "job42",
+        ],
+        "1\n2\n",
+    );
+    assert_eq!(
+        batch.stdout,
+        "job42: 1.0 km = 1000.0 m\njob42: 2.0 km = 2000.0 m\n"
+    );
+    let empty: Run = run(&[
+        "convert", "--from", "c", "--to", "f", "--value", "100", "--label", "",
+    ]);
+    assert_eq!(empty.stdout, "100.0 °C = 212.0 °F\n");
+}
+
+#[test]
+fn spec_converts_a_compact_request() {
+    assert_eq!(
+        run(&["convert", "--spec", "100c>f"]).stdout,
+        "100.0 °C = 212.0 °F\n"
+    );
+    assert_eq!(
+        run(&["convert", "--spec", "5km>miles"]).stdout,
+        "5.0 km = 3.1069 miles\n"
+    );
+}
+
+#[test]
+fn auto_precision_matches_the_source_value() {
+    let short: Run = run(&[
+        "convert",
+        "--from",
+        "km",
+        "--to",
+        "miles",
+        "--value",
+        "1.5",
+        "--precision",
+        "auto",
+    ]);
+    assert_eq!(short.stdout, "1.5 km = 0.93 miles\n");
+    let long: Run = run(&[
+        "convert",
+        "--from",
+        "km",
+        "--to",
+        "miles",
+        "--value",
+        "1.23456",
+        "--precision",
+        "auto",
+    ]);
+    assert_eq!(long.stdout, "1.23456 km = 0.76712 miles\n");
+}
+
+#[test]
+fn to_base_converts_to_the_base_unit() {
+    let temperature: Run = run(&[
+        "convert",
+        "--from",
+        "fahrenheit",
+        "--value",
+        "212",
+        "--to-base",
+    ]);
+    assert_eq!(temperature.stdout, "212.0 °F = 100.0 °C\n");
+    let length: Run = run(&["convert", "--from", "m", "--value", "2", "--to-base"]);
+    assert_eq!(length.stdout, "2.0 m = 200.0 cm\n");
+}
+
+#[test]
+fn echo_command_prints_the_flag_form() {
+    let result: Run = run(&["convert", "--spec", "100c>f", "--echo-command"]);
+    assert_eq!(result.stdout, "100.0 °C = 212.0 °F\n");
+    assert_eq!(
+        result.stderr,
+        "unitconv convert --from celsius --to fahrenheit --value 100\n"
+    );
+}
+
+#[test]
+fn markdown_and_latex_snippets_are_escaped() {
+    let args: [&str; 7] = ["convert", "--from", "c", "--to", "f", "--value", "100"];
+    let markdown: Run = run(&[&args[..], &["--format-markdown"]].concat());
+    assert_eq!(markdown.stdout, "$100.0\\,°C = 212.0\\,°F$\n");
+    let latex: Run = run(&[&args[..], &["--format-latex"]].concat());
+    assert_eq!(
+        latex.stdout,
+        "\\(100.0\\,^{\\circ}\\mathrm{C} = 212.0\\,^{\\circ}\\mathrm{F}\\)\n"
+    );
+}
+
+#[test]
+#[cfg(not(feature = "history"))]
+fn conversions_work_without_the_history_feature() {
+    let dir: TempDir = TempDir::new().unwrap();
+    let result: Run = run_in(
+        dir.path(),
+        &["convert", "--from", "c", "--to", "f", "--value", "100"],
+    );
+    assert_eq!(result.code, 0);
+    assert_eq!(result.stdout, "100.0 °C = 212.0 °F\n");
+    assert!(!dir.path().join("data/history.json").exists());
+}
+
+#[test]
+fn units_with_identical_factors_convert_exactly() {
+    let dir: TempDir = TempDir::new().unwrap();
+    fs::create_dir(dir.path().join("data")).unwrap();
+    fs::write(
+        dir.path().join("data/units.toml"),
+        "[twin]\nsymbol = \"tw\"\ndimension = \"length\"\nfactor = 1.0000000001\n",
+    )
+    .unwrap();
+    let args: [&str; 9] = [
+        "convert",
+        "--from",
+        "tw",
+        "--to",
+        "cm",
+        "--value",
+        "0.3",
+        "--decimals",
+        "15",
+    ];
+    let strict: Run = run_in(dir.path(), &args);
+    assert_eq!(strict.stdout, "0.3 tw = 0.30000000003 cm\n");
+
+    fs::write(
+        dir.path().join("data/config.toml"),
+        "identity_tolerance = 1e-9\n",
+    )
+    .unwrap();
+    let tolerant: Run = run_in(dir.path(), &args);
+    assert_eq!(tolerant.stdout, "0.3 tw = 0.3 cm\n");
+}
+
+#[test]
+fn exact_prints_whole_byte_counts() {
+    let result: Run = run(&[
+        "convert", "--from", "gib", "--to", "b", "--value", "5", "--exact",
+    ]);
+    assert_eq!(result.stdout, "5 GiB = 5368709120 B\n");
+}
+
+#[test]
+fn tiny_negative_results_print_as_zero() {
+    let result: Run = run(&[
+        "convert",
+        "--from",
+        "f",
+        "--to",
+        "c",
+        "--value",
+        "31.99999999",
+    ]);
+    assert_eq!(result.stdout, "32.0 °F = 0.0 °C\n");
+}
+
+#[test]
+fn steps_print_a_table_of_the_range() {
+    let args: [&str; 5] = ["convert", "--from", "c", "--to", "f"];
+    let table: Run = run(&[
+        &args[..],
+        &["--from-value", "0", "--to-value", "30", "--step", "10"],
+    ]
+    .concat());
+    assert_eq!(
+        table.stdout,
+        "  °C    °F\n 0.0  32.0\n10.0  50.0\n20.0  68.0\n30.0  86.0\n"
+    );
+
+    let reversed: Run = run(&[
+        &args[..],
+        &["--from-value", "30", "--to-value", "0", "--step", "10"],
+    ]
+    .concat());
+    assert_eq!(reversed.code, 1);
+    let no_step: Run = run(&[
+        &args[..],
+        &["--from-value", "0", "--to-value", "30", "--step", "0"],
+    ]
+    .concat());
+    assert_eq!(
+        no_step.stderr,
+        "Error: [ERROR] --step must be a positive number, got 0.\n"
+    );
+}
+
+#[test]
+fn humanize_splits_a_duration() {
+    let result: Run = run(&[
+        "convert",
+        "--from",
+        "s",
+        "--to",
+        "h",
+        "--value",
+        "93784",
+        "--humanize",
+    ]);
+    assert_eq!(result.stdout, "93784 s = 1 d 2 h 3 min 4 s\n");
+}
+
+#[test]
+fn round_to_snaps_the_result() {
+    let result: Run = run(&[
+        "convert",
+        "--from",
+        "m",
+        "--to",
+        "cm",
+        "--value",
+        "0.0265",
+        "--round-to",
+        "0.25",
+    ]);
+    assert_eq!(result.stdout, "0.0265 m = 2.75 cm\n");
+    let zero: Run = run(&[
+        "convert",
+        "--from",
+        "m",
+        "--to",
+        "cm",
+        "--value",
+        "1",
+        "--round-to",
+        "0",
+    ]);
+    assert_eq!(zero.code, 2);
+    assert!(
+        zero.stderr.contains("increment '0' must be greater than 0"),
+        "{}",
+        zero.stderr
+    );
+}
+
+#[test]
+fn assume_dimension_resolves_shared_symbols() {
+    let time: Run = run(&[
+        "convert",
+        "--from",
+        "m",
+        "--to",
+        "s",
+        "--value",
+        "2",
+        "--assume-dimension",
+        "time",
+    ]);
+    assert_eq!(time.stdout, "2.0 min = 120.0 s\n");
+    let length: Run = run(&[
+        "convert",
+        "--from",
+        "m",
+        "--to",
+        "cm",
+        "--value",
+        "2",
+        "--assume-dimension",
+        "length",
+    ]);
+    assert_eq!(length.stdout, "2.0 m = 200.0 cm\n");
+    let outside: Run = run(&[
+        "convert",
+        "--from",
+        "c",
+        "--to",
+        "f",
+        "--value",
+        "2",
+        "--assume-dimension",
+        "length",
+    ]);
+    assert_eq!(outside.code, 1);
+    assert!(
+        outside
+            .stderr
+            .starts_with("Error: [ERROR] Source unit 'c' is not a [length] unit (--assume-dimension), but [temperature] celsius."),
+        "{}",
+        outside.stderr
+    );
+}
+
+#[test]
+fn non_utf8_locale_selects_ascii_symbols() {
+    let dir: TempDir = TempDir::new().unwrap();
+    let args: [&str; 7] = ["convert", "--from", "c", "--to", "f", "--value", "1"];
+    for locale in ["C", "en_US.ISO-8859-1"] {
+        let ascii: Run = finish(unitconv(dir.path()).env("LC_ALL", locale).args(args), "");
+        assert_eq!(ascii.stdout, "1.0 C = 33.8 F\n", "{}", locale);
+    }
+    let unicode: Run = run_in(dir.path(), &args);
+    assert_eq!(unicode.stdout, "1.0 °C = 33.8 °F\n");
+    let forced: Run = finish(
+        unitconv(dir.path())
+            .env("LC_ALL", "C")
+            .args(args)
+            .args(["--charset", "unicode"]),
+        "",
+    );
+    assert_eq!(forced.stdout, "1.0 °C = 33.8 °F\n");
+}
+
+#[test]
+fn tolerant_parse_accepts_a_unit_after_the_value() {
+    for value in ["  100  celsius  ", "100c"] {
+        let result: Run = run(&["convert", "--value", value, "--to", "f", "--tolerant-parse"]);
+        assert_eq!(result.stdout, "100.0 °C = 212.0 °F\n", "{:?}", value);
+    }
+    let strict: Run = run(&["convert", "--value", "100c", "--to", "f"]);
+    assert_eq!(strict.code, 1);
+    assert!(
+        strict.stderr.contains("add --tolerant-parse"),
+        "{}",
+        strict.stderr
+    );
+}
+
+#[test]
+fn custom_units_cache_is_rebuilt_when_the_file_changes() {
+    let dir: TempDir = TempDir::new().unwrap();
+    fs::create_dir(dir.path().join("data")).unwrap();
+    let units: String =
+        String::from("[furlong]\nsymbol = \"fur\"\ndimension = \"length\"\nfactor = 20116.8\n");
+    fs::write(dir.path().join("data/units.toml"), &units).unwrap();
+    let convert = |unit: &str| -> Run {
+        return run_in(
+            dir.path(),
+            &["convert", "--from", unit, "--to", "m", "--value", "1"],
+        );
+    };
+    assert_eq!(convert("fur").stdout, "1.0 fur = 201.168 m\n");
+
+    // A symbol changed in the cache alone is only seen if the cache is read.
+    let cache_path: PathBuf = dir.path().join("data/units.cache");
+    let cache: String = fs::read_to_string(&cache_path).unwrap();
+    fs::write(
+        &cache_path,
+        cache.replace("furlong\tfur\t", "furlong\tfl\t"),
+    )
+    .unwrap();
+    assert_eq!(convert("fl").stdout, "1.0 fl = 201.168 m\n");
+
+    fs::write(
+        dir.path().join("data/units.toml"),
+        format!("# Old units\n{}", units),
+    )
+    .unwrap();
+    assert_eq!(convert("fur").stdout, "1.0 fur = 201.168 m\n");
+    assert_eq!(convert("fl").code, 1);
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn from_json_reports_each_request() {
+    let result: Run = run_with_input(
+        &["convert", "--from-json", "-"],
+        r#"[{"from": "celsius", "to": "fahrenheit", "value": 100},
+            {"from": "celsius", "to": "km", "value": 1},
+            {"from": "km", "to": "nm", "value": 1e300}]"#,
+    );
+    assert_eq!(result.code, 0);
+    let results: serde_json::Value = serde_json::from_str(&result.stdout).unwrap();
+    assert_eq!(
+        results,
+        serde_json::json!([
+            {"from": "celsius", "to": "fahrenheit", "value": 100.0, "result": 212.0},
+            {"from": "celsius", "to": "km", "value": 1.0,
+             "error": "Cannot convert between different unit categories: [temperature] celsius → [length] km"},
+            {"from": "km", "to": "nm", "value": 1e300,
+             "error": "Converting 1e300 km to nm overflows to infinity. Use a larger target unit or a smaller value."}
+        ])
+    );
+}
+
+#[test]
+fn system_picks_the_unit_of_that_system() {
+    let imperial: Run = run(&[
+        "convert", "--from", "cm", "--value", "30", "--system", "imperial",
+    ]);
+    assert_eq!(imperial.stdout, "30.0 cm = 11.811 inch\n");
+    let si: Run = run(&["convert", "--from", "ft", "--value", "10", "--system", "si"]);
+    assert_eq!(si.stdout, "10.0 ft = 3.048 m\n");
+    let cgs: Run = run(&[
+        "convert", "--from", "ft", "--value", "10", "--system", "cgs",
+    ]);
+    assert_eq!(cgs.stdout, "10.0 ft = 304.8 cm\n");
+}
+
+#[test]
+fn per_converts_a_temperature_gradient() {
+    let result: Run = run(&[
+        "convert", "--from", "c", "--to", "f", "--value", "1", "--per", "m", "--per-to", "ft",
+    ]);
+    assert_eq!(result.stdout, "1.0 °C/m = 0.5486 °F/ft\n");
+}
+
+#[test]
+fn show_both_systems_adds_the_imperial_equivalent() {
+    let result: Run = run(&[
+        "convert",
+        "--from",
+        "cm",
+        "--to",
+        "m",
+        "--value",
+        "30",
+        "--show-both-systems",
+    ]);
+    assert_eq!(result.stdout, "30.0 cm = 0.3 m\n30.0 cm = 11.811 inch\n");
+}
+
+#[test]
+fn dimension_requires_units_of_that_dimension() {
+    let length: Run = run(&[
+        "convert",
+        "--from",
+        "cm",
+        "--to",
+        "km",
+        "--value",
+        "1",
+        "--dimension",
+        "length",
+    ]);
+    assert_eq!(length.code, 0);
+    let temperature: Run = run(&[
+        "convert",
+        "--from",
+        "c",
+        "--to",
+        "f",
+        "--value",
+        "1",
+        "--dimension",
+        "length",
+    ]);
+    assert_eq!(temperature.code, 1);
+    assert_eq!(
+        temperature.stderr,
+        "Error: [ERROR] Expected only [length] units (--dimension), but celsius is a [temperature] unit.\n"
+    );
+}
+
+#[test]
+fn shell_export_prints_an_assignment() {
+    let result: Run = run(&[
+        "convert",
+        "--from",
+        "c",
+        "--to",
+        "f",
+        "--value",
+        "100",
+        "--shell-export",
+        "RESULT",
+    ]);
+    assert_eq!(result.stdout, "RESULT=212.0\n");
+    let invalid: Run = run(&[
+        "convert",
+        "--from",
+        "c",
+        "--to",
+        "f",
+        "--value",
+        "100",
+        "--shell-export",
+        "1BAD",
+    ]);
+    assert_eq!(invalid.code, 2);
+    assert_eq!(invalid.stdout, "");
+}
+
+#[test]
+fn human_scales_data_sizes() {
+    let binary: Run = run(&["convert", "--from", "b", "--value", "1536", "--human"]);
+    assert_eq!(binary.stdout, "1536.0 B = 1.5 KiB\n");
+    let decimal: Run = run(&[
+        "convert", "--from", "b", "--value", "1000000", "--human", "decimal",
+    ]);
+    assert_eq!(decimal.stdout, "1000000.0 B = 1.0 MB\n");
+}
+
+#[test]
+fn compound_height_converts_to_centimeters() {
+    let result: Run = run(&[
+        "convert",
+        "--value",
+        "5 ft 11 in",
+        "--to",
+        "cm",
+        "--compound",
+    ]);
+    assert_eq!(result.stdout, "5.916667 ft = 180.34 cm\n");
+}
+
+#[test]
+fn random_with_a_seed_is_reproducible() {
+    let args: [&str; 6] = ["convert", "--random", "--seed", "42", "--value", "10"];
+    let first: Run = run(&args);
+    let second: Run = run(&args);
+    assert_eq!(
+        first.stderr,
+        "Random [temperature] conversion: kelvin → celsius\n"
+    );
+    assert_eq!(first.stdout, "10.0 K = -263.15 °C\n");
+    assert_eq!(second.stdout, first.stdout);
+    assert_eq!(second.stderr, first.stderr);
+}
+
+#[test]
+fn overflowing_results_are_not_printed_as_inf() {
+    let result: Run = run(&["convert", "--from", "km", "--to", "nm", "--value", "1e300"]);
+    assert_eq!(result.code, 1);
+    assert_eq!(result.stdout, "");
+    assert_eq!(
+        result.stderr,
+        "Error: [ERROR] Converting 1e300 km to nm overflows to infinity. Use a larger target unit (e.g., --best-unit) or a smaller value.\n"
+    );
+
+    let quiet: Run = run(&[
+        "--quiet-errors",
+        "convert",
+        "--from",
+        "km",
+        "--to",
+        "nm",
+        "--value",
+        "1e300",
+    ]);
+    assert_eq!(quiet.code, 1);
+    assert_eq!(quiet.stderr, "");
+}
+
+#[test]
+fn max_precision_caps_auto_precision() {
+    let args: [&str; 9] = [
+        "convert",
+        "--from",
+        "km",
+        "--to",
+        "miles",
+        "--value",
+        "1.123456789012345",
+        "--precision",
+        "auto",
+    ];
+    let uncapped: Run = run(&args);
+    assert_eq!(uncapped.stdout, "1.123457 km = 0.6980836844157278 miles\n");
+    let capped: Run = run(&[&args[..], &["--max-precision", "10"]].concat());
+    assert_eq!(capped.stdout, "1.123457 km = 0.6980836844 miles\n");
+}
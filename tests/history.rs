@@ -0,0 +1,676 @@
+//! Tests of the conversion history, run through the `unitconv` binary.
+
+#![cfg(feature = "history")]
+#![allow(clippy::needless_return)]
+
+mod common;
+
+use common::{Run, run_in, unitconv};
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::PathBuf;
+use std::process::{Child, Output, Stdio};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+use tempfile::TempDir;
+
+#[test]
+fn history_parent_that_is_a_file_is_reported() {
+    let dir: TempDir = TempDir::new().unwrap();
+    fs::write(dir.path().join("data"), "").unwrap();
+
+    let result: Run = run_in(
+        dir.path(),
+        &[
+            "convert",
+            "--from",
+            "celsius",
+            "--to",
+            "fahrenheit",
+            "--value",
+            "100",
+        ],
+    );
+    assert_eq!(result.code, 1);
+    assert_eq!(
+        result.stderr.trim_end(),
+        "Error: [ERROR] History path parent 'data' exists but is not a directory. Move or rename that file so the history directory can be created."
+    );
+    assert!(dir.path().join("data").is_file());
+}
+
+#[test]
+fn padding_is_left_out_of_history_records() {
+    let dir: TempDir = TempDir::new().unwrap();
+    let result: Run = run_in(
+        dir.path(),
+        &[
+            "convert", "--from", "km", "--to", "cm", "--value", "-5", "--pad", "12",
+        ],
+    );
+    assert_eq!(result.stdout, "        -5.0 km =    -500000.0 cm\n");
+
+    let history: Run = run_in(dir.path(), &["history"]);
+    assert!(
+        history.stdout.contains("-5.0 km = -500000.0 cm\n"),
+        "{}",
+        history.stdout
+    );
+}
+
+#[test]
+fn group_by_dimension_buckets_records() {
+    let dir: TempDir = TempDir::new().unwrap();
+    run_in(
+        dir.path(),
+        &[
+            "convert",
+            "--from",
+            "celsius",
+            "--to",
+            "fahrenheit",
+            "--value",
+            "100",
+        ],
+    );
+    run_in(
+        dir.path(),
+        &["convert", "--from", "km", "--to", "cm", "--value", "1"],
+    );
+    run_in(
+        dir.path(),
+        &[
+            "convert", "--from", "kelvin", "--to", "celsius", "--value", "0",
+        ],
+    );
+    let path: PathBuf = dir.path().join("data/history.json");
+    let content: String = fs::read_to_string(&path).unwrap();
+    let legacy: String = content.replacen(
+        "\"list\": [",
+        "\"list\": [{\"value\": \"5 feet = 1.524 meters\"},",
+        1,
+    );
+    fs::write(&path, legacy).unwrap();
+
+    let result: Run = run_in(dir.path(), &["history", "--group-by", "dimension"]);
+    assert_eq!(result.code, 0, "{}", result.stderr);
+    let headers: Vec<&str> = result
+        .stdout
+        .lines()
+        .filter(|line| line.starts_with('['))
+        .collect();
+    assert_eq!(
+        headers,
+        ["[temperature] (2)", "[length] (1)", "[ungrouped] (1)"]
+    );
+    assert!(result.stdout.contains("1. 5 feet = 1.524 meters"));
+}
+
+#[test]
+fn notes_are_shown_in_the_history() {
+    let dir: TempDir = TempDir::new().unwrap();
+    run_in(
+        dir.path(),
+        &[
+            "convert",
+            "--from",
+            "celsius",
+            "--to",
+            "fahrenheit",
+            "--value",
+            "100",
+        ],
+    );
+    run_in(
+        dir.path(),
+        &[
+            "convert",
+            "--from",
+            "km",
+            "--to",
+            "miles",
+            "--value",
+            "2",
+            "--note",
+            "trail run",
+        ],
+    );
+
+    let result: Run = run_in(dir.path(), &["history"]);
+    let lines: Vec<&str> = result.stdout.lines().collect();
+    assert!(lines[1].ends_with("] 100.0 °C = 212.0 °F"), "{}", lines[1]);
+    assert!(
+        lines[2].ends_with("] 2.0 km = 1.2427 miles (trail run)"),
+        "{}",
+        lines[2]
+    );
+}
+
+#[test]
+fn corrupt_history_is_reported_only_by_commands_that_use_it() {
+    let dir: TempDir = TempDir::new().unwrap();
+    fs::create_dir(dir.path().join("data")).unwrap();
+    fs::write(dir.path().join("data/history.json"), "{not json").unwrap();
+
+    let convert: Run = run_in(
+        dir.path(),
+        &[
+            "convert",
+            "--from",
+            "celsius",
+            "--to",
+            "fahrenheit",
+            "--value",
+            "1",
+        ],
+    );
+    assert_eq!(convert.code, 1);
+    assert!(
+        convert
+            .stderr
+            .starts_with("Error: [ERROR] History file 'data/history.json' is not valid:"),
+        "{}",
+        convert.stderr
+    );
+    assert!(convert.stderr.contains("Fix the JSON by hand"));
+
+    for args in [
+        &["list"][..],
+        &["verify"],
+        &["check", "--from", "celsius", "--to", "kelvin"],
+    ] {
+        let result: Run = run_in(dir.path(), args);
+        assert_eq!(result.code, 0, "{:?}: {}", args, result.stderr);
+    }
+}
+
+#[test]
+fn follow_prints_appended_records_once() {
+    let dir: TempDir = TempDir::new().unwrap();
+    run_in(
+        dir.path(),
+        &[
+            "convert",
+            "--from",
+            "celsius",
+            "--to",
+            "fahrenheit",
+            "--value",
+            "100",
+        ],
+    );
+    let path: PathBuf = dir.path().join("data/history.json");
+    let tampered: String = fs::read_to_string(&path)
+        .unwrap()
+        .replace("212.0 °F", "213.0 °F");
+    fs::write(&path, tampered).unwrap();
+
+    let mut follow: Child = unitconv(dir.path())
+        .args(["history", "--follow"])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let stdout = follow.stdout.take().unwrap();
+    let (sender, lines) = mpsc::channel::<String>();
+    thread::spawn(move || {
+        for line in BufReader::new(stdout).lines() {
+            if sender.send(line.unwrap()).is_err() {
+                break;
+            }
+        }
+    });
+    let next = || lines.recv_timeout(Duration::from_secs(10)).unwrap();
+    assert_eq!(next(), "Conversion History:");
+    assert!(next().ends_with("] 100.0 °C = 213.0 °F"));
+
+    run_in(
+        dir.path(),
+        &["convert", "--from", "km", "--to", "m", "--value", "1"],
+    );
+    assert!(next().ends_with("] 1.0 km = 1000.0 m"));
+    run_in(
+        dir.path(),
+        &["convert", "--from", "m", "--to", "cm", "--value", "1"],
+    );
+    assert!(next().ends_with("] 1.0 m = 100.0 cm"));
+
+    follow.kill().unwrap();
+    let output: Output = follow.wait_with_output().unwrap();
+    let stderr: String = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(
+        stderr.matches("does not match its checksum").count(),
+        1,
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn seed_history_adds_the_requested_records() {
+    let dir: TempDir = TempDir::new().unwrap();
+    let seeded: Run = run_in(dir.path(), &["seed-history", "5"]);
+    assert_eq!(
+        seeded.stdout,
+        "Added 5 sample conversions to the history.\n"
+    );
+
+    let result: Run = run_in(dir.path(), &["history"]);
+    let records: Vec<&str> = result.stdout.lines().skip(1).collect();
+    assert_eq!(records.len(), 5, "{}", result.stdout);
+
+    run_in(dir.path(), &["seed-history", "200"]);
+    let result: Run = run_in(dir.path(), &["history"]);
+    assert!(!result.stdout.contains(" = 0.0 "), "{}", result.stdout);
+}
+
+#[test]
+fn value_range_filters_records() {
+    let dir: TempDir = TempDir::new().unwrap();
+    for value in ["50", "100", "150"] {
+        run_in(
+            dir.path(),
+            &[
+                "convert",
+                "--from",
+                "celsius",
+                "--to",
+                "fahrenheit",
+                "--value",
+                value,
+            ],
+        );
+    }
+
+    for args in [
+        &["history", "--value-range", "90..110"][..],
+        &["history", "--value-range", "200..250", "--match", "output"],
+    ] {
+        let result: Run = run_in(dir.path(), args);
+        let records: Vec<&str> = result.stdout.lines().skip(1).collect();
+        assert_eq!(records.len(), 1, "{:?}: {}", args, result.stdout);
+        assert!(records[0].starts_with("2. ") && records[0].ends_with("] 100.0 °C = 212.0 °F"));
+    }
+}
+
+#[test]
+fn timestamps_are_shown_in_the_requested_timezone() {
+    let dir: TempDir = TempDir::new().unwrap();
+    fs::create_dir(dir.path().join("data")).unwrap();
+    // 2024-07-04 12:00:00 UTC and 2024-01-15 12:00:00 UTC.
+    fs::write(
+        dir.path().join("data/history.json"),
+        r#"{"list": [
+            {"value": "1.0 km = 1000.0 m", "timestamp": 1720094400},
+            {"value": "2.0 km = 2000.0 m", "timestamp": 1705320000}
+        ]}"#,
+    )
+    .unwrap();
+
+    let result: Run = run_in(dir.path(), &["history", "--timezone", "America/New_York"]);
+    assert_eq!(
+        result.stdout,
+        "Conversion History:\n\
+         1. [2024-07-04 08:00:00 -04:00] 1.0 km = 1000.0 m\n\
+         2. [2024-01-15 07:00:00 -05:00] 2.0 km = 2000.0 m\n"
+    );
+    let utc: Run = run_in(dir.path(), &["history"]);
+    assert!(
+        utc.stdout.contains("1. [2024-07-04 12:00:00 +00:00]"),
+        "{}",
+        utc.stdout
+    );
+}
+
+#[test]
+fn failed_command_saves_only_with_commit_partial() {
+    let dir: TempDir = TempDir::new().unwrap();
+    let args: [&str; 7] = [
+        "convert",
+        "--from",
+        "km",
+        "--to",
+        "m,celsius,cm",
+        "--value",
+        "1",
+    ];
+    let failed: Run = run_in(dir.path(), &args);
+    assert_eq!(failed.code, 1);
+    assert_eq!(failed.stdout, "1.0 km = 1000.0 m\n");
+    let empty: Run = run_in(dir.path(), &["history"]);
+    assert_eq!(empty.stderr, "No conversion history found.\n");
+
+    let partial: Run = run_in(dir.path(), &[&args[..], &["--commit-partial"]].concat());
+    assert_eq!(partial.code, 1);
+    let history: Run = run_in(dir.path(), &["history"]);
+    let records: Vec<&str> = history.stdout.lines().skip(1).collect();
+    assert_eq!(records.len(), 1, "{}", history.stdout);
+    assert!(records[0].ends_with("] 1.0 km = 1000.0 m"));
+}
+
+#[test]
+fn long_lines_are_truncated_to_the_maximum_width() {
+    let dir: TempDir = TempDir::new().unwrap();
+    fs::create_dir(dir.path().join("data")).unwrap();
+    fs::write(
+        dir.path().join("data/history.json"),
+        r#"{"list": [
+            {"value": "1.0 km = 1000.0 m", "timestamp": 1720094400,
+             "note": "a fairly long note that would wrap in a narrow terminal"}
+        ]}"#,
+    )
+    .unwrap();
+
+    let narrow: Run = run_in(dir.path(), &["history", "--max-width", "40"]);
+    let line: &str = narrow.stdout.lines().nth(1).unwrap();
+    assert_eq!(line, "1. [2024-07-04 12:00:00 +00:00] 1.0 km …");
+    assert_eq!(line.chars().count(), 40);
+
+    let unlimited: Run = run_in(dir.path(), &["history", "--max-width", "0"]);
+    assert!(
+        unlimited.stdout.ends_with(
+            "1.0 km = 1000.0 m (a fairly long note that would wrap in a narrow terminal)\n"
+        ),
+        "{}",
+        unlimited.stdout
+    );
+}
+
+#[test]
+fn suggestions_prefer_recently_used_units() {
+    let dir: TempDir = TempDir::new().unwrap();
+    // "celsin" is two edits away from both "celsius" and "kelvin".
+    let typo: [&str; 7] = ["convert", "--from", "celsin", "--to", "f", "--value", "1"];
+    run_in(
+        dir.path(),
+        &["convert", "--from", "kelvin", "--to", "f", "--value", "1"],
+    );
+    let result: Run = run_in(dir.path(), &typo);
+    assert_eq!(
+        result.stderr,
+        "Error: [ERROR] Source unit 'celsin' not recognized. Did you mean 'kelvin'?\n"
+    );
+
+    for _ in 0..3 {
+        run_in(
+            dir.path(),
+            &["convert", "--from", "celsius", "--to", "f", "--value", "1"],
+        );
+    }
+    let result: Run = run_in(dir.path(), &typo);
+    assert_eq!(
+        result.stderr,
+        "Error: [ERROR] Source unit 'celsin' not recognized. Did you mean 'celsius'?\n"
+    );
+}
+
+#[test]
+fn page_shows_one_chunk_of_the_history() {
+    let dir: TempDir = TempDir::new().unwrap();
+    run_in(dir.path(), &["seed-history", "25"]);
+
+    let result: Run = run_in(dir.path(), &["history", "--page", "2", "--page-size", "10"]);
+    let lines: Vec<&str> = result.stdout.lines().collect();
+    assert_eq!(lines.len(), 12, "{}", result.stdout);
+    let numbers: Vec<&str> = lines[1..11]
+        .iter()
+        .map(|line| line.split_once(". ").unwrap().0)
+        .collect();
+    assert_eq!(
+        numbers,
+        ["11", "12", "13", "14", "15", "16", "17", "18", "19", "20"]
+    );
+    assert_eq!(lines[11], "Page 2/3");
+
+    let missing: Run = run_in(dir.path(), &["history", "--page", "4", "--page-size", "10"]);
+    assert_eq!(missing.code, 1);
+}
+
+#[test]
+fn tampered_records_are_reported() {
+    let dir: TempDir = TempDir::new().unwrap();
+    for value in ["1", "2"] {
+        run_in(
+            dir.path(),
+            &["convert", "--from", "km", "--to", "m", "--value", value],
+        );
+    }
+    let intact: Run = run_in(dir.path(), &["history"]);
+    assert_eq!(intact.stderr, "");
+
+    let path: PathBuf = dir.path().join("data/history.json");
+    let content: String = fs::read_to_string(&path).unwrap();
+    fs::write(
+        &path,
+        content.replace("2.0 km = 2000.0 m", "2.0 km = 9999.0 m"),
+    )
+    .unwrap();
+    let tampered: Run = run_in(dir.path(), &["history"]);
+    assert_eq!(
+        tampered.stderr,
+        "Warning: [WARNING] History file 'data/history.json', record 2: '2.0 km = 9999.0 m' does not match its checksum; it may have been edited by hand.\n"
+    );
+    assert_eq!(tampered.stdout.lines().count(), 3);
+}
+
+#[test]
+fn export_writes_each_format() {
+    let dir: TempDir = TempDir::new().unwrap();
+    let export = |format: &str| -> String {
+        let file: String = format!("export.{}", format);
+        let result: Run = run_in(
+            dir.path(),
+            &["history", "export", "--format", format, "--file", &file],
+        );
+        assert_eq!(result.code, 0, "{}", result.stderr);
+        return fs::read_to_string(dir.path().join(file)).unwrap();
+    };
+    assert_eq!(export("json").trim_end(), "[]");
+    assert_eq!(export("csv"), "value,from,to,input,output,note,timestamp\n");
+    assert_eq!(export("md").lines().count(), 2);
+
+    fs::create_dir(dir.path().join("data")).unwrap();
+    fs::write(
+        dir.path().join("data/history.json"),
+        r#"{"list": [
+            {"value": "1.0 km = 1000.0 m", "from": "km", "to": "m",
+             "input": 1.0, "output": 1000.0, "timestamp": 1720094400}
+        ]}"#,
+    )
+    .unwrap();
+    let json: serde_json::Value = serde_json::from_str(&export("json")).unwrap();
+    assert_eq!(json[0]["value"], "1.0 km = 1000.0 m");
+    assert_eq!(json[0]["output"], 1000.0);
+    assert_eq!(
+        export("csv"),
+        "value,from,to,input,output,note,timestamp\n\
+         1.0 km = 1000.0 m,km,m,1,1000,,1720094400\n"
+    );
+    assert_eq!(
+        export("md"),
+        "| Conversion        | From | To  | Input | Output | Note | Timestamp                  |\n\
+         | ----------------- | ---- | --- | ----- | ------ | ---- | -------------------------- |\n\
+         | 1.0 km = 1000.0 m | km   | m   | 1     | 1000   |      | 2024-07-04 12:00:00 +00:00 |\n"
+    );
+}
+
+#[test]
+fn jsonl_history_appends_one_record_per_line() {
+    let dir: TempDir = TempDir::new().unwrap();
+    fs::create_dir(dir.path().join("data")).unwrap();
+    fs::write(
+        dir.path().join("data/config.toml"),
+        "history_format = \"jsonl\"\n",
+    )
+    .unwrap();
+    run_in(
+        dir.path(),
+        &["convert", "--from", "km", "--to", "m", "--value", "1"],
+    );
+    let path: PathBuf = dir.path().join("data/history.jsonl");
+    let first: String = fs::read_to_string(&path).unwrap();
+    run_in(
+        dir.path(),
+        &["convert", "--from", "c", "--to", "f", "--value", "100"],
+    );
+    let both: String = fs::read_to_string(&path).unwrap();
+    assert!(both.starts_with(&first));
+    assert_eq!(both.lines().count(), 2);
+    for line in both.lines() {
+        let record: serde_json::Value = serde_json::from_str(line).unwrap();
+        assert!(record["value"].is_string(), "{}", line);
+    }
+    assert!(!dir.path().join("data/history.json").exists());
+
+    let history: Run = run_in(dir.path(), &["history"]);
+    let records: Vec<&str> = history.stdout.lines().skip(1).collect();
+    assert_eq!(records.len(), 2, "{}", history.stdout);
+    assert!(records[0].ends_with("] 1.0 km = 1000.0 m"));
+    assert!(records[1].ends_with("] 100.0 °C = 212.0 °F"));
+}
+
+#[test]
+fn normalize_rewrites_records_with_current_symbols() {
+    let dir: TempDir = TempDir::new().unwrap();
+    fs::create_dir(dir.path().join("data")).unwrap();
+    fs::write(
+        dir.path().join("data/history.json"),
+        r#"{"list": [
+            {"value": "10.0 kilometer = 6.2137 mile", "from": "kilometer", "to": "mile",
+             "input": 10.0, "output": 6.2137, "timestamp": 1720094400},
+            {"value": "legacy line", "timestamp": 1720094400}
+        ]}"#,
+    )
+    .unwrap();
+
+    let result: Run = run_in(dir.path(), &["history", "normalize"]);
+    assert_eq!(
+        result.stdout,
+        "Normalized 1 record(s); skipped 1 record(s) without recognized units.\n"
+    );
+    let history: Run = run_in(dir.path(), &["history"]);
+    assert_eq!(
+        history.stdout,
+        "Conversion History:\n\
+         1. [2024-07-04 12:00:00 +00:00] 10.0 km = 6.2137 miles\n\
+         2. [2024-07-04 12:00:00 +00:00] legacy line\n"
+    );
+}
+
+#[test]
+fn audit_log_survives_history_clear() {
+    let dir: TempDir = TempDir::new().unwrap();
+    fs::create_dir(dir.path().join("data")).unwrap();
+    fs::write(
+        dir.path().join("data/config.toml"),
+        "audit_log = \"data/audit.jsonl\"\n",
+    )
+    .unwrap();
+    run_in(
+        dir.path(),
+        &["convert", "--from", "km", "--to", "m", "--value", "1"],
+    );
+    run_in(
+        dir.path(),
+        &["convert", "--from", "c", "--to", "f", "--value", "100"],
+    );
+
+    let cleared: Run = run_in(dir.path(), &["history", "clear"]);
+    assert_eq!(cleared.stdout, "Cleared 2 record(s) from the history.\n");
+    let history: Run = run_in(dir.path(), &["history"]);
+    assert_eq!(history.stderr, "No conversion history found.\n");
+
+    let audit: String = fs::read_to_string(dir.path().join("data/audit.jsonl")).unwrap();
+    let values: Vec<String> = audit
+        .lines()
+        .map(|line| {
+            let record: serde_json::Value = serde_json::from_str(line).unwrap();
+            return record["value"].as_str().unwrap().to_string();
+        })
+        .collect();
+    assert_eq!(values, ["1.0 km = 1000.0 m", "100.0 °C = 212.0 °F"]);
+}
+
+#[test]
+fn pinned_records_survive_rotation() {
+    let dir: TempDir = TempDir::new().unwrap();
+    fs::create_dir(dir.path().join("data")).unwrap();
+    fs::write(dir.path().join("data/config.toml"), "history_limit = 3\n").unwrap();
+    let convert = |value: &str| {
+        run_in(
+            dir.path(),
+            &["convert", "--from", "km", "--to", "m", "--value", value],
+        );
+    };
+    for value in ["1", "2", "3"] {
+        convert(value);
+    }
+    let pinned: Run = run_in(dir.path(), &["history", "pin", "1"]);
+    assert_eq!(pinned.stdout, "Pinned record 1: 1.0 km = 1000.0 m\n");
+    for value in ["4", "5", "6"] {
+        convert(value);
+    }
+
+    let history: Run = run_in(dir.path(), &["history"]);
+    let records: Vec<&str> = history
+        .stdout
+        .lines()
+        .skip(1)
+        .map(|line| line.split_once("] ").unwrap().1)
+        .collect();
+    assert_eq!(
+        records,
+        [
+            "1.0 km = 1000.0 m",
+            "5.0 km = 5000.0 m",
+            "6.0 km = 6000.0 m"
+        ]
+    );
+    assert!(history.stdout.contains("\n1. ★ ["), "{}", history.stdout);
+}
+
+#[test]
+fn dedupe_removes_repeated_conversions() {
+    for (keep, expected) in [
+        (
+            "first",
+            [
+                "1.0 km = 1000.0 m",
+                "2.0 km = 2000.0 m",
+                "3.0 km = 3000.0 m",
+            ],
+        ),
+        (
+            "last",
+            [
+                "3.0 km = 3000.0 m",
+                "2.0 km = 2000.0 m",
+                "1.0 km = 1000.0 m",
+            ],
+        ),
+    ] {
+        let dir: TempDir = TempDir::new().unwrap();
+        for value in ["1", "2", "1", "3", "2", "1"] {
+            run_in(
+                dir.path(),
+                &["convert", "--from", "km", "--to", "m", "--value", value],
+            );
+        }
+        let result: Run = run_in(dir.path(), &["history", "dedupe", "--keep", keep]);
+        assert_eq!(
+            result.stdout,
+            "Removed 3 duplicate record(s) from the history.\n"
+        );
+
+        let history: Run = run_in(dir.path(), &["history"]);
+        let records: Vec<&str> = history
+            .stdout
+            .lines()
+            .skip(1)
+            .map(|line| line.split_once("] ").unwrap().1)
+            .collect();
+        assert_eq!(records, expected, "--keep {}", keep);
+    }
+}
@@ -0,0 +1,125 @@
+//! Tests of the public library API, used the way a downstream crate would.
+
+#![allow(clippy::needless_return)]
+
+use std::fmt::{Display, Formatter};
+use unitconv::{
+    Conversion, Convertible, Unit, UnitDimension, base_unit, convert, convert_detailed,
+    convert_many,
+};
+
+/// A length unit defined outside of the crate: the furlong (201.168 m).
+struct Furlong;
+
+impl Display for Furlong {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "fur")
+    }
+}
+
+impl Convertible for Furlong {
+    fn to_base(&self, v: f64) -> f64 {
+        return v * 20116.8;
+    }
+
+    fn from_base(&self, v: f64) -> f64 {
+        return v / 20116.8;
+    }
+
+    fn dimension(&self) -> &str {
+        return "length";
+    }
+}
+
+/// The US survey mile (5280 US survey feet), named like the statute mile.
+struct SurveyMile;
+
+impl Display for SurveyMile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "miles")
+    }
+}
+
+impl Convertible for SurveyMile {
+    fn to_base(&self, v: f64) -> f64 {
+        return v * 160934.72;
+    }
+
+    fn from_base(&self, v: f64) -> f64 {
+        return v / 160934.72;
+    }
+
+    fn dimension(&self) -> &str {
+        return "length";
+    }
+}
+
+#[test]
+fn custom_convertible_converts_against_built_in_units() {
+    let centimeters: f64 = convert(&Furlong, &Unit::Centimeter, &2.0).unwrap();
+    assert!((centimeters - 40233.6).abs() < 1e-9, "{}", centimeters);
+    let furlongs: f64 = convert(&Unit::Kilometer, &Furlong, &1.0).unwrap();
+    assert!((furlongs - 4.970969538).abs() < 1e-9, "{}", furlongs);
+    assert!(convert(&Furlong, &Unit::Celsius, &1.0).is_err());
+}
+
+#[test]
+fn units_with_the_same_name_are_still_converted() {
+    let statute: f64 = convert(&SurveyMile, &Unit::Mile, &1.0).unwrap();
+    let expected: f64 = 160934.72 / Unit::Mile.to_base(1.0);
+    assert!((statute - expected).abs() < 1e-12, "{}", statute);
+    assert!(statute > 1.0);
+    assert_eq!(convert(&Unit::Mile, &Unit::Mile, &1.5).unwrap(), 1.5);
+}
+
+#[test]
+#[cfg(feature = "wasm")]
+fn convert_str_takes_unit_names() {
+    assert_eq!(
+        unitconv::convert_str("celsius", "fahrenheit", 100.0),
+        Ok(212.0)
+    );
+    let error: String = unitconv::convert_str("celsius", "furlong", 100.0).unwrap_err();
+    assert!(!error.starts_with("Error: [ERROR]"), "{}", error);
+    assert!(unitconv::convert_str("celsius", "km", 100.0).is_err());
+}
+
+#[test]
+fn convert_many_keeps_the_target_order() {
+    let targets: [Unit; 3] = [Unit::Fahrenheit, Unit::Kelvin, Unit::Meter];
+    let results: Vec<anyhow::Result<f64>> = convert_many(&Unit::Celsius, &targets, 100.0);
+    assert_eq!(results.len(), 3);
+    assert_eq!(*results[0].as_ref().unwrap(), 212.0);
+    assert!((results[1].as_ref().unwrap() - 373.15).abs() < 1e-9);
+    assert!(results[2].is_err());
+}
+
+#[test]
+fn convert_detailed_fills_every_field() {
+    let conversion: Conversion = convert_detailed(&Unit::Fahrenheit, &Unit::Kelvin, 212.0).unwrap();
+    assert_eq!(conversion.input, 212.0);
+    assert!(
+        (conversion.output - 373.15).abs() < 1e-9,
+        "{}",
+        conversion.output
+    );
+    assert!(conversion.from == Unit::Fahrenheit);
+    assert!(conversion.to == Unit::Kelvin);
+    assert!(
+        (conversion.base_value - 100.0).abs() < 1e-9,
+        "{}",
+        conversion.base_value
+    );
+    assert!(convert_detailed(&Unit::Fahrenheit, &Unit::Meter, 212.0).is_err());
+}
+
+#[test]
+fn base_unit_matches_the_factor_table() {
+    assert!(base_unit(&UnitDimension::Length) == Unit::Centimeter);
+    assert!(base_unit(&UnitDimension::Temperature) == Unit::Celsius);
+    for dimension in UnitDimension::all_dimensions().iter() {
+        let base: Unit = base_unit(dimension);
+        assert!(base.dimension() == *dimension);
+        assert_eq!(base.to_base(1.0), 1.0, "{}", base);
+    }
+}